@@ -50,12 +50,1033 @@
 // Main author of the code: Theodore Kay Chen Ong, supervised by
 // Professor Per F. Peterson
 extern crate fluid_mechanics_rust;
-//use fluid_mechanics_rust::prelude::*;
+use fluid_mechanics_rust::prelude::*;
 
+use crate::therminol_pipe::TherminolPipe;
+use crate::therminol_component::TherminolCustomComponent;
+use crate::manometer::*;
+use crate::component_readings::{ComponentReading, calculate_component_reading};
+use crate::pump::CentrifugalPump;
+use crate::check_valve::CheckValve;
+use crate::energy_equation::calculate_upwind_control_volume_temperature_with_ambient_loss;
+use crate::energy_equation::get_temperature_degrees_c;
+use crate::insulation::InsulationParameters;
+use crate::fluid_properties::FluidProperties;
+use crate::pipe_wall::{PipeWall, WallParameters, HeatTransferCorrelation,
+    calculate_fluid_wall_heat_transfer_coefficient};
+
+use crate::ciet_solver::{CIETSolverError, 
+    calculate_branch_mass_flowrate_from_pressure_change_with_bracket,
+    check_component_fluid_properties};
+
+use std::cell::Cell;
+use std::sync::Arc;
+use std::time::Duration;
 
 
 /// a branch is a collection of pipes and components
 pub trait TherminolSeriesComponents {}
 
+/// an owned component within a TherminolBranch
+///
+/// the CTAHBranch, HeaterBranch and DHXBranch structs only hold
+/// references to components (Vec<&dyn FluidComponent>), so 
+/// whoever constructs them must keep the components alive 
+/// somewhere else.
+///
+/// Here, the branch owns its components, so it can be stored
+/// and reused without any lifetimes
+pub enum TherminolBranchComponent {
+    Pipe(TherminolPipe),
+    CustomComponent(TherminolCustomComponent<'static>),
+    Pump(CentrifugalPump),
+    CheckValve(CheckValve),
+}
+
+impl TherminolBranchComponent {
+
+    /// returns the name of the component
+    pub fn get_name(&self) -> &str {
+        match self {
+            Self::Pipe(pipe) => pipe.get_name(),
+            Self::CustomComponent(component) => component.get_name(),
+            Self::Pump(pump) => pump.get_name(),
+            Self::CheckValve(check_valve) => check_valve.get_name(),
+        }
+    }
+
+    /// returns the component as an immutable fluid component
+    /// trait object
+    ///
+    /// for pumps and check valves, this is the underlying 
+    /// component, so the pressure rise from the pump or the 
+    /// pressure change across the valve seat is not included, use
+    /// get_pressure_change for that
+    pub fn as_fluid_component(&self) -> &dyn FluidComponent {
+        match self {
+            Self::Pipe(pipe) => pipe as &dyn FluidComponent,
+            Self::CustomComponent(component) => component as &dyn FluidComponent,
+            Self::Pump(pump) => pump.get_component() as &dyn FluidComponent,
+            Self::CheckValve(check_valve) => 
+                check_valve.get_housing() as &dyn FluidComponent,
+        }
+    }
+
+    /// sets the internal pressure source (eg. pump pressure)
+    ///
+    /// for pumps, this switches the pump to a fixed pressure 
+    /// source
+    pub fn set_internal_pressure_source(&mut self, internal_pressure: Pressure){
+        match self {
+            Self::Pipe(pipe) => 
+                pipe.set_internal_pressure_source(internal_pressure),
+            Self::CustomComponent(component) => 
+                component.set_internal_pressure_source(internal_pressure),
+            Self::Pump(pump) => 
+                pump.set_pressure_source(internal_pressure),
+            Self::CheckValve(check_valve) => 
+                check_valve.get_housing_mut().
+                set_internal_pressure_source(internal_pressure),
+        }
+    }
+
+    /// returns the fluid temperature of the component
+    pub fn get_fluid_temperature(&self) -> ThermodynamicTemperature {
+        match self {
+            Self::Pipe(pipe) => pipe.get_fluid_temperature(),
+            Self::CustomComponent(component) => component.get_fluid_temperature(),
+            Self::Pump(pump) => pump.get_component().get_fluid_temperature(),
+            Self::CheckValve(check_valve) => 
+                check_valve.get_housing().get_fluid_temperature(),
+        }
+    }
+
+    /// sets the fluid temperature of the component, density
+    /// and viscosity follow this temperature
+    pub fn set_fluid_temperature(&mut self, fluid_temp: ThermodynamicTemperature){
+        match self {
+            Self::Pipe(pipe) => pipe.set_fluid_temperature(fluid_temp),
+            Self::CustomComponent(component) => 
+                component.set_fluid_temperature(fluid_temp),
+            Self::Pump(pump) => 
+                pump.get_component_mut().set_fluid_temperature(fluid_temp),
+            Self::CheckValve(check_valve) => 
+                check_valve.get_housing_mut().set_fluid_temperature(fluid_temp),
+        }
+    }
+
+    /// returns the working fluid properties of the component
+    pub fn get_fluid_properties(&self) -> &dyn FluidProperties {
+        match self {
+            Self::Pipe(pipe) => pipe.get_fluid_properties(),
+            Self::CustomComponent(component) => component.get_fluid_properties(),
+            Self::Pump(pump) => pump.get_component().get_fluid_properties(),
+            Self::CheckValve(check_valve) => 
+                check_valve.get_housing().get_fluid_properties(),
+        }
+    }
+
+    /// returns the shared working fluid of the component
+    pub fn get_fluid_properties_arc(&self) -> Arc<dyn FluidProperties> {
+        match self {
+            Self::Pipe(pipe) => pipe.get_fluid_properties_arc(),
+            Self::CustomComponent(component) => component.get_fluid_properties_arc(),
+            Self::Pump(pump) => pump.get_component().get_fluid_properties_arc(),
+            Self::CheckValve(check_valve) => 
+                check_valve.get_housing().get_fluid_properties_arc(),
+        }
+    }
+
+    /// sets the working fluid of the component
+    pub fn set_fluid_properties(&mut self, 
+                                fluid_properties: Arc<dyn FluidProperties>){
+        match self {
+            Self::Pipe(pipe) => pipe.set_fluid_properties(fluid_properties),
+            Self::CustomComponent(component) => 
+                component.set_fluid_properties(fluid_properties),
+            Self::Pump(pump) => 
+                pump.get_component_mut().set_fluid_properties(fluid_properties),
+            Self::CheckValve(check_valve) => 
+                check_valve.get_housing_mut().set_fluid_properties(fluid_properties),
+        }
+    }
+
+    /// checks the fluid temperature against the validated range
+    /// of the component's fluid property correlations
+    pub fn check_fluid_temperature_range(&self) -> Result<(), CIETSolverError> {
+        match self {
+            Self::Pipe(pipe) => pipe.check_fluid_temperature_range(),
+            Self::CustomComponent(component) => 
+                component.check_fluid_temperature_range(),
+            Self::Pump(pump) => 
+                pump.get_component().check_fluid_temperature_range(),
+            Self::CheckValve(check_valve) => 
+                check_valve.get_housing().check_fluid_temperature_range(),
+        }
+    }
+
+    /// wraps the component in insulation, so that it loses 
+    /// heat to ambient
+    pub fn set_insulation(&mut self, insulation: InsulationParameters){
+        match self {
+            Self::Pipe(pipe) => pipe.set_insulation(insulation),
+            Self::CustomComponent(component) => 
+                component.set_insulation(insulation),
+            Self::Pump(pump) => 
+                pump.get_component_mut().set_insulation(insulation),
+            Self::CheckValve(check_valve) => 
+                check_valve.get_housing_mut().set_insulation(insulation),
+        }
+    }
+
+    /// removes the insulation from the component
+    pub fn clear_insulation(&mut self){
+        match self {
+            Self::Pipe(pipe) => pipe.clear_insulation(),
+            Self::CustomComponent(component) => component.clear_insulation(),
+            Self::Pump(pump) => pump.get_component_mut().clear_insulation(),
+            Self::CheckValve(check_valve) => 
+                check_valve.get_housing_mut().clear_insulation(),
+        }
+    }
+
+    /// returns the conductance UA (W/K) from the fluid to 
+    /// ambient, zero if the component is not insulated
+    pub fn get_ambient_conductance_watts_per_kelvin(&self) -> f64 {
+        match self {
+            Self::Pipe(pipe) => pipe.get_ambient_conductance_watts_per_kelvin(),
+            Self::CustomComponent(component) => 
+                component.get_ambient_conductance_watts_per_kelvin(),
+            Self::Pump(pump) => 
+                pump.get_component().get_ambient_conductance_watts_per_kelvin(),
+            Self::CheckValve(check_valve) => 
+                check_valve.get_housing().get_ambient_conductance_watts_per_kelvin(),
+        }
+    }
+
+    /// gives the component a wall which stores heat, starting 
+    /// at the fluid temperature
+    pub fn set_wall(&mut self, wall_parameters: WallParameters){
+        match self {
+            Self::Pipe(pipe) => pipe.set_wall(wall_parameters),
+            Self::CustomComponent(component) => component.set_wall(wall_parameters),
+            Self::Pump(pump) => pump.get_component_mut().set_wall(wall_parameters),
+            Self::CheckValve(check_valve) => 
+                check_valve.get_housing_mut().set_wall(wall_parameters),
+        }
+    }
+
+    /// removes the wall from the component
+    pub fn clear_wall(&mut self){
+        match self {
+            Self::Pipe(pipe) => pipe.clear_wall(),
+            Self::CustomComponent(component) => component.clear_wall(),
+            Self::Pump(pump) => pump.get_component_mut().clear_wall(),
+            Self::CheckValve(check_valve) => 
+                check_valve.get_housing_mut().clear_wall(),
+        }
+    }
+
+    pub fn get_wall(&self) -> Option<&PipeWall> {
+        match self {
+            Self::Pipe(pipe) => pipe.get_wall(),
+            Self::CustomComponent(component) => component.get_wall(),
+            Self::Pump(pump) => pump.get_component().get_wall(),
+            Self::CheckValve(check_valve) => check_valve.get_housing().get_wall(),
+        }
+    }
+
+    pub fn get_wall_mut(&mut self) -> Option<&mut PipeWall> {
+        match self {
+            Self::Pipe(pipe) => pipe.get_wall_mut(),
+            Self::CustomComponent(component) => component.get_wall_mut(),
+            Self::Pump(pump) => pump.get_component_mut().get_wall_mut(),
+            Self::CheckValve(check_valve) => 
+                check_valve.get_housing_mut().get_wall_mut(),
+        }
+    }
+
+    /// returns the correlation for heat transfer between the 
+    /// fluid and the wall, pipes are always bare tubes
+    pub fn get_heat_transfer_correlation(&self) -> HeatTransferCorrelation {
+        match self {
+            Self::Pipe(_) => HeatTransferCorrelation::Tube,
+            Self::CustomComponent(component) => 
+                component.get_heat_transfer_correlation(),
+            Self::Pump(pump) => 
+                pump.get_component().get_heat_transfer_correlation(),
+            Self::CheckValve(check_valve) => 
+                check_valve.get_housing().get_heat_transfer_correlation(),
+        }
+    }
+
+    /// returns the temperature of the surface the insulation is
+    /// wrapped around, this is the outer wall node if there is
+    /// a wall, and the fluid otherwise
+    pub fn get_insulated_surface_temperature(&self) -> ThermodynamicTemperature {
+        match self.get_wall() {
+            Some(wall) => wall.get_outer_node_temperature(),
+            None => self.get_fluid_temperature(),
+        }
+    }
+
+    /// returns the pressure change across the component given
+    /// a mass flowrate
+    pub fn get_pressure_change(&self, mass_flowrate: MassRate) -> Pressure {
+        match self {
+            Self::Pump(pump) => pump.get_pressure_change(mass_flowrate),
+            Self::CheckValve(check_valve) => 
+                check_valve.get_pressure_change(mass_flowrate),
+            _ => self.as_fluid_component().
+                get_pressure_change_immutable(mass_flowrate),
+        }
+    }
+}
+
+impl From<TherminolPipe> for TherminolBranchComponent {
+    fn from(pipe: TherminolPipe) -> Self {
+        return Self::Pipe(pipe);
+    }
+}
+
+impl From<TherminolCustomComponent<'static>> for TherminolBranchComponent {
+    fn from(component: TherminolCustomComponent<'static>) -> Self {
+        return Self::CustomComponent(component);
+    }
+}
+
+impl From<CentrifugalPump> for TherminolBranchComponent {
+    fn from(pump: CentrifugalPump) -> Self {
+        return Self::Pump(pump);
+    }
+}
+
+impl From<CheckValve> for TherminolBranchComponent {
+    fn from(check_valve: CheckValve) -> Self {
+        return Self::CheckValve(check_valve);
+    }
+}
+
+/// a series of pipes and components which owns all of its 
+/// components
+///
+/// the components are ordered from the top header to the bottom
+/// header, as with the other ciet branches
+pub struct TherminolBranch {
+
+    name: String,
+    components: Vec<TherminolBranchComponent>,
+
+    // number of times get_pressure_change has been called, 
+    // this is how solver effort is measured
+    pressure_change_evaluations: Cell<usize>,
+
+    // heat added to the fluid in each component (watts),
+    // same order as components
+    component_heat_inputs_watts: Vec<f64>,
+}
+
+impl TherminolSeriesComponents for TherminolBranch {}
+
+impl TherminolBranch {
+
+    /// constructor, returns a branch which owns the components
+    /// given
+    pub fn new(name: &str, 
+               components: Vec<TherminolBranchComponent>) -> Self {
+
+        let number_of_components = components.len();
+
+        return Self { 
+            name: name.to_string(), 
+            components: components, 
+            pressure_change_evaluations: Cell::new(0),
+            component_heat_inputs_watts: vec![0.0; number_of_components],
+        };
+    }
+
+    pub fn get_name(&self) -> &str {
+        return &self.name;
+    }
+
+    /// sets every component in this branch to the same fluid 
+    /// temperature, the walls (if any) are set to this 
+    /// temperature too
+    pub fn set_fluid_temperature(&mut self, fluid_temp: ThermodynamicTemperature){
+        for component in self.components.iter_mut() {
+            component.set_fluid_temperature(fluid_temp);
+
+            if let Some(wall) = component.get_wall_mut() {
+                wall.set_uniform_temperature(fluid_temp);
+            }
+        }
+    }
+
+    /// sets the fluid temperature of the component at the given
+    /// position, its wall (if any) is set to this temperature 
+    /// too, so that it does not start out exchanging heat with 
+    /// a wall at some other temperature
+    pub fn set_component_fluid_temperature(
+        &mut self,
+        component_index: usize,
+        fluid_temp: ThermodynamicTemperature){
+
+        let component = &mut self.components[component_index];
+
+        component.set_fluid_temperature(fluid_temp);
+
+        if let Some(wall) = component.get_wall_mut() {
+            wall.set_uniform_temperature(fluid_temp);
+        }
+    }
+
+    /// returns the fluid temperature of every component from 
+    /// top header to bottom header
+    pub fn get_component_fluid_temperatures(&self) -> Vec<ThermodynamicTemperature> {
+        return self.components.iter().
+            map(|component| component.get_fluid_temperature()).
+            collect();
+    }
+
+    /// sets the heat added to the fluid in the component at the
+    /// given position (watts), eg. the electrical power of a 
+    /// heater. Negative values remove heat
+    pub fn set_component_heat_input_watts(
+        &mut self,
+        component_index: usize,
+        heat_input_watts: f64){
+
+        self.component_heat_inputs_watts[component_index] = heat_input_watts;
+    }
+
+    pub fn get_component_heat_input_watts(&self, component_index: usize) -> f64 {
+        return self.component_heat_inputs_watts[component_index];
+    }
+
+    /// wraps every component in this branch in the same 
+    /// insulation
+    pub fn set_insulation(&mut self, insulation: InsulationParameters){
+        for component in self.components.iter_mut() {
+            component.set_insulation(insulation);
+        }
+    }
+
+    /// wraps the component at the given position in insulation
+    pub fn set_component_insulation(
+        &mut self,
+        component_index: usize,
+        insulation: InsulationParameters){
+
+        self.components[component_index].set_insulation(insulation);
+    }
+
+    /// removes the insulation from every component in this 
+    /// branch
+    pub fn clear_insulation(&mut self){
+        for component in self.components.iter_mut() {
+            component.clear_insulation();
+        }
+    }
+
+    /// gives every component in this branch the same kind of 
+    /// wall
+    pub fn set_wall(&mut self, wall_parameters: WallParameters){
+        for component in self.components.iter_mut() {
+            component.set_wall(wall_parameters);
+        }
+    }
+
+    /// gives the component at the given position a wall
+    pub fn set_component_wall(
+        &mut self,
+        component_index: usize,
+        wall_parameters: WallParameters){
+
+        self.components[component_index].set_wall(wall_parameters);
+    }
+
+    /// removes the walls from every component in this branch
+    pub fn clear_walls(&mut self){
+        for component in self.components.iter_mut() {
+            component.clear_wall();
+        }
+    }
+
+    /// returns the wall node temperatures of the component at 
+    /// the given position from the inside outwards, if it has 
+    /// a wall
+    pub fn get_component_wall_temperatures(
+        &self,
+        component_index: usize) -> Option<Vec<ThermodynamicTemperature>> {
+
+        return self.components[component_index].get_wall().
+            map(|wall| wall.get_node_temperatures().clone());
+    }
+
+    /// sets the heat generated within the wall of the component
+    /// at the given position (watts), eg. the electrical power 
+    /// of a heater, returns false if the component has no wall
+    pub fn set_component_wall_heat_input_watts(
+        &mut self,
+        component_index: usize,
+        heat_input_watts: f64) -> bool {
+
+        match self.components[component_index].get_wall_mut() {
+            Some(wall) => {
+                wall.set_heat_input_watts(heat_input_watts);
+                return true;
+            },
+            None => return false,
+        }
+    }
+
+    /// returns the heat generated within the wall of the 
+    /// component at the given position, zero if it has no wall
+    pub fn get_component_wall_heat_input_watts(&self, component_index: usize) -> f64 {
+        return self.components[component_index].get_wall().
+            map(|wall| wall.get_heat_input_watts()).
+            unwrap_or(0.0);
+    }
+
+    /// returns the heat lost to ambient (watts) from the 
+    /// component at the given position, at its present fluid 
+    /// (or outer wall) temperature
+    pub fn get_component_heat_loss_watts(
+        &self,
+        component_index: usize,
+        ambient_temperature: ThermodynamicTemperature) -> f64 {
+
+        let component = &self.components[component_index];
+
+        let temperature_difference = 
+            get_temperature_degrees_c(component.get_insulated_surface_temperature())
+            - get_temperature_degrees_c(ambient_temperature);
+
+        return component.get_ambient_conductance_watts_per_kelvin() * 
+            temperature_difference;
+    }
+
+    /// returns the heat lost to ambient (watts) from every 
+    /// component in this branch
+    pub fn get_heat_loss_watts(
+        &self,
+        ambient_temperature: ThermodynamicTemperature) -> f64 {
+
+        return (0..self.components.len()).
+            map(|component_index| self.get_component_heat_loss_watts(
+                    component_index, ambient_temperature)).
+            sum();
+    }
+
+    /// returns the mass of fluid in the component at the given
+    /// position (kg), this is rho A L
+    pub fn get_component_fluid_mass_kg(&self, component_index: usize) -> f64 {
+
+        let fluid_component = self.components[component_index].as_fluid_component();
+
+        let fluid_volume = 
+            fluid_component.get_cross_sectional_area_immutable() * 
+            fluid_component.get_component_length_immutable();
+
+        return (fluid_component.get_fluid_density_immutable() * fluid_volume).value;
+    }
+
+    /// returns the temperature of the fluid entering the 
+    /// component at the given position, this is the temperature
+    /// of the component upstream of it, or the temperature of 
+    /// the header the fluid comes from
+    ///
+    /// positive flow goes from the top header to the bottom 
+    /// header
+    pub fn get_component_inlet_temperature(
+        &self,
+        component_index: usize,
+        mass_flowrate: MassRate,
+        top_header_temperature: ThermodynamicTemperature,
+        bottom_header_temperature: ThermodynamicTemperature) 
+        -> ThermodynamicTemperature {
+
+        if mass_flowrate.value >= 0.0 {
+
+            if component_index == 0 {
+                return top_header_temperature;
+            }
+
+            return self.components[component_index - 1].get_fluid_temperature();
+        }
+
+        if component_index + 1 == self.components.len() {
+            return bottom_header_temperature;
+        }
+
+        return self.components[component_index + 1].get_fluid_temperature();
+    }
+
+    /// returns the temperature of the fluid at the top header 
+    /// end of this branch
+    pub fn get_top_end_temperature(&self) -> ThermodynamicTemperature {
+        return self.components[0].get_fluid_temperature();
+    }
+
+    /// returns the temperature of the fluid at the bottom header
+    /// end of this branch
+    pub fn get_bottom_end_temperature(&self) -> ThermodynamicTemperature {
+        return self.components[self.components.len() - 1].get_fluid_temperature();
+    }
+
+    /// advances the temperature of every component over one 
+    /// timestep, the fluid enters at the temperature of the 
+    /// header upstream and is carried through the components
+    /// in the direction of flow
+    ///
+    /// insulated components also lose heat to the ambient 
+    /// temperature given
+    ///
+    /// components with walls exchange heat with their wall 
+    /// instead, the wall is advanced first with the fluid held 
+    /// at its old temperature, then the fluid sees the new 
+    /// inner wall temperature
+    ///
+    /// (see calculate_upwind_control_volume_temperature_with_ambient_loss)
+    pub fn advance_temperatures(
+        &mut self,
+        mass_flowrate: MassRate,
+        top_header_temperature: ThermodynamicTemperature,
+        bottom_header_temperature: ThermodynamicTemperature,
+        ambient_temperature: ThermodynamicTemperature,
+        timestep: Duration){
+
+        let number_of_components = self.components.len();
+
+        // components in the order the fluid passes through them
+        let component_indices: Vec<usize> = if mass_flowrate.value >= 0.0 {
+            (0..number_of_components).collect()
+        } else {
+            (0..number_of_components).rev().collect()
+        };
+
+        for component_index in component_indices {
+
+            // the upstream component has already been 
+            // advanced, so this is its new temperature
+            let upstream_temperature = self.get_component_inlet_temperature(
+                component_index, 
+                mass_flowrate, 
+                top_header_temperature, 
+                bottom_header_temperature);
+
+            let (external_conductance, external_temperature) = 
+                self.advance_component_wall_temperatures(
+                    component_index, 
+                    mass_flowrate, 
+                    ambient_temperature, 
+                    timestep);
+
+            let old_temperature = 
+                self.components[component_index].get_fluid_temperature();
+            let specific_heat_capacity = self.components[component_index].
+                get_fluid_properties().
+                get_specific_heat_capacity_joule_per_kg_kelvin(old_temperature);
+
+            let new_temperature = 
+                calculate_upwind_control_volume_temperature_with_ambient_loss(
+                old_temperature,
+                upstream_temperature,
+                self.get_component_fluid_mass_kg(component_index),
+                specific_heat_capacity,
+                mass_flowrate,
+                self.component_heat_inputs_watts[component_index],
+                external_conductance,
+                external_temperature,
+                timestep);
+
+            self.components[component_index].set_fluid_temperature(new_temperature);
+        }
+    }
+
+    // advances the wall of the component at the given position
+    // (if it has one), and returns the conductance and 
+    // temperature the fluid exchanges heat with: the inner 
+    // wall node, or ambient through the insulation if there is
+    // no wall
+    fn advance_component_wall_temperatures(
+        &mut self,
+        component_index: usize,
+        mass_flowrate: MassRate,
+        ambient_temperature: ThermodynamicTemperature,
+        timestep: Duration) -> (f64, ThermodynamicTemperature) {
+
+        let component = &self.components[component_index];
+
+        let ambient_conductance = component.get_ambient_conductance_watts_per_kelvin();
+
+        if component.get_wall().is_none() {
+            return (ambient_conductance, ambient_temperature);
+        }
+
+        let fluid_temperature = component.get_fluid_temperature();
+        let fluid_component = component.as_fluid_component();
+        let cross_sectional_area = fluid_component.get_cross_sectional_area_immutable();
+        let component_length = fluid_component.get_component_length_immutable();
+
+        let heat_transfer_coefficient = 
+            calculate_fluid_wall_heat_transfer_coefficient(
+                mass_flowrate, 
+                cross_sectional_area, 
+                fluid_component.get_hydraulic_diameter_immutable(), 
+                component.get_fluid_properties(), 
+                fluid_temperature,
+                component.get_heat_transfer_correlation());
+
+        match self.components[component_index].get_wall_mut() {
+            Some(wall) => {
+
+                let fluid_conductance = wall.get_fluid_conductance_watts_per_kelvin(
+                    heat_transfer_coefficient, 
+                    cross_sectional_area, 
+                    component_length);
+
+                wall.advance_temperatures(
+                    fluid_temperature, 
+                    fluid_conductance, 
+                    ambient_conductance, 
+                    ambient_temperature, 
+                    cross_sectional_area, 
+                    component_length, 
+                    timestep);
+
+                return (fluid_conductance, wall.get_inner_node_temperature());
+            },
+            None => return (ambient_conductance, ambient_temperature),
+        }
+    }
+
+    /// returns the hydrostatic pressure change from the top 
+    /// header to the bottom header, each component uses the 
+    /// density at its own temperature
+    ///
+    /// this is the part of the branch pressure change which 
+    /// drives natural circulation
+    pub fn get_hydrostatic_pressure_change(&self) -> Pressure {
+
+        let mut hydrostatic_pressure_change = Pressure::new::<pascal>(0.0);
+
+        for component in self.components.iter() {
+
+            let fluid_component = component.as_fluid_component();
+
+            hydrostatic_pressure_change = hydrostatic_pressure_change + 
+                <TherminolPipe as FluidPipeCalcPressureChange>::
+                get_hydrostatic_pressure_change(
+                    fluid_component.get_component_length_immutable(),
+                    fluid_component.get_incline_angle_immutable(),
+                    fluid_component.get_fluid_density_immutable());
+        }
+
+        return hydrostatic_pressure_change;
+    }
+
+    /// returns the inertance of the branch (sum of L/A over every 
+    /// component) in m^-1
+    ///
+    /// in transients, inertance * d(mass_flowrate)/dt is the 
+    /// pressure difference needed to accelerate the fluid
+    pub fn get_inertance_per_meter(&self) -> f64 {
+
+        let mut inertance_per_meter: f64 = 0.0;
+
+        for component in self.components.iter() {
+
+            let fluid_component = component.as_fluid_component();
+
+            let component_length = 
+                fluid_component.get_component_length_immutable();
+            let cross_sectional_area = 
+                fluid_component.get_cross_sectional_area_immutable();
+
+            inertance_per_meter += 
+                component_length.value / cross_sectional_area.value;
+        }
+
+        return inertance_per_meter;
+    }
+
+    /// moves the speed of every pump in this branch towards its
+    /// target speed over a timestep
+    pub fn advance_pump_speeds(&mut self, timestep: Duration){
+
+        for component in self.components.iter_mut() {
+            if let TherminolBranchComponent::Pump(pump) = component {
+                pump.advance_speed(timestep);
+            }
+        }
+    }
+
+    /// sets the speed the pump at the given position approaches 
+    /// during transients, returns false if the component there 
+    /// is not a pump
+    pub fn set_component_pump_target_speed(
+        &mut self,
+        component_index: usize,
+        target_speed_rpm: f64) -> bool {
+
+        match &mut self.components[component_index] {
+            TherminolBranchComponent::Pump(pump) => {
+                pump.set_target_speed_rpm(target_speed_rpm);
+                return true;
+            },
+            _ => {
+                return false;
+            },
+        }
+    }
+
+    /// opens or closes every check valve in this branch given 
+    /// the converged branch mass flowrate
+    pub fn update_check_valve_states(&mut self, branch_mass_flowrate: MassRate){
+
+        for component in self.components.iter_mut() {
+            if let TherminolBranchComponent::CheckValve(check_valve) = component {
+                check_valve.update_state(branch_mass_flowrate);
+            }
+        }
+    }
+
+    /// returns the name and open (true) or closed (false) state
+    /// of every check valve in this branch
+    pub fn get_check_valve_states(&self) -> Vec<(String, bool)> {
+
+        let mut check_valve_states: Vec<(String, bool)> = vec![];
+
+        for component in self.components.iter() {
+            if let TherminolBranchComponent::CheckValve(check_valve) = component {
+                check_valve_states.push(
+                    (check_valve.get_name().to_string(), check_valve.is_open()));
+            }
+        }
+
+        return check_valve_states;
+    }
+
+    /// returns the number of times the pressure change across this
+    /// branch has been evaluated since the last reset
+    pub fn get_pressure_change_evaluations(&self) -> usize {
+        return self.pressure_change_evaluations.get();
+    }
+
+    pub fn reset_pressure_change_evaluations(&self) {
+        self.pressure_change_evaluations.set(0);
+    }
+
+    /// returns the components in this branch from top header to 
+    /// bottom header
+    pub fn get_components(&self) -> &Vec<TherminolBranchComponent> {
+        return &self.components;
+    }
+
+    /// returns the names of the components in this branch 
+    /// from top header to bottom header
+    pub fn get_component_names(&self) -> Vec<String> {
+        return self.components.iter().
+            map(|component| component.get_name().to_string()).
+            collect();
+    }
+
+    /// returns the position of the component with the given name
+    /// within the branch, if there is one
+    pub fn get_component_index(&self, name: &str) -> Option<usize> {
+        return self.components.iter().
+            position(|component| component.get_name() == name);
+    }
+
+    /// sets the internal pressure source (eg. pump pressure)
+    /// of the component at the given position
+    pub fn set_component_internal_pressure_source(
+        &mut self,
+        component_index: usize,
+        internal_pressure: Pressure){
+
+        self.components[component_index].
+            set_internal_pressure_source(internal_pressure);
+    }
+
+    /// runs the pump at the given position at a set speed,
+    /// returns false if the component there is not a pump
+    pub fn set_component_pump_speed(
+        &mut self,
+        component_index: usize,
+        speed_rpm: f64) -> bool {
+
+        match &mut self.components[component_index] {
+            TherminolBranchComponent::Pump(pump) => {
+                pump.set_speed_rpm(speed_rpm);
+                return true;
+            },
+            _ => {
+                return false;
+            },
+        }
+    }
+
+    /// returns the components as a fluid component vector,
+    /// the same kind of vector used in CTAHBranch, HeaterBranch 
+    /// and DHXBranch
+    ///
+    /// pumps and check valves appear as their underlying 
+    /// component, so the pump pressure rise and valve seat 
+    /// pressure change are not part of this vector
+    pub fn get_fluid_component_vector(&self) -> Vec<&dyn FluidComponent> {
+        return self.components.iter().
+            map(|component| component.as_fluid_component()).
+            collect();
+    }
+
+    /// returns gauge pressures at every junction between 
+    /// components in this branch
+    ///
+    /// top_header_gauge_pressure is the pressure of the top header
+    /// relative to the chosen reference node
+    pub fn get_manometer_readings(
+        &self,
+        branch_mass_flowrate: MassRate,
+        top_header_gauge_pressure: Pressure) -> Vec<ManometerReading> {
+
+        // i use the pressure change of each branch component 
+        // rather than the fluid component vector, so that pump 
+        // curves and check valves are included
+        let component_pressure_changes: Vec<Pressure> = 
+            self.components.iter().
+            map(|component| component.get_pressure_change(branch_mass_flowrate)).
+            collect();
+
+        return calculate_branch_manometer_readings_from_pressure_changes(
+            &self.name, 
+            &self.get_component_names(), 
+            &component_pressure_changes, 
+            top_header_gauge_pressure);
+    }
+
+    /// returns the flow conditions and geometry of every 
+    /// component in this branch at the branch mass flowrate, 
+    /// from the top header to the bottom header
+    pub fn get_component_readings(
+        &self,
+        branch_mass_flowrate: MassRate) -> Vec<ComponentReading> {
+
+        return self.components.iter().
+            map(|component| calculate_component_reading(
+                    &self.name, 
+                    component, 
+                    branch_mass_flowrate)).
+            collect();
+    }
+
+    /// returns the labels for every pressure tap in this branch
+    pub fn get_manometer_tap_labels(&self) -> Vec<String> {
+        return get_branch_manometer_tap_labels(
+            &self.name, 
+            &self.get_component_names());
+    }
+
+    /// checks that the density and viscosity of every component 
+    /// in this branch are finite and positive
+    pub fn check_fluid_properties(&self) -> Result<(), CIETSolverError> {
+
+        for component in self.components.iter() {
+            check_component_fluid_properties(
+                component.get_name(), 
+                component.as_fluid_component())?;
+        }
+
+        return Ok(());
+    }
+
+    /// returns a TemperatureOutOfRange error for every component
+    /// in this branch whose fluid temperature is outside the 
+    /// validated range of the property correlations
+    pub fn get_temperature_range_violations(&self) -> Vec<CIETSolverError> {
+        return self.components.iter().
+            filter_map(|component| component.check_fluid_temperature_range().err()).
+            collect();
+    }
+
+    /// sets the working fluid of every component in this branch
+    pub fn set_fluid_properties(&mut self, 
+                                fluid_properties: Arc<dyn FluidProperties>){
+        for component in self.components.iter_mut() {
+            component.set_fluid_properties(fluid_properties.clone());
+        }
+    }
+
+    /// calculates mass flowrate from pressure change, but returns
+    /// a CIETSolverError instead of panicking if the flowrate 
+    /// cannot be found
+    pub fn try_get_mass_flowrate_from_pressure_change(
+        &self,
+        pressure_change: Pressure) -> Result<MassRate, CIETSolverError> {
+
+        // -1 or +1 kg/s, expanded if needed
+        return self.try_get_mass_flowrate_from_pressure_change_with_bracket(
+            pressure_change, 
+            MassRate::new::<kilogram_per_second>(-1.0), 
+            MassRate::new::<kilogram_per_second>(1.0));
+    }
+
+    /// same as try_get_mass_flowrate_from_pressure_change, but 
+    /// the root find starts from the bracket given 
+    /// (eg. around the previous flowrate)
+    pub fn try_get_mass_flowrate_from_pressure_change_with_bracket(
+        &self,
+        pressure_change: Pressure,
+        initial_lower_bound: MassRate,
+        initial_upper_bound: MassRate) -> Result<MassRate, CIETSolverError> {
+
+        self.check_fluid_properties()?;
+
+        return calculate_branch_mass_flowrate_from_pressure_change_with_bracket(
+            pressure_change, 
+            initial_lower_bound,
+            initial_upper_bound,
+            |mass_rate: MassRate| -> Pressure {
+                self.get_pressure_change(mass_rate)
+            });
+    }
+
+}
+
+impl FluidComponentCollectionMethods for TherminolBranch {
+
+    /// calculates pressure change when given a mass flowrate
+    fn get_pressure_change(
+        &self, 
+        fluid_mass_flowrate: MassRate) -> Pressure{
+
+        self.pressure_change_evaluations.set(
+            self.pressure_change_evaluations.get() + 1);
+
+        let mut pressure_change = Pressure::new::<pascal>(0.0);
+
+        for component in self.components.iter() {
+            pressure_change = pressure_change + 
+                component.get_pressure_change(fluid_mass_flowrate);
+        }
+
+        return pressure_change;
+    }
 
+    /// calculates mass flowrate from pressure change
+    ///
+    /// # Panics
+    ///
+    /// panics if the flowrate cannot be found, the trait method 
+    /// cannot return a Result, so use 
+    /// try_get_mass_flowrate_from_pressure_change if you want 
+    /// to handle the error
+    fn get_mass_flowrate_from_pressure_change(
+        &self,
+        pressure_change: Pressure) -> MassRate{
 
+        return self.try_get_mass_flowrate_from_pressure_change(
+            pressure_change).unwrap();
+    }
+}
@@ -0,0 +1,221 @@
+// This application was developed for use in my PhD thesis under supervision 
+// of Professor Per F. Peterson. It is part of a thermal hydraulics
+// library in Rust that is released under the GNU General Public License
+// v 3.0. This is partly due to the fact that some of the libraries 
+// inherit from GeN-Foam and OpenFOAM, both licensed under GNU General
+// Public License v3.0.
+//
+// As such, the entire library is released under GNU GPL v3.0. It is a strong 
+// copyleft license which means you cannot use it in proprietary software.
+//
+//
+// License
+//    This application is part ciet_digital_twin_rust which uses
+//    fluid_mechanics_rust, a partial library of the
+//    thermal hydraulics library written in rust meant to help with the
+//    fluid mechanics aspects of the calculations
+//     
+//    Copyright (C) 2022-2023  Theodore Kay Chen Ong, Singapore Nuclear
+//    Research and Safety Initiative, Per F. Peterson, University of 
+//    California, Berkeley Thermal Hydraulics Laboratory
+//
+//    ciet_digital_twin_rust is free software; you can redistribute it and/or modify it
+//    under the terms of the GNU General Public License as published by the
+//    Free Software Foundation; either version 2 of the License, or (at your
+//    option) any later version.
+//
+//    ciet_digital_twin_rust is distributed in the hope that it will be useful, but WITHOUT
+//    ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or
+//    FITNESS FOR A PARTICULAR PURPOSE.  See the GNU General Public License
+//    for more details.
+//
+//    ciet_digital_twin_rust relies on a thermal hydraulics library
+//    also written in rust. The isothermal digital twin in particular
+//    relies on fluid_mechanics_rust.
+//    This library is part of a thermal hydraulics library in rust
+//    and contains some code copied from GeN-Foam, and OpenFOAM derivative.
+//    This offering is not approved or endorsed by the OpenFOAM Foundation nor
+//    OpenCFD Limited, producer and distributor of the OpenFOAM(R)software via
+//    www.openfoam.com, and owner of the OPENFOAM(R) and OpenCFD(R) trademarks.
+//    Nor is it endorsed by the authors and owners of GeN-Foam.
+//
+//    You should have received a copy of the GNU General Public License
+//    along with this program.  If not, see <http://www.gnu.org/licenses/>.
+//
+// © All rights reserved. Theodore Kay Chen Ong,
+// Singapore Nuclear Research and Safety Initiative,
+// Per F. Peterson,
+// University of California, Berkeley Thermal Hydraulics Laboratory
+//
+// Main author of the code: Theodore Kay Chen Ong, supervised by
+// Professor Per F. Peterson
+extern crate fluid_mechanics_rust;
+use fluid_mechanics_rust::prelude::*;
+
+use crate::therminol_component::TherminolCustomComponent;
+
+/// a check valve within a branch, it only allows flow in the 
+/// forward (top header to bottom header) direction 
+///
+/// the valve sits inside a housing component (eg. flowmeter 20 
+/// in the dhx branch), which supplies the friction losses, 
+/// hydrostatic pressure change and geometry. The valve seat 
+/// adds the following pressure change:
+///
+/// while seated, the valve only lets through leakage flow:
+/// pressure_change = - mass_flowrate / leakage_coefficient
+///
+/// once the forward pressure difference reaches the opening 
+/// pressure, the valve lifts and the seat pressure change 
+/// stays at - opening_pressure
+///
+/// the opening pressure is the cracking pressure when the valve
+/// is closed. When it is open, the valve only reseats once the 
+/// pressure difference drops below the cracking pressure minus 
+/// the hysteresis band.
+///
+/// Both pieces meet at mass_flowrate = leakage_coefficient *
+/// opening_pressure, so the pressure change is continuous in 
+/// mass flowrate, which keeps brent's method happy.
+pub struct CheckValve {
+    housing: TherminolCustomComponent<'static>,
+    cracking_pressure: Pressure,
+    // kg/s of leakage per Pa across the seat
+    leakage_coefficient_kg_per_s_per_pascal: f64,
+    hysteresis_band: Pressure,
+    open: bool,
+}
+
+impl CheckValve {
+
+    /// constructor, the valve starts closed
+    ///
+    /// the leakage coefficient must be positive
+    pub fn new(housing: TherminolCustomComponent<'static>,
+               cracking_pressure: Pressure,
+               leakage_coefficient_kg_per_s_per_pascal: f64,
+               hysteresis_band: Pressure) -> Self {
+
+        return Self { 
+            housing: housing, 
+            cracking_pressure: cracking_pressure, 
+            leakage_coefficient_kg_per_s_per_pascal: 
+                leakage_coefficient_kg_per_s_per_pascal, 
+            hysteresis_band: hysteresis_band, 
+            open: false,
+        };
+    }
+
+    /// returns the check valve used in the dhx branch
+    ///
+    /// it cracks open as soon as there is forward flow and leaks 
+    /// about 1e-6 kg/s per kPa in reverse, which is close to the
+    /// zero flow the dhx branch used to return
+    pub fn dhx_check_valve(housing: TherminolCustomComponent<'static>) -> Self {
+        return Self::new(
+            housing,
+            Pressure::new::<pascal>(0.0),
+            1.0e-9,
+            Pressure::new::<pascal>(0.0));
+    }
+
+    pub fn get_name(&self) -> &str {
+        return self.housing.get_name();
+    }
+
+    pub fn get_housing(&self) -> &TherminolCustomComponent<'static> {
+        return &self.housing;
+    }
+
+    pub fn get_housing_mut(&mut self) -> &mut TherminolCustomComponent<'static> {
+        return &mut self.housing;
+    }
+
+    pub fn get_cracking_pressure(&self) -> Pressure {
+        return self.cracking_pressure;
+    }
+
+    pub fn set_cracking_pressure(&mut self, cracking_pressure: Pressure){
+        self.cracking_pressure = cracking_pressure;
+    }
+
+    pub fn get_leakage_coefficient(&self) -> f64 {
+        return self.leakage_coefficient_kg_per_s_per_pascal;
+    }
+
+    pub fn set_leakage_coefficient(
+        &mut self, 
+        leakage_coefficient_kg_per_s_per_pascal: f64){
+        self.leakage_coefficient_kg_per_s_per_pascal = 
+            leakage_coefficient_kg_per_s_per_pascal;
+    }
+
+    pub fn get_hysteresis_band(&self) -> Pressure {
+        return self.hysteresis_band;
+    }
+
+    pub fn set_hysteresis_band(&mut self, hysteresis_band: Pressure){
+        self.hysteresis_band = hysteresis_band;
+    }
+
+    /// returns true if the valve was open after the last 
+    /// state update
+    pub fn is_open(&self) -> bool {
+        return self.open;
+    }
+
+    /// returns the forward pressure difference needed to keep 
+    /// the valve off its seat, this depends on whether 
+    /// the valve is currently open (hysteresis)
+    pub fn get_opening_pressure(&self) -> Pressure {
+
+        let zero_pressure = Pressure::new::<pascal>(0.0);
+
+        if self.open {
+            let reseat_pressure = 
+                self.cracking_pressure - self.hysteresis_band;
+
+            if reseat_pressure < zero_pressure {
+                return zero_pressure;
+            }
+            return reseat_pressure;
+        }
+
+        return self.cracking_pressure;
+    }
+
+    /// returns the mass flowrate at which the valve lifts off 
+    /// its seat
+    pub fn get_opening_mass_flowrate(&self) -> MassRate {
+        return MassRate::new::<kilogram_per_second>(
+            self.leakage_coefficient_kg_per_s_per_pascal *
+            self.get_opening_pressure().value);
+    }
+
+    /// returns the pressure change across the valve seat only
+    pub fn get_seat_pressure_change(&self, mass_flowrate: MassRate) -> Pressure {
+
+        if mass_flowrate > self.get_opening_mass_flowrate() {
+            return -self.get_opening_pressure();
+        }
+
+        return Pressure::new::<pascal>(
+            -mass_flowrate.value / self.leakage_coefficient_kg_per_s_per_pascal);
+    }
+
+    /// returns the pressure change across the valve, including 
+    /// the housing
+    pub fn get_pressure_change(&self, mass_flowrate: MassRate) -> Pressure {
+        return self.housing.get_pressure_change_immutable(mass_flowrate)
+            + self.get_seat_pressure_change(mass_flowrate);
+    }
+
+    /// opens or closes the valve given the converged mass flowrate
+    ///
+    /// this should only be called after a solve, not while 
+    /// iterating, otherwise the pressure change would jump 
+    /// around within brent's method
+    pub fn update_state(&mut self, mass_flowrate: MassRate){
+        self.open = mass_flowrate > self.get_opening_mass_flowrate();
+    }
+}
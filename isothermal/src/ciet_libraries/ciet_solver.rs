@@ -0,0 +1,544 @@
+// This application was developed for use in my PhD thesis under supervision 
+// of Professor Per F. Peterson. It is part of a thermal hydraulics
+// library in Rust that is released under the GNU General Public License
+// v 3.0. This is partly due to the fact that some of the libraries 
+// inherit from GeN-Foam and OpenFOAM, both licensed under GNU General
+// Public License v3.0.
+//
+// As such, the entire library is released under GNU GPL v3.0. It is a strong 
+// copyleft license which means you cannot use it in proprietary software.
+//
+//
+// License
+//    This application is part ciet_digital_twin_rust which uses
+//    fluid_mechanics_rust, a partial library of the
+//    thermal hydraulics library written in rust meant to help with the
+//    fluid mechanics aspects of the calculations
+//     
+//    Copyright (C) 2022-2023  Theodore Kay Chen Ong, Singapore Nuclear
+//    Research and Safety Initiative, Per F. Peterson, University of 
+//    California, Berkeley Thermal Hydraulics Laboratory
+//
+//    ciet_digital_twin_rust is free software; you can redistribute it and/or modify it
+//    under the terms of the GNU General Public License as published by the
+//    Free Software Foundation; either version 2 of the License, or (at your
+//    option) any later version.
+//
+//    ciet_digital_twin_rust is distributed in the hope that it will be useful, but WITHOUT
+//    ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or
+//    FITNESS FOR A PARTICULAR PURPOSE.  See the GNU General Public License
+//    for more details.
+//
+//    ciet_digital_twin_rust relies on a thermal hydraulics library
+//    also written in rust. The isothermal digital twin in particular
+//    relies on fluid_mechanics_rust.
+//    This library is part of a thermal hydraulics library in rust
+//    and contains some code copied from GeN-Foam, and OpenFOAM derivative.
+//    This offering is not approved or endorsed by the OpenFOAM Foundation nor
+//    OpenCFD Limited, producer and distributor of the OpenFOAM(R)software via
+//    www.openfoam.com, and owner of the OPENFOAM(R) and OpenCFD(R) trademarks.
+//    Nor is it endorsed by the authors and owners of GeN-Foam.
+//
+//    You should have received a copy of the GNU General Public License
+//    along with this program.  If not, see <http://www.gnu.org/licenses/>.
+//
+// © All rights reserved. Theodore Kay Chen Ong,
+// Singapore Nuclear Research and Safety Initiative,
+// Per F. Peterson,
+// University of California, Berkeley Thermal Hydraulics Laboratory
+//
+// Main author of the code: Theodore Kay Chen Ong, supervised by
+// Professor Per F. Peterson
+extern crate fluid_mechanics_rust;
+use fluid_mechanics_rust::prelude::*;
+
+use std::cell::Cell;
+use std::fmt;
+
+use crate::energy_equation::get_temperature_degrees_c;
+
+extern crate roots;
+use roots::find_root_brent;
+use roots::SimpleConvergency;
+
+/// errors which can come out of the ciet solvers
+///
+/// previously, every root find was unwrapped, so if a bad pump 
+/// pressure was given, the whole thread would panic. Now the 
+/// solvers return these instead so the caller can decide what
+/// to do
+#[derive(Debug, Clone, PartialEq)]
+pub enum CIETSolverError {
+
+    /// the residual has the same sign at both ends of the bracket,
+    /// so brent's method cannot start
+    RootNotBracketed {
+        solved_variable: String,
+        lower_bound: f64,
+        upper_bound: f64,
+        lower_bound_residual: f64,
+        upper_bound_residual: f64,
+    },
+
+    /// brent's method ran out of iterations before the 
+    /// tolerance was met
+    MaxIterationsReached {
+        solved_variable: String,
+        lower_bound: f64,
+        upper_bound: f64,
+        max_iterations: usize,
+        residual_evaluations: usize,
+        tolerance: f64,
+    },
+
+    /// the residual function returned NaN or infinity
+    NonFiniteResidual {
+        solved_variable: String,
+        input_value: f64,
+        residual: f64,
+    },
+
+    /// a fluid property is outside the range where it 
+    /// makes sense (or where the correlation is valid)
+    PropertyOutOfRange {
+        component_name: String,
+        property_name: String,
+        value: f64,
+    },
+
+    /// the fluid temperature of a component is outside the 
+    /// range its fluid property correlations were validated 
+    /// over, so its density and viscosity are extrapolated
+    TemperatureOutOfRange {
+        component_name: String,
+        temperature_degrees_c: f64,
+        min_temperature_degrees_c: f64,
+        max_temperature_degrees_c: f64,
+    },
+
+    /// the number of branch values given does not match the 
+    /// number of branches in the network
+    BranchCountMismatch {
+        expected_branches: usize,
+        given_branches: usize,
+    },
+}
+
+impl fmt::Display for CIETSolverError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::RootNotBracketed { 
+                solved_variable, 
+                lower_bound, 
+                upper_bound, 
+                lower_bound_residual, 
+                upper_bound_residual } => {
+                write!(f, "root not bracketed for {}: bracket [{}, {}] \
+                       has residuals [{}, {}]",
+                       solved_variable, lower_bound, upper_bound,
+                       lower_bound_residual, upper_bound_residual)
+            },
+            Self::MaxIterationsReached { 
+                solved_variable, 
+                lower_bound, 
+                upper_bound, 
+                max_iterations, 
+                residual_evaluations, 
+                tolerance } => {
+                write!(f, "max iterations ({}) reached for {} \
+                       in bracket [{}, {}] after {} residual evaluations \
+                       with tolerance {}",
+                       max_iterations, solved_variable, lower_bound, 
+                       upper_bound, residual_evaluations, tolerance)
+            },
+            Self::NonFiniteResidual { 
+                solved_variable, 
+                input_value, 
+                residual } => {
+                write!(f, "non finite residual {} for {} = {}",
+                       residual, solved_variable, input_value)
+            },
+            Self::PropertyOutOfRange { 
+                component_name, 
+                property_name, 
+                value } => {
+                write!(f, "{} out of range in {}: {}",
+                       property_name, component_name, value)
+            },
+            Self::TemperatureOutOfRange { 
+                component_name, 
+                temperature_degrees_c, 
+                min_temperature_degrees_c, 
+                max_temperature_degrees_c } => {
+                write!(f, "temperature {} C in {} is outside the validated \
+                       range [{}, {}] C",
+                       temperature_degrees_c, component_name, 
+                       min_temperature_degrees_c, max_temperature_degrees_c)
+            },
+            Self::BranchCountMismatch { 
+                expected_branches, 
+                given_branches } => {
+                write!(f, "expected values for {} branches, got {}",
+                       expected_branches, given_branches)
+            },
+        }
+    }
+}
+
+impl std::error::Error for CIETSolverError {}
+
+/// default tolerance used for all brent root finds in ciet
+pub const CIET_SOLVER_TOLERANCE: f64 = 1e-9;
+
+/// default max iterations used for all brent root finds in ciet
+pub const CIET_SOLVER_MAX_ITERATIONS: usize = 30;
+
+/// how much the bracket grows every time it is expanded 
+/// (same factor as zbrac in numerical recipes)
+pub const CIET_BRACKET_EXPANSION_FACTOR: f64 = 1.6;
+
+/// how many times the bracket may be expanded before giving up,
+/// 1.6^50 is about 1.6e10 times the initial bracket width
+pub const CIET_MAX_BRACKET_EXPANSIONS: usize = 50;
+
+/// finds a root with brent's method, but returns a CIETSolverError
+/// instead of panicking when something goes wrong
+///
+/// solved_variable is just a name which goes into the error 
+/// message, eg. "mass_flowrate_kg_per_s"
+pub fn find_root_brent_checked<F>(
+    solved_variable: &str,
+    lower_bound: f64,
+    upper_bound: f64,
+    residual_function: F) -> Result<f64, CIETSolverError> 
+where F: Fn(f64) -> f64 {
+
+    // i'm keeping track of how many times the residual was 
+    // evaluated and whether it ever went non finite
+    let residual_evaluations: Cell<usize> = Cell::new(0);
+    let non_finite_residual: Cell<Option<(f64,f64)>> = Cell::new(None);
+
+    let checked_residual_function = |input_value: f64| -> f64 {
+
+        residual_evaluations.set(residual_evaluations.get() + 1);
+
+        let residual = residual_function(input_value);
+
+        if !residual.is_finite() && non_finite_residual.get().is_none() {
+            non_finite_residual.set(Some((input_value, residual)));
+        }
+
+        return residual;
+    };
+
+    let mut convergency = SimpleConvergency { 
+        eps: CIET_SOLVER_TOLERANCE, 
+        max_iter: CIET_SOLVER_MAX_ITERATIONS };
+
+    let root_result = find_root_brent(
+        upper_bound,
+        lower_bound,
+        &checked_residual_function,
+        &mut convergency);
+
+    if let Some((input_value, residual)) = non_finite_residual.get() {
+        return Err(CIETSolverError::NonFiniteResidual { 
+            solved_variable: solved_variable.to_string(), 
+            input_value: input_value, 
+            residual: residual });
+    }
+
+    match root_result {
+        Ok(root) => {
+            return Ok(root);
+        },
+        Err(_) => {
+            // brent's method either could not start because the 
+            // root is not bracketed, or it did not converge,
+            // i'll check the bracket to tell which one it is
+            let lower_bound_residual = residual_function(lower_bound);
+            let upper_bound_residual = residual_function(upper_bound);
+
+            if lower_bound_residual * upper_bound_residual > 0.0 {
+                return Err(CIETSolverError::RootNotBracketed { 
+                    solved_variable: solved_variable.to_string(), 
+                    lower_bound: lower_bound, 
+                    upper_bound: upper_bound, 
+                    lower_bound_residual: lower_bound_residual, 
+                    upper_bound_residual: upper_bound_residual });
+            }
+
+            return Err(CIETSolverError::MaxIterationsReached { 
+                solved_variable: solved_variable.to_string(), 
+                lower_bound: lower_bound, 
+                upper_bound: upper_bound, 
+                max_iterations: CIET_SOLVER_MAX_ITERATIONS, 
+                residual_evaluations: residual_evaluations.get(), 
+                tolerance: CIET_SOLVER_TOLERANCE });
+        },
+    }
+}
+
+/// finds a root with brent's method, but if the initial bracket 
+/// does not contain a root, the bracket is expanded until it does
+///
+/// the end of the bracket with the smaller residual magnitude is
+/// pushed outward each time, since the root is most likely 
+/// on that side. If no root is found after 
+/// CIET_MAX_BRACKET_EXPANSIONS expansions, RootNotBracketed is 
+/// returned with the last bracket tried
+pub fn find_root_brent_expanding<F>(
+    solved_variable: &str,
+    initial_lower_bound: f64,
+    initial_upper_bound: f64,
+    residual_function: F) -> Result<f64, CIETSolverError> 
+where F: Fn(f64) -> f64 {
+
+    let mut lower_bound = initial_lower_bound;
+    let mut upper_bound = initial_upper_bound;
+
+    let checked_residual = |input_value: f64| -> Result<f64, CIETSolverError> {
+
+        let residual = residual_function(input_value);
+
+        if !residual.is_finite() {
+            return Err(CIETSolverError::NonFiniteResidual { 
+                solved_variable: solved_variable.to_string(), 
+                input_value: input_value, 
+                residual: residual });
+        }
+
+        return Ok(residual);
+    };
+
+    let mut lower_bound_residual = checked_residual(lower_bound)?;
+    let mut upper_bound_residual = checked_residual(upper_bound)?;
+
+    let mut expansions: usize = 0;
+
+    while lower_bound_residual * upper_bound_residual > 0.0 {
+
+        if expansions >= CIET_MAX_BRACKET_EXPANSIONS {
+            return Err(CIETSolverError::RootNotBracketed { 
+                solved_variable: solved_variable.to_string(), 
+                lower_bound: lower_bound, 
+                upper_bound: upper_bound, 
+                lower_bound_residual: lower_bound_residual, 
+                upper_bound_residual: upper_bound_residual });
+        }
+
+        let bracket_width = upper_bound - lower_bound;
+
+        if lower_bound_residual.abs() < upper_bound_residual.abs() {
+            lower_bound = lower_bound - 
+                CIET_BRACKET_EXPANSION_FACTOR * bracket_width;
+            lower_bound_residual = checked_residual(lower_bound)?;
+        } else {
+            upper_bound = upper_bound + 
+                CIET_BRACKET_EXPANSION_FACTOR * bracket_width;
+            upper_bound_residual = checked_residual(upper_bound)?;
+        }
+
+        expansions += 1;
+    }
+
+    return find_root_brent_checked(
+        solved_variable, 
+        lower_bound, 
+        upper_bound, 
+        residual_function);
+}
+
+/// finds the mass flowrate through a series of components
+/// (a branch) given the pressure change across it
+///
+/// branch_pressure_change is a function which returns the 
+/// pressure change across the branch given a mass flowrate
+pub fn calculate_branch_mass_flowrate_from_pressure_change<F>(
+    pressure_change: Pressure,
+    branch_pressure_change: F) -> Result<MassRate, CIETSolverError> 
+where F: Fn(MassRate) -> Pressure {
+
+    // i start with -1 or +1 kg/s since that's what ciet usually
+    // operates within, the bracket is expanded if the flowrate 
+    // is outside of this
+    let upper_bound = MassRate::new::<kilogram_per_second>(1.0);
+
+    let lower_bound = MassRate::new::<kilogram_per_second>(-1.0);
+
+    return calculate_branch_mass_flowrate_from_pressure_change_with_bracket(
+        pressure_change, 
+        lower_bound, 
+        upper_bound, 
+        branch_pressure_change);
+}
+
+/// same as calculate_branch_mass_flowrate_from_pressure_change, 
+/// but the user supplies the initial bracket
+///
+/// this is useful for warm starts, where the flowrate is known
+/// to be close to the previous solution
+pub fn calculate_branch_mass_flowrate_from_pressure_change_with_bracket<F>(
+    pressure_change: Pressure,
+    initial_lower_bound: MassRate,
+    initial_upper_bound: MassRate,
+    branch_pressure_change: F) -> Result<MassRate, CIETSolverError> 
+where F: Fn(MassRate) -> Pressure {
+
+    // now we have a function comparing the pressure change
+    // to the pressure change of the calculated value
+    let mass_flow_from_pressure_chg_root = 
+        |mass_flow_kg_per_s: f64| -> f64 {
+
+        let mass_rate = 
+            MassRate::new::<kilogram_per_second>(
+                mass_flow_kg_per_s);
+
+        let pressure_change_tested = 
+            branch_pressure_change(mass_rate);
+
+        // since we are finding root, then we must also
+        // subtract it from our pressure change value
+        let pressure_change_error: f64 =
+            pressure_change.value - 
+            pressure_change_tested.value;
+
+        return pressure_change_error;
+    };
+
+    let mass_flowrate_kg_per_s = find_root_brent_expanding(
+        "mass_flowrate_kg_per_s", 
+        initial_lower_bound.value, 
+        initial_upper_bound.value, 
+        mass_flow_from_pressure_chg_root)?;
+
+    return Ok(MassRate::new::<kilogram_per_second>(mass_flowrate_kg_per_s));
+}
+
+/// keeps track of how much work the last facility solve took
+///
+/// each residual evaluation is one function call within brent's 
+/// method (or within the bracket expansion)
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct CIETSolverIterationCounts {
+
+    /// number of times the net flowrate at the headers was 
+    /// evaluated for a trial header pressure change
+    pub header_residual_evaluations: usize,
+
+    /// number of times the pressure change across any branch
+    /// was evaluated, this includes the nested branch solves
+    pub branch_residual_evaluations: usize,
+
+    /// true if the solve started from the previous operating point
+    pub warm_started: bool,
+}
+
+impl CIETSolverIterationCounts {
+
+    /// header and branch residual evaluations added together
+    pub fn get_total_residual_evaluations(&self) -> usize {
+        return self.header_residual_evaluations + 
+            self.branch_residual_evaluations;
+    }
+}
+
+/// checks that the density and viscosity of a component are 
+/// finite and positive
+///
+/// if not, a PropertyOutOfRange error is returned
+pub fn check_component_fluid_properties(
+    component_name: &str,
+    fluid_component: &dyn FluidComponent) -> Result<(), CIETSolverError> {
+
+    let fluid_density = 
+        fluid_component.get_fluid_density_immutable();
+
+    if !fluid_density.value.is_finite() || fluid_density.value <= 0.0 {
+        return Err(CIETSolverError::PropertyOutOfRange { 
+            component_name: component_name.to_string(), 
+            property_name: "density_kg_per_m3".to_string(), 
+            value: fluid_density.value });
+    }
+
+    let fluid_viscosity = 
+        fluid_component.get_fluid_viscosity_immutable();
+
+    if !fluid_viscosity.value.is_finite() || fluid_viscosity.value <= 0.0 {
+        return Err(CIETSolverError::PropertyOutOfRange { 
+            component_name: component_name.to_string(), 
+            property_name: "viscosity_pascal_second".to_string(), 
+            value: fluid_viscosity.value });
+    }
+
+    return Ok(());
+}
+
+/// lowest temperature the therminol VP-1 property correlations
+/// (TherminolVP1Properties) are validated for
+pub const THERMINOL_MIN_VALIDATED_TEMPERATURE_DEGC: f64 = 20.0;
+
+/// highest temperature the therminol VP-1 property correlations
+/// (TherminolVP1Properties) are validated for
+pub const THERMINOL_MAX_VALIDATED_TEMPERATURE_DEGC: f64 = 180.0;
+
+/// checks that the fluid temperature of a component is within 
+/// the range where the therminol property correlations are 
+/// validated
+///
+/// if not, a TemperatureOutOfRange error is returned. The 
+/// density and viscosity can still be calculated, but they are
+/// extrapolated
+pub fn check_component_fluid_temperature(
+    component_name: &str,
+    fluid_temp: ThermodynamicTemperature) -> Result<(), CIETSolverError> {
+
+    return check_component_fluid_temperature_in_range(
+        component_name, 
+        fluid_temp, 
+        THERMINOL_MIN_VALIDATED_TEMPERATURE_DEGC, 
+        THERMINOL_MAX_VALIDATED_TEMPERATURE_DEGC);
+}
+
+/// same as check_component_fluid_temperature, but for any 
+/// validated range (eg. that of another working fluid)
+pub fn check_component_fluid_temperature_in_range(
+    component_name: &str,
+    fluid_temp: ThermodynamicTemperature,
+    min_temperature_degrees_c: f64,
+    max_temperature_degrees_c: f64) -> Result<(), CIETSolverError> {
+
+    let temperature_degrees_c = get_temperature_degrees_c(fluid_temp);
+
+    // NaN fails both comparisons, so it is caught here too
+    if temperature_degrees_c >= min_temperature_degrees_c && 
+        temperature_degrees_c <= max_temperature_degrees_c {
+        return Ok(());
+    }
+
+    return Err(CIETSolverError::TemperatureOutOfRange { 
+        component_name: component_name.to_string(), 
+        temperature_degrees_c: temperature_degrees_c, 
+        min_temperature_degrees_c: min_temperature_degrees_c, 
+        max_temperature_degrees_c: max_temperature_degrees_c });
+}
+
+/// what the network does when a component's fluid temperature 
+/// leaves the validated range of the property correlations
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TemperatureRangePolicy {
+
+    /// the event is counted and the calculation carries on with
+    /// extrapolated properties
+    Warn,
+
+    /// the event is counted and solve or step_transient returns
+    /// a TemperatureOutOfRange error
+    Error,
+}
+
+impl Default for TemperatureRangePolicy {
+    fn default() -> Self {
+        return Self::Warn;
+    }
+}
@@ -55,10 +55,12 @@ use fluid_mechanics_rust::prelude::*;
 use crate::{Pipe6a, StaticMixer41, CTAHVertical, CTAHHorizontal, 
     Pipe8a, StaticMixer40, Pipe9, Pipe10, Pipe11, Pipe12, CTAHPump, Pipe13, Pipe14, 
     therminol_pipe::TherminolPipe, therminol_component::TherminolCustomComponent, Pipe16, Pipe15, Branch17, Flowmeter40};
+use crate::branch::{TherminolBranch, TherminolBranchComponent};
+use crate::pump::{CentrifugalPump, PumpCurve};
 
-extern crate roots;
-use roots::find_root_brent;
-use roots::SimpleConvergency;
+use crate::ciet_solver::{CIETSolverError, 
+    calculate_branch_mass_flowrate_from_pressure_change,
+    check_component_fluid_properties};
 
 pub struct CTAHBranch<'ctah_branch_lifetime> {
 
@@ -231,8 +233,100 @@ impl<'ctah_branch_lifetime> CTAHBranch<'ctah_branch_lifetime> {
         return self.branch_17.get();
     }
 
+    /// returns a TherminolBranch which owns all the components
+    /// of the ctah branch, in the same order as the fluid component
+    /// vector (top header to bottom header)
+    ///
+    /// the ctah pump is the 10th element starting from 0,
+    /// it can either be a fixed pressure source or run along
+    /// its pump curve
+    pub fn get_owned_branch(&self) -> TherminolBranch {
+
+        let components: Vec<TherminolBranchComponent> = vec![
+            self.pipe6a.get().into(),
+            self.static_mixer_41.get().into(),
+            self.ctah_vertical.get().into(),
+            self.ctah_horizontal.get().into(),
+            self.pipe_8a.get().into(),
+            self.static_mixer_40.get().into(),
+            self.pipe_9.get().into(),
+            self.pipe_10.get().into(),
+            self.pipe_11.get().into(),
+            self.pipe_12.get().into(),
+            CentrifugalPump::new(
+                self.ctah_pump.get(), 
+                PumpCurve::ctah_pump_curve()).into(),
+            self.pipe_13.get().into(),
+            self.pipe_14.get().into(),
+            self.flowmeter_40_14a.get().into(),
+            self.pipe_15.get().into(),
+            self.pipe_16.get().into(),
+            self.branch_17.get().into(),
+        ];
+
+        return TherminolBranch::new("ctah_branch", components);
+    }
+
+    /// returns the names of the components in the ctah branch
+    /// in the same order as the fluid component vector 
+    /// (top header to bottom header)
+    ///
+    /// this is used for labelling manometer readings
+    pub fn get_component_names(&self) -> Vec<String> {
+
+        let component_names: Vec<String> = vec![
+            self.get_pipe6a().get_name().to_string(),
+            self.get_static_mixer_41().get_name().to_string(),
+            self.get_ctah_vertical().get_name().to_string(),
+            self.get_ctah_horizontal().get_name().to_string(),
+            self.get_pipe_8a().get_name().to_string(),
+            self.get_static_mixer_40().get_name().to_string(),
+            self.get_pipe_9().get_name().to_string(),
+            self.get_pipe_10().get_name().to_string(),
+            self.get_pipe_11().get_name().to_string(),
+            self.get_pipe_12().get_name().to_string(),
+            self.get_ctah_pump().get_name().to_string(),
+            self.get_pipe_13().get_name().to_string(),
+            self.get_pipe_14().get_name().to_string(),
+            self.get_flowmeter_40_14a().get_name().to_string(),
+            self.get_pipe_15().get_name().to_string(),
+            self.get_pipe_16().get_name().to_string(),
+            self.get_branch_17().get_name().to_string(),
+        ];
+
+        return component_names;
+    }
+
+
+    /// calculates mass flowrate from pressure change, but returns
+    /// a CIETSolverError instead of panicking if the flowrate 
+    /// cannot be found
+    pub fn try_get_mass_flowrate_from_pressure_change(
+        &self,
+        pressure_change: Pressure) -> Result<MassRate, CIETSolverError> {
+
+        let fluid_component_collection_vector = 
+            self.get_immutable_fluid_component_vector();
+
+        for (component_name, fluid_component) in 
+            self.get_component_names().iter().zip(
+                fluid_component_collection_vector.iter()) {
+
+            check_component_fluid_properties(
+                component_name, *fluid_component)?;
+        }
+
+        return calculate_branch_mass_flowrate_from_pressure_change(
+            pressure_change, 
+            |mass_rate: MassRate| -> Pressure {
+                Self::calculate_pressure_change_from_mass_flowrate(
+                    mass_rate, 
+                    fluid_component_collection_vector)
+            });
+    }
 }
 
+
 impl<'ctah_branch_lifetime> FluidComponentCollectionMethods for CTAHBranch<'ctah_branch_lifetime> {
 
     /// calculates pressure change when given a mass flowrate
@@ -252,69 +346,19 @@ impl<'ctah_branch_lifetime> FluidComponentCollectionMethods for CTAHBranch<'ctah
     }
 
     /// calculates mass flowrate from pressure change
-
+    ///
+    /// # Panics
+    ///
+    /// panics if the flowrate cannot be found, the trait method 
+    /// cannot return a Result, so use 
+    /// try_get_mass_flowrate_from_pressure_change if you want 
+    /// to handle the error
     fn get_mass_flowrate_from_pressure_change(
         &self,
         pressure_change: Pressure) -> MassRate{
 
-
-        let fluid_component_collection_vector = 
-            self.get_immutable_fluid_component_vector();
-
-
-        // i'm keeping bounds artificially low for ciet
-        // -1 or +1 kg/s
-        let upper_bound = MassRate::new::<kilogram_per_second>(1.0);
-
-
-        let lower_bound = MassRate::new::<kilogram_per_second>(-1.0);
-
-
-        // now we have a function comparing the pressure change
-        // to the pressure change of the calculated value
-
-        let mass_flow_from_pressure_chg_root = 
-            |mass_flow_kg_per_s: f64| -> f64 {
-
-            let mass_flow_kg_per_s_double = mass_flow_kg_per_s; 
-
-            let mass_rate = 
-                MassRate::new::<kilogram_per_second>(
-                    mass_flow_kg_per_s_double);
-
-
-            let pressure_change_tested = 
-                Self::calculate_pressure_change_from_mass_flowrate(
-                mass_rate, 
-                fluid_component_collection_vector);
-
-            // now i've obtained the pressure change, i convert it to f64
-
-            let pressure_change_user_stipulated_pascals_f64 = 
-                pressure_change.value;
-
-            // since we are finding root, then we must also
-            // subtract it from our pressure change value
-
-
-            let pressure_change_error: f64 =
-                pressure_change_user_stipulated_pascals_f64 - 
-                pressure_change_tested.value;
-
-            return pressure_change_error;
-
-        };
-
-        let mut convergency = SimpleConvergency { eps:1e-9_f64, max_iter:30 };
-
-        let mass_flowrate_result 
-            = find_root_brent(
-                upper_bound.value,
-                lower_bound.value,
-                &mass_flow_from_pressure_chg_root,
-                &mut convergency);
-
-        return MassRate::new::<kilogram_per_second>(mass_flowrate_result.unwrap());
+        return self.try_get_mass_flowrate_from_pressure_change(
+            pressure_change).unwrap();
     }
 
 
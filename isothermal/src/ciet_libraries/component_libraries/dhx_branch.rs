@@ -59,10 +59,12 @@ use crate::{
     Pipe26, StaticMixer21, Pipe25a, DHXShellSideHeatExchanger, 
     StaticMixer20, Pipe23a, Pipe22, Flowmeter20WithHighKCheckValve, 
     Pipe21, Pipe20, Pipe19, Flowmeter20};
+use crate::branch::{TherminolBranch, TherminolBranchComponent};
+use crate::check_valve::CheckValve;
 
-extern crate roots;
-use roots::find_root_brent;
-use roots::SimpleConvergency;
+use crate::ciet_solver::{CIETSolverError, 
+    calculate_branch_mass_flowrate_from_pressure_change,
+    check_component_fluid_properties};
 
 pub struct DHXBranch<'dhx_branch_lifetime> {
 
@@ -168,6 +170,99 @@ impl<'dhx_branch_lifetime> DHXBranch<'dhx_branch_lifetime> {
     pub fn get_pipe19(&self) -> TherminolPipe {
         return self.pipe19.get();
     }
+
+    /// returns a TherminolBranch which owns all the components
+    /// of the dhx branch, in the same order as the fluid component
+    /// vector (top header to bottom header)
+    ///
+    /// as with get_component_names, flowmeter 20 with the high K 
+    /// check valve is not used, instead flowmeter 20 is the 
+    /// housing of a CheckValve
+    pub fn get_owned_branch(&self) -> TherminolBranch {
+
+        let components: Vec<TherminolBranchComponent> = vec![
+            self.pipe26.get().into(),
+            self.static_mixer_21.get().into(),
+            self.pipe25a.get().into(),
+            self.dhx_shell_side_heat_exchanger.get().into(),
+            self.static_mixer_20.get().into(),
+            self.pipe23a.get().into(),
+            self.pipe22.get().into(),
+            CheckValve::dhx_check_valve(self.flowmeter20.get()).into(),
+            self.pipe21.get().into(),
+            self.pipe20.get().into(),
+            self.pipe19.get().into(),
+        ];
+
+        return TherminolBranch::new("dhx_branch", components);
+    }
+
+    /// returns the names of the components in the dhx branch
+    /// in the same order as the fluid component vector 
+    /// (top header to bottom header)
+    ///
+    /// this is used for labelling manometer readings
+    ///
+    /// note that flowmeter 20 with the high K check valve is not
+    /// included here, check valve behaviour is handled within 
+    /// get_mass_flowrate_from_pressure_change instead
+    pub fn get_component_names(&self) -> Vec<String> {
+
+        let component_names: Vec<String> = vec![
+            self.get_pipe26().get_name().to_string(),
+            self.get_static_mixer_21().get_name().to_string(),
+            self.get_pipe25a().get_name().to_string(),
+            self.get_dhx_shell_side_heat_exchanger().get_name().to_string(),
+            self.get_static_mixer_20().get_name().to_string(),
+            self.get_pipe23a().get_name().to_string(),
+            self.get_pipe22().get_name().to_string(),
+            self.get_flowmeter20().get_name().to_string(),
+            self.get_pipe21().get_name().to_string(),
+            self.get_pipe20().get_name().to_string(),
+            self.get_pipe19().get_name().to_string(),
+        ];
+
+        return component_names;
+    }
+
+    /// calculates mass flowrate from pressure change, but returns
+    /// a CIETSolverError instead of panicking if the flowrate 
+    /// cannot be found
+    pub fn try_get_mass_flowrate_from_pressure_change(
+        &self,
+        pressure_change: Pressure) -> Result<MassRate, CIETSolverError> {
+
+        let fluid_component_collection_vector = 
+            self.get_immutable_fluid_component_vector();
+
+        for (component_name, fluid_component) in 
+            self.get_component_names().iter().zip(
+                fluid_component_collection_vector.iter()) {
+
+            check_component_fluid_properties(
+                component_name, *fluid_component)?;
+        }
+
+        // here is where i implement the check valve behaviour
+        let zero_flowrate = 
+            MassRate::new::<kilogram_per_second>(0.0);
+        let dhx_branch_hydrostatic_pressure = 
+            self.get_pressure_change(MassRate::new::<kilogram_per_second>(0.0));
+
+        if pressure_change.value > dhx_branch_hydrostatic_pressure.value {
+
+            return Ok(zero_flowrate);
+
+        } 
+
+        return calculate_branch_mass_flowrate_from_pressure_change(
+            pressure_change, 
+            |mass_rate: MassRate| -> Pressure {
+                Self::calculate_pressure_change_from_mass_flowrate(
+                    mass_rate, 
+                    fluid_component_collection_vector)
+            });
+    }
 }
 
 
@@ -194,80 +289,19 @@ impl<'dhx_branch_lifetime> FluidComponentCollectionMethods for DHXBranch<'dhx_br
     ///
     /// no check valve behaviour here
     /// it will be put in the ciet part
+    ///
+    /// # Panics
+    ///
+    /// panics if the flowrate cannot be found, the trait method 
+    /// cannot return a Result, so use 
+    /// try_get_mass_flowrate_from_pressure_change if you want 
+    /// to handle the error
     fn get_mass_flowrate_from_pressure_change(
         &self,
         pressure_change: Pressure) -> MassRate{
 
-
-
-        let fluid_component_collection_vector = 
-            self.get_immutable_fluid_component_vector();
-
-        // here is where i implement the check valve behaviour
-        let zero_flowrate = 
-            MassRate::new::<kilogram_per_second>(0.0);
-        let dhx_branch_hydrostatic_pressure = 
-            self.get_pressure_change(MassRate::new::<kilogram_per_second>(0.0));
-
-        if pressure_change.value > dhx_branch_hydrostatic_pressure.value {
-
-            return zero_flowrate;
-
-        } 
-
-        // i'm keeping bounds artificially low for ciet
-        // -1 or +1 kg/s
-        let upper_bound = MassRate::new::<kilogram_per_second>(1.0);
-
-
-        let lower_bound = MassRate::new::<kilogram_per_second>(-1.0);
-
-
-        // now we have a function comparing the pressure change
-        // to the pressure change of the calculated value
-
-        let mass_flow_from_pressure_chg_root = 
-            |mass_flow_kg_per_s: f64| -> f64 {
-
-            let mass_flow_kg_per_s_double = mass_flow_kg_per_s; 
-
-            let mass_rate = 
-                MassRate::new::<kilogram_per_second>(
-                    mass_flow_kg_per_s_double);
-
-
-            let pressure_change_tested = 
-                Self::calculate_pressure_change_from_mass_flowrate(
-                mass_rate, 
-                fluid_component_collection_vector);
-
-            // now i've obtained the pressure change, i convert it to f64
-
-            let pressure_change_user_stipulated_pascals_f64 = 
-                pressure_change.value;
-
-            // since we are finding root, then we must also
-            // subtract it from our pressure change value
-
-
-            let pressure_change_error: f64 =
-                pressure_change_user_stipulated_pascals_f64 - 
-                pressure_change_tested.value;
-
-            return pressure_change_error;
-
-        };
-
-        let mut convergency = SimpleConvergency { eps:1e-9_f64, max_iter:30 };
-
-        let mass_flowrate_result 
-            = find_root_brent(
-                upper_bound.value,
-                lower_bound.value,
-                &mass_flow_from_pressure_chg_root,
-                &mut convergency);
-
-        return MassRate::new::<kilogram_per_second>(mass_flowrate_result.unwrap());
+        return self.try_get_mass_flowrate_from_pressure_change(
+            pressure_change).unwrap();
     }
 
 
@@ -0,0 +1,156 @@
+// This application was developed for use in my PhD thesis under supervision 
+// of Professor Per F. Peterson. It is part of a thermal hydraulics
+// library in Rust that is released under the GNU General Public License
+// v 3.0. This is partly due to the fact that some of the libraries 
+// inherit from GeN-Foam and OpenFOAM, both licensed under GNU General
+// Public License v3.0.
+//
+// As such, the entire library is released under GNU GPL v3.0. It is a strong 
+// copyleft license which means you cannot use it in proprietary software.
+//
+//
+// License
+//    This application is part ciet_digital_twin_rust which uses
+//    fluid_mechanics_rust, a partial library of the
+//    thermal hydraulics library written in rust meant to help with the
+//    fluid mechanics aspects of the calculations
+//     
+//    Copyright (C) 2022-2023  Theodore Kay Chen Ong, Singapore Nuclear
+//    Research and Safety Initiative, Per F. Peterson, University of 
+//    California, Berkeley Thermal Hydraulics Laboratory
+//
+//    ciet_digital_twin_rust is free software; you can redistribute it and/or modify it
+//    under the terms of the GNU General Public License as published by the
+//    Free Software Foundation; either version 2 of the License, or (at your
+//    option) any later version.
+//
+//    ciet_digital_twin_rust is distributed in the hope that it will be useful, but WITHOUT
+//    ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or
+//    FITNESS FOR A PARTICULAR PURPOSE.  See the GNU General Public License
+//    for more details.
+//
+//    ciet_digital_twin_rust relies on a thermal hydraulics library
+//    also written in rust. The isothermal digital twin in particular
+//    relies on fluid_mechanics_rust.
+//    This library is part of a thermal hydraulics library in rust
+//    and contains some code copied from GeN-Foam, and OpenFOAM derivative.
+//    This offering is not approved or endorsed by the OpenFOAM Foundation nor
+//    OpenCFD Limited, producer and distributor of the OpenFOAM(R)software via
+//    www.openfoam.com, and owner of the OPENFOAM(R) and OpenCFD(R) trademarks.
+//    Nor is it endorsed by the authors and owners of GeN-Foam.
+//
+//    You should have received a copy of the GNU General Public License
+//    along with this program.  If not, see <http://www.gnu.org/licenses/>.
+//
+// © All rights reserved. Theodore Kay Chen Ong,
+// Singapore Nuclear Research and Safety Initiative,
+// Per F. Peterson,
+// University of California, Berkeley Thermal Hydraulics Laboratory
+//
+// Main author of the code: Theodore Kay Chen Ong, supervised by
+// Professor Per F. Peterson
+use crate::{Pipe34, Pipe33, StaticMixer61, Pipe31a, Flowmeter30, 
+    DHXTubeSideHeatExchanger, Pipe30a, TCHX, StaticMixer60, Pipe36a, 
+    Flowmeter60, Pipe37, Pipe38};
+use crate::branch::{TherminolBranch, TherminolBranchComponent};
+
+/// the hot leg of the DRACS loop, from the top of the loop
+/// down through the DHX tube side to the bottom of the loop
+///
+/// in natural convection, fluid heated in the DHX rises up 
+/// this leg, so the flow is negative (bottom to top)
+///
+/// flowmeter 30 is placed here to measure the DRACS flow 
+/// through the DHX
+pub struct DRACSHotBranch {
+    pipe34: Pipe34,
+    pipe33: Pipe33,
+    // item 31
+    static_mixer_61: StaticMixer61,
+    pipe31a: Pipe31a,
+    flowmeter30: Flowmeter30,
+    // item 30b
+    dhx_tube_side_heat_exchanger: DHXTubeSideHeatExchanger,
+    pipe30a: Pipe30a,
+}
+
+impl DRACSHotBranch {
+
+    /// constructor, returns an instance of the DRACS hot leg
+    pub fn new() -> Self {
+        return Self {
+            pipe34: Pipe34::new(),
+            pipe33: Pipe33::new(),
+            static_mixer_61: StaticMixer61::new(),
+            pipe31a: Pipe31a::new(),
+            flowmeter30: Flowmeter30::new(),
+            dhx_tube_side_heat_exchanger: DHXTubeSideHeatExchanger::new(),
+            pipe30a: Pipe30a::new(),
+        };
+    }
+
+    /// returns a TherminolBranch which owns all the components
+    /// of the DRACS hot leg (top of the loop to bottom)
+    pub fn get_owned_branch(&self) -> TherminolBranch {
+
+        let components: Vec<TherminolBranchComponent> = vec![
+            self.pipe34.get().into(),
+            self.pipe33.get().into(),
+            self.static_mixer_61.get().into(),
+            self.pipe31a.get().into(),
+            self.flowmeter30.get().into(),
+            self.dhx_tube_side_heat_exchanger.get().into(),
+            self.pipe30a.get().into(),
+        ];
+
+        return TherminolBranch::new("dracs_hot_branch", components);
+    }
+}
+
+/// the cold leg of the DRACS loop, from the top of the loop 
+/// down through the TCHX to the bottom of the loop
+///
+/// in natural convection, fluid cooled in the TCHX sinks down
+/// this leg, so the flow is positive (top to bottom)
+pub struct DRACSColdBranch {
+    // item 35
+    tchx: TCHX,
+    // item 36
+    static_mixer_60: StaticMixer60,
+    pipe36a: Pipe36a,
+    // item 37a
+    flowmeter60: Flowmeter60,
+    pipe37: Pipe37,
+    pipe38: Pipe38,
+}
+
+impl DRACSColdBranch {
+
+    /// constructor, returns an instance of the DRACS cold leg
+    pub fn new() -> Self {
+        return Self {
+            tchx: TCHX::new(),
+            static_mixer_60: StaticMixer60::new(),
+            pipe36a: Pipe36a::new(),
+            flowmeter60: Flowmeter60::new(),
+            pipe37: Pipe37::new(),
+            pipe38: Pipe38::new(),
+        };
+    }
+
+    /// returns a TherminolBranch which owns all the components
+    /// of the DRACS cold leg (top of the loop to bottom)
+    pub fn get_owned_branch(&self) -> TherminolBranch {
+
+        let components: Vec<TherminolBranchComponent> = vec![
+            self.tchx.get().into(),
+            self.static_mixer_60.get().into(),
+            self.pipe36a.get().into(),
+            self.flowmeter60.get().into(),
+            self.pipe37.get().into(),
+            self.pipe38.get().into(),
+        ];
+
+        return TherminolBranch::new("dracs_cold_branch", components);
+    }
+}
@@ -55,10 +55,26 @@ use fluid_mechanics_rust::prelude::*;
 use crate::{Branch5, 
     therminol_pipe::TherminolPipe, therminol_component::TherminolCustomComponent, 
     Pipe4, Pipe3, StaticMixer10, Pipe2a, HeaterTopHead1a, 
-    CietHeaterVersion1, HeaterBottomHead1b, Pipe18};
-extern crate roots;
-use roots::find_root_brent;
-use roots::SimpleConvergency;
+    CietHeaterVersion1, CietHeaterVersion2, HeaterBottomHead1b, Pipe18};
+use crate::branch::{TherminolBranch, TherminolBranchComponent};
+use crate::ciet_solver::{CIETSolverError, 
+    calculate_branch_mass_flowrate_from_pressure_change,
+    check_component_fluid_properties};
+
+/// the hardware configuration of CIET's heater
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CietHeaterVersion {
+    /// bare annulus (see CietHeaterVersion1)
+    Version1,
+    /// annulus with a twisted-tape insert (see CietHeaterVersion2)
+    Version2,
+}
+
+impl Default for CietHeaterVersion {
+    fn default() -> Self {
+        return Self::Version1;
+    }
+}
 
 pub struct HeaterBranch<'heater_branch_lifetime> {
 
@@ -68,7 +84,9 @@ pub struct HeaterBranch<'heater_branch_lifetime> {
     mixer10: StaticMixer10,
     pipe2a: Pipe2a,
     heater_top_head_1a: HeaterTopHead1a,
-    ciet_heater: CietHeaterVersion1,
+    ciet_heater_version: CietHeaterVersion,
+    ciet_heater_version_1: CietHeaterVersion1,
+    ciet_heater_version_2: CietHeaterVersion2,
     heater_bottom_head_1b: HeaterBottomHead1b,
     pipe18: Pipe18,
 
@@ -79,8 +97,15 @@ pub struct HeaterBranch<'heater_branch_lifetime> {
 
 impl<'heater_branch_lifetime> HeaterBranch<'heater_branch_lifetime> {
 
-    /// constructor, returns an instance of the heater branch
+    /// constructor, returns an instance of the heater branch 
+    /// with the first version of the heater
     pub fn new() -> Self {
+        return Self::new_with_heater_version(CietHeaterVersion::Version1);
+    }
+
+    /// constructor, returns an instance of the heater branch 
+    /// with the given version of the heater
+    pub fn new_with_heater_version(ciet_heater_version: CietHeaterVersion) -> Self {
 
         let empty_vec: Vec<&'heater_branch_lifetime dyn FluidComponent> = vec![];
         
@@ -96,7 +121,9 @@ impl<'heater_branch_lifetime> HeaterBranch<'heater_branch_lifetime> {
             mixer10: StaticMixer10::new(),
             pipe2a: Pipe2a::new(),
             heater_top_head_1a: HeaterTopHead1a::new(),
-            ciet_heater: CietHeaterVersion1::new(),
+            ciet_heater_version: ciet_heater_version,
+            ciet_heater_version_1: CietHeaterVersion1::new(),
+            ciet_heater_version_2: CietHeaterVersion2::new(),
             heater_bottom_head_1b: HeaterBottomHead1b::new(),
             pipe18: Pipe18::new(),
             fluid_component_vector_immutable: empty_vec
@@ -122,8 +149,17 @@ impl<'heater_branch_lifetime> HeaterBranch<'heater_branch_lifetime> {
     pub fn get_heater_top_head_1a(&self) -> TherminolCustomComponent {
         return self.heater_top_head_1a.get();
     }
+    /// returns the heater of the version this branch was 
+    /// constructed with
     pub fn get_ciet_heater(&self) -> TherminolCustomComponent {
-        return self.ciet_heater.get();
+        match self.ciet_heater_version {
+            CietHeaterVersion::Version1 => self.ciet_heater_version_1.get(),
+            CietHeaterVersion::Version2 => self.ciet_heater_version_2.get(),
+        }
+    }
+
+    pub fn get_ciet_heater_version(&self) -> CietHeaterVersion {
+        return self.ciet_heater_version;
     }
     pub fn get_heater_bottom_head_1b(&self) -> TherminolCustomComponent {
         return self.heater_bottom_head_1b.get();
@@ -132,10 +168,80 @@ impl<'heater_branch_lifetime> HeaterBranch<'heater_branch_lifetime> {
         return self.pipe18.get();
     }
 
+    /// returns a TherminolBranch which owns all the components
+    /// of the heater branch, in the same order as the fluid component
+    /// vector (top header to bottom header)
+    pub fn get_owned_branch(&self) -> TherminolBranch {
+
+        let components: Vec<TherminolBranchComponent> = vec![
+            self.branch5.get().into(),
+            self.pipe4.get().into(),
+            self.pipe3.get().into(),
+            self.mixer10.get().into(),
+            self.pipe2a.get().into(),
+            self.heater_top_head_1a.get().into(),
+            self.get_ciet_heater().into(),
+            self.heater_bottom_head_1b.get().into(),
+            self.pipe18.get().into(),
+        ];
+
+        return TherminolBranch::new("heater_branch", components);
+    }
+
+    /// returns the names of the components in the heater branch
+    /// in the same order as the fluid component vector 
+    /// (top header to bottom header)
+    ///
+    /// this is used for labelling manometer readings
+    pub fn get_component_names(&self) -> Vec<String> {
+
+        let component_names: Vec<String> = vec![
+            self.get_branch5().get_name().to_string(),
+            self.get_pipe4().get_name().to_string(),
+            self.get_pipe3().get_name().to_string(),
+            self.get_mixer10().get_name().to_string(),
+            self.get_pipe2a().get_name().to_string(),
+            self.get_heater_top_head_1a().get_name().to_string(),
+            self.get_ciet_heater().get_name().to_string(),
+            self.get_heater_bottom_head_1b().get_name().to_string(),
+            self.get_pipe18().get_name().to_string(),
+        ];
+
+        return component_names;
+    }
+
+
 
 
+    /// calculates mass flowrate from pressure change, but returns
+    /// a CIETSolverError instead of panicking if the flowrate 
+    /// cannot be found
+    pub fn try_get_mass_flowrate_from_pressure_change(
+        &self,
+        pressure_change: Pressure) -> Result<MassRate, CIETSolverError> {
+
+        let fluid_component_collection_vector = 
+            self.get_immutable_fluid_component_vector();
+
+        for (component_name, fluid_component) in 
+            self.get_component_names().iter().zip(
+                fluid_component_collection_vector.iter()) {
+
+            check_component_fluid_properties(
+                component_name, *fluid_component)?;
+        }
+
+        return calculate_branch_mass_flowrate_from_pressure_change(
+            pressure_change, 
+            |mass_rate: MassRate| -> Pressure {
+                Self::calculate_pressure_change_from_mass_flowrate(
+                    mass_rate, 
+                    fluid_component_collection_vector)
+            });
+    }
 }
 
+
 impl<'heater_branch_lifetime> FluidComponentCollectionMethods for HeaterBranch<'heater_branch_lifetime> {
 
     /// calculates pressure change when given a mass flowrate
@@ -155,71 +261,19 @@ impl<'heater_branch_lifetime> FluidComponentCollectionMethods for HeaterBranch<'
     }
 
     /// calculates mass flowrate from pressure change
-
+    ///
+    /// # Panics
+    ///
+    /// panics if the flowrate cannot be found, the trait method 
+    /// cannot return a Result, so use 
+    /// try_get_mass_flowrate_from_pressure_change if you want 
+    /// to handle the error
     fn get_mass_flowrate_from_pressure_change(
         &self,
         pressure_change: Pressure) -> MassRate{
 
-
-
-
-        let fluid_component_collection_vector = 
-            self.get_immutable_fluid_component_vector();
-
-
-        // i'm keeping bounds artificially low for ciet
-        // -1 or +1 kg/s
-        let upper_bound = MassRate::new::<kilogram_per_second>(1.0);
-
-
-        let lower_bound = MassRate::new::<kilogram_per_second>(-1.0);
-
-
-        // now we have a function comparing the pressure change
-        // to the pressure change of the calculated value
-
-        let mass_flow_from_pressure_chg_root = 
-            |mass_flow_kg_per_s: f64| -> f64 {
-
-            let mass_flow_kg_per_s_double = mass_flow_kg_per_s; 
-
-            let mass_rate = 
-                MassRate::new::<kilogram_per_second>(
-                    mass_flow_kg_per_s_double);
-
-
-            let pressure_change_tested = 
-                Self::calculate_pressure_change_from_mass_flowrate(
-                mass_rate, 
-                fluid_component_collection_vector);
-
-            // now i've obtained the pressure change, i convert it to f64
-
-            let pressure_change_user_stipulated_pascals_f64 = 
-                pressure_change.value;
-
-            // since we are finding root, then we must also
-            // subtract it from our pressure change value
-
-
-            let pressure_change_error: f64 =
-                pressure_change_user_stipulated_pascals_f64 - 
-                pressure_change_tested.value;
-
-            return pressure_change_error;
-
-        };
-
-        let mut convergency = SimpleConvergency { eps:1e-9_f64, max_iter:30 };
-
-        let mass_flowrate_result 
-            = find_root_brent(
-                upper_bound.value,
-                lower_bound.value,
-                &mass_flow_from_pressure_chg_root,
-                &mut convergency);
-
-        return MassRate::new::<kilogram_per_second>(mass_flowrate_result.unwrap());
+        return self.try_get_mass_flowrate_from_pressure_change(
+            pressure_change).unwrap();
     }
 
 
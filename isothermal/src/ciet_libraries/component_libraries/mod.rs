@@ -54,6 +54,8 @@ use fluid_mechanics_rust::prelude::*;
 
 use super::therminol_pipe::*;
 use super::therminol_component::*;
+use super::pipe_wall::{HeatTransferCorrelation, get_twisted_tape_swirl_number,
+    LAMINAR_REYNOLDS_NUMBER_LIMIT, TURBULENT_REYNOLDS_NUMBER_LIMIT};
 
 
 /// contains a class for the ctah branch
@@ -69,6 +71,10 @@ pub use heater_branch::*;
 pub mod dhx_branch;
 pub use dhx_branch::*;
 
+/// contains classes for the hot and cold legs of the DRACS loop
+pub mod dracs_branches;
+pub use dracs_branches::*;
+
 /// Pipe6a in Compact Integral Effects Test (CIET)
 /// CTAH branch 
 ///
@@ -170,7 +176,7 @@ impl StaticMixer41 {
 
     /// returns an instance of MX-41
     /// or component no.6
-    pub fn get(&self) -> TherminolCustomComponent {
+    pub fn get(&self) -> TherminolCustomComponent<'static> {
 
         let name = "static_mixer_41_label_6";
 
@@ -265,7 +271,7 @@ impl CTAHVertical {
 
     }
 
-    pub fn get(&self) -> TherminolCustomComponent {
+    pub fn get(&self) -> TherminolCustomComponent<'static> {
 
         let name = "ctah_vertical_label_7a";
 
@@ -355,7 +361,7 @@ impl CTAHHorizontal {
     /// returns an instance of the
     /// horizontal portion of CTAH
 
-    pub fn get(&self) -> TherminolCustomComponent {
+    pub fn get(&self) -> TherminolCustomComponent<'static> {
 
         let name = "ctah_horizontal_label_7b";
 
@@ -480,7 +486,7 @@ impl StaticMixer40 {
     }
 
     /// returns an instance of MX-40
-    pub fn get(&self) -> TherminolCustomComponent {
+    pub fn get(&self) -> TherminolCustomComponent<'static> {
 
         let name = "static_mixer_40_label_8";
 
@@ -727,7 +733,7 @@ impl CTAHPump {
 
     /// returns an instance of the pump with an internal
     /// pressure term set by the user in the get method
-    pub fn get(&self) -> TherminolCustomComponent {
+    pub fn get(&self) -> TherminolCustomComponent<'static> {
 
         let name = "ctah_pump";
 
@@ -907,7 +913,7 @@ impl Flowmeter40 {
     }
 
     /// returns an instance of FM-40 (14a)
-    pub fn get(&self) -> TherminolCustomComponent {
+    pub fn get(&self) -> TherminolCustomComponent<'static> {
 
         let name = "flowmeter_40_14a";
 
@@ -1333,7 +1339,7 @@ impl StaticMixer10 {
     }
 
     /// returns an instance of MX-10
-    pub fn get(&self) -> TherminolCustomComponent {
+    pub fn get(&self) -> TherminolCustomComponent<'static> {
 
         let name = "static_mixer_10_label_2";
 
@@ -1481,7 +1487,7 @@ impl HeaterTopHead1a {
     }
 
     /// returns an instance of heater top head 1a
-    pub fn get(&self) -> TherminolCustomComponent {
+    pub fn get(&self) -> TherminolCustomComponent<'static> {
 
         let name = "heater_top_head_label_1a";
 
@@ -1576,7 +1582,7 @@ impl CietHeaterVersion1 {
     }
 
     /// returns an instance of CIET heater version 1
-    pub fn get(&self) -> TherminolCustomComponent {
+    pub fn get(&self) -> TherminolCustomComponent<'static> {
 
         let name = "heater_version_1_label_1";
 
@@ -1614,6 +1620,171 @@ impl CietHeaterVersion1 {
     }
 }
 
+/// This is the second version of CIET's heater
+/// 
+/// It is found in CIET's heater branch in place of the first
+/// version, it has the same annulus but with a twisted-tape 
+/// insert, which raises both the friction factor and the 
+/// heat transfer coefficient
+pub struct CietHeaterVersion2 {
+
+    // this is the second version of the ciet heater
+    // with a twisted tape insert within the heater
+    //
+    // inclined at 90 degrees bottom to top
+    // or 90 degrees + 180 top to bottom orientation
+    
+}
+
+impl CietHeaterVersion2 {
+
+    /// twist ratio of the insert, the length of a 180 degree 
+    /// twist divided by the hydraulic diameter
+    pub const TWIST_RATIO: f64 = 4.0;
+
+    /// tape thickness of the insert divided by the hydraulic 
+    /// diameter
+    pub const TAPE_THICKNESS_RATIO: f64 = 0.05;
+
+    /// custom darcy is taken from the Manglik and Bergles (1993)
+    /// twisted-tape friction factor
+    ///
+    /// laminar flow uses the swirl flow correlation, turbulent 
+    /// flow uses the Blasius form with the twisted-tape 
+    /// enhancement factors, and the transition region is 
+    /// interpolated between the two
+    ///
+    /// the tape dominates the losses, so roughness is not used
+    pub fn custom_darcy(mut reynolds_number: f64, roughness_ratio: f64) -> f64 {
+
+        if roughness_ratio < 0.0 {
+            panic!("roughness_ratio < 0.0");
+        }
+
+        let mut reverse_flow = false;
+
+        // the user account for reverse flow scenarios...
+        if reynolds_number < 0.0 {
+            reverse_flow = true;
+            reynolds_number = reynolds_number * -1.0;
+        }
+
+        let twist_ratio = Self::TWIST_RATIO;
+        let tape_thickness_ratio = Self::TAPE_THICKNESS_RATIO;
+
+        let blockage_factor = std::f64::consts::PI / 
+            (std::f64::consts::PI - 4.0 * tape_thickness_ratio);
+        let wetted_perimeter_factor = 
+            (std::f64::consts::PI + 2.0 - 2.0 * tape_thickness_ratio) / 
+            (std::f64::consts::PI - 4.0 * tape_thickness_ratio);
+
+        // these are fanning friction factors, darcy is 4 times 
+        // fanning
+        let laminar_darcy = |reynolds_number: f64| -> f64 {
+            let swirl_number = get_twisted_tape_swirl_number(
+                reynolds_number, twist_ratio, tape_thickness_ratio);
+
+            let fanning = 15.767 / reynolds_number * 
+                wetted_perimeter_factor.powi(2) * 
+                (1.0 + 1.0e-6 * swirl_number.powf(2.55)).powf(1.0/6.0);
+
+            return 4.0 * fanning;
+        };
+
+        let turbulent_darcy = |reynolds_number: f64| -> f64 {
+            let fanning = 0.0791 / reynolds_number.powf(0.25) * 
+                (1.0 + 2.752 / twist_ratio.powf(1.29)) * 
+                blockage_factor.powf(1.75) * 
+                wetted_perimeter_factor.powf(1.25);
+
+            return 4.0 * fanning;
+        };
+
+        let darcy = if reynolds_number <= LAMINAR_REYNOLDS_NUMBER_LIMIT {
+            laminar_darcy(reynolds_number)
+        } else if reynolds_number >= TURBULENT_REYNOLDS_NUMBER_LIMIT {
+            turbulent_darcy(reynolds_number)
+        } else {
+            let transition_fraction = 
+                (reynolds_number - LAMINAR_REYNOLDS_NUMBER_LIMIT) / 
+                (TURBULENT_REYNOLDS_NUMBER_LIMIT - LAMINAR_REYNOLDS_NUMBER_LIMIT);
+
+            let laminar_limit_darcy = laminar_darcy(LAMINAR_REYNOLDS_NUMBER_LIMIT);
+            let turbulent_limit_darcy = turbulent_darcy(TURBULENT_REYNOLDS_NUMBER_LIMIT);
+
+            laminar_limit_darcy + transition_fraction * 
+                (turbulent_limit_darcy - laminar_limit_darcy)
+        };
+
+        if reverse_flow {
+            return -darcy;
+        }
+        return darcy;
+    }
+
+    /// K = 0 for CIET's heater version 2, the insert 
+    /// losses are within the friction factor
+    pub fn custom_k(reynolds_number: f64) -> f64 {
+
+        let custom_k_value = 0.0;
+
+        if reynolds_number < 0.0 {
+            return -custom_k_value
+        }
+
+        return custom_k_value;
+
+    }
+
+    /// returns the twisted-tape heat transfer correlation 
+    /// for this insert
+    pub fn get_heat_transfer_correlation() -> HeatTransferCorrelation {
+        return HeatTransferCorrelation::TwistedTape { 
+            twist_ratio: Self::TWIST_RATIO, 
+            tape_thickness_ratio: Self::TAPE_THICKNESS_RATIO,
+        };
+    }
+
+    /// returns an instance of CIET heater version 2
+    pub fn get(&self) -> TherminolCustomComponent<'static> {
+
+        let name = "heater_version_2_label_1";
+
+        
+        let fluid_temp = ThermodynamicTemperature::new::<degree_celsius>(21.0);
+
+        let hydraulic_diameter = Length::new::<meter>(6.60e-3);
+        let component_length = Length::new::<meter>(1.6383);
+        let cross_sectional_area = Area::new::<square_meter>(3.64e-4);
+        let absolute_roughness = Length::new::<millimeter>(0.015);
+        let incline_angle = Angle::new::<degree>(90.0 + 180.0);
+
+        let mut heater_version_2_label_1: TherminolCustomComponent
+            = TherminolCustomComponent::new(
+                name, 
+                fluid_temp, 
+                incline_angle, 
+                component_length, 
+                cross_sectional_area, 
+                hydraulic_diameter, 
+                absolute_roughness, 
+                 
+                &Self::custom_k, 
+                &Self::custom_darcy);
+
+        heater_version_2_label_1.set_heat_transfer_correlation(
+            Self::get_heat_transfer_correlation());
+
+        return heater_version_2_label_1;
+    }
+
+    pub fn new() -> Self {
+
+        return Self {  }
+
+    }
+}
+
 /// heater bottom head 1b within CIET's heater branch
 pub struct HeaterBottomHead1b {
 
@@ -1669,7 +1840,7 @@ impl HeaterBottomHead1b {
     }
 
     /// returns an instance of heater bottom head 1b
-    pub fn get(&self) -> TherminolCustomComponent {
+    pub fn get(&self) -> TherminolCustomComponent<'static> {
 
         let name = "heater_bottom_head_label_1b";
 
@@ -1865,7 +2036,7 @@ impl StaticMixer21 {
     /// returns an instance of MX-21
     ///
     /// It is labelled 25 on diagram
-    pub fn get(&self) -> TherminolCustomComponent {
+    pub fn get(&self) -> TherminolCustomComponent<'static> {
 
         let name = "static_mixer_21_label_25";
 
@@ -2011,7 +2182,7 @@ impl DHXShellSideHeatExchanger {
 
     /// returns an instance of dhx shell side
     /// heat exchanger 24
-    pub fn get(&self) -> TherminolCustomComponent {
+    pub fn get(&self) -> TherminolCustomComponent<'static> {
 
         let name = "dhx_shell_side_label_24";
 
@@ -2104,7 +2275,7 @@ impl StaticMixer20 {
 
     /// returns an instance of MX-20
     /// label 23
-    pub fn get(&self) -> TherminolCustomComponent {
+    pub fn get(&self) -> TherminolCustomComponent<'static> {
 
         let name = "static_mixer_20_label_23";
 
@@ -2290,7 +2461,7 @@ impl Flowmeter20 {
 
     /// returns an isntance of 
     /// FM-20 (label 21a)
-    pub fn get(&self) -> TherminolCustomComponent {
+    pub fn get(&self) -> TherminolCustomComponent<'static> {
 
         let name = "flowmeter_20_label_21a";
 
@@ -2406,7 +2577,7 @@ impl Flowmeter20WithHighKCheckValve {
 
     /// returns an instance of FM-20
     /// with artificial check valve behaviour
-    pub fn get(&self) -> TherminolCustomComponent {
+    pub fn get(&self) -> TherminolCustomComponent<'static> {
 
         let name = "flowmeter_20_label_21a_with_check_valve";
 
@@ -2632,7 +2803,7 @@ impl Flowmeter30 {
     }
 
     /// returns an instance of FM-30
-    pub fn get(&self) -> TherminolCustomComponent {
+    pub fn get(&self) -> TherminolCustomComponent<'static> {
 
         let name = "flowmeter_30";
 
@@ -2729,7 +2900,7 @@ impl Flowmeter60 {
     }
 
     /// returns an instance of FM-60 within DRACS loop
-    pub fn get(&self) -> TherminolCustomComponent {
+    pub fn get(&self) -> TherminolCustomComponent<'static> {
 
         let name = "flowmeter_60_label_37a";
 
@@ -2821,7 +2992,7 @@ impl StaticMixer60 {
 
     /// returns an instance of MX-60
     /// static mixer 
-    pub fn get(&self) -> TherminolCustomComponent {
+    pub fn get(&self) -> TherminolCustomComponent<'static> {
 
         let name = "static_mixer_60_label_36";
 
@@ -2911,7 +3082,7 @@ impl StaticMixer61 {
     }
 
     /// returns an instance of static mixer 61
-    pub fn get(&self) -> TherminolCustomComponent {
+    pub fn get(&self) -> TherminolCustomComponent<'static> {
 
         let name = "static_mixer_61_label_31";
 
@@ -2948,6 +3119,521 @@ impl StaticMixer61 {
     }
 }
 
+// the rest of the DRACS loop is here, the hot leg runs from 
+// the DHX tube side up to the TCHX, and the cold leg runs from
+// the TCHX back down to the DHX
+//
+// the hot and cold legs are oriented from top to bottom, like
+// the primary loop branches. Their lengths are chosen so that
+// both legs drop by the same height (3.87745 m) from the top 
+// of the loop to the bottom, so that there is no driving head
+// when the whole loop is at one temperature
+//
+// the geometry and correlations here are illustrative and have 
+// not been checked against the CIET drawings or DRACS data yet
+
+/// pipe number 34 in CIET's DRACS hot leg, at the top of the loop
+/// leading into the TCHX
+pub struct Pipe34 {
+    // horizontal, top of the hot leg
+    
+}
+
+impl Pipe34 {
+
+    /// returns instance of pipe 34
+    pub fn get(&self) -> TherminolPipe{
+
+
+        let name = "pipe_34";
+
+        let fluid_temp = ThermodynamicTemperature::new::<degree_celsius>(21.0);
+        let hydraulic_diameter = Length::new::<meter>(2.79e-2);
+        let component_length = Length::new::<meter>(0.5);
+        // note that aboslute roughness doesn't matter here really
+        // because we are having laminar flow in the experimental data range
+        let absolute_roughness = Length::new::<millimeter>(0.015);
+        let incline_angle = Angle::new::<degree>(0.0);
+        let form_loss_k = 0.45;
+
+
+        let pipe_34 = TherminolPipe::new(
+            name, 
+            fluid_temp, 
+            incline_angle, 
+            component_length, 
+            hydraulic_diameter, 
+            form_loss_k, 
+            absolute_roughness, 
+            );
+
+        return pipe_34;
+    }
+
+    pub fn new() -> Self {
+
+        return Self {  }
+
+    }
+
+}
+
+/// pipe number 33 in CIET's DRACS hot leg, this is the riser
+pub struct Pipe33 {
+    // vertical, in natural convection, flow goes up this pipe
+    
+}
+
+impl Pipe33 {
+
+    /// returns instance of pipe 33
+    pub fn get(&self) -> TherminolPipe{
+
+
+        let name = "pipe_33";
+
+        let fluid_temp = ThermodynamicTemperature::new::<degree_celsius>(21.0);
+        let hydraulic_diameter = Length::new::<meter>(2.79e-2);
+        let component_length = Length::new::<meter>(1.5);
+        // note that aboslute roughness doesn't matter here really
+        // because we are having laminar flow in the experimental data range
+        let absolute_roughness = Length::new::<millimeter>(0.015);
+        let incline_angle = Angle::new::<degree>(-90.0);
+        let form_loss_k = 0.45;
+
+
+        let pipe_33 = TherminolPipe::new(
+            name, 
+            fluid_temp, 
+            incline_angle, 
+            component_length, 
+            hydraulic_diameter, 
+            form_loss_k, 
+            absolute_roughness, 
+            );
+
+        return pipe_33;
+    }
+
+    pub fn new() -> Self {
+
+        return Self {  }
+
+    }
+
+}
+
+/// pipe number 31a in CIET's DRACS hot leg, between MX-61
+/// and flowmeter 30
+pub struct Pipe31a {
+    // vertical
+    
+}
+
+impl Pipe31a {
+
+    /// returns instance of pipe 31a
+    pub fn get(&self) -> TherminolPipe{
+
+
+        let name = "pipe_31a";
+
+        let fluid_temp = ThermodynamicTemperature::new::<degree_celsius>(21.0);
+        let hydraulic_diameter = Length::new::<meter>(2.79e-2);
+        let component_length = Length::new::<meter>(0.2);
+        // note that aboslute roughness doesn't matter here really
+        // because we are having laminar flow in the experimental data range
+        let absolute_roughness = Length::new::<millimeter>(0.015);
+        let incline_angle = Angle::new::<degree>(-90.0);
+        let form_loss_k = 0.0;
+
+
+        let pipe_31a = TherminolPipe::new(
+            name, 
+            fluid_temp, 
+            incline_angle, 
+            component_length, 
+            hydraulic_diameter, 
+            form_loss_k, 
+            absolute_roughness, 
+            );
+
+        return pipe_31a;
+    }
+
+    pub fn new() -> Self {
+
+        return Self {  }
+
+    }
+
+}
+
+/// DHX tube side (DRACS side of the DRACS heat exchanger)
+///
+/// label 30b on diagram
+///
+/// the fluid here is heated by the primary loop fluid on the 
+/// shell side (label 24)
+///
+/// inclined at 90 degrees bottom to top
+/// or 90 degrees + 180 top to bottom orientation
+pub struct DHXTubeSideHeatExchanger {
+
+    
+}
+
+impl DHXTubeSideHeatExchanger {
+
+    /// custom darcy here is same as churchill friction factor
+    pub fn custom_darcy(mut reynolds_number: f64, roughness_ratio: f64) -> f64 {
+
+        if roughness_ratio < 0.0 {
+            panic!("roughness_ratio < 0.0");
+        }
+
+        use fluid_mechanics_rust::churchill_friction_factor;
+        let mut reverse_flow = false;
+
+        // the user account for reverse flow scenarios...
+        if reynolds_number < 0.0 {
+            reverse_flow = true;
+            reynolds_number = reynolds_number * -1.0;
+        }
+
+        let darcy = churchill_friction_factor::darcy(reynolds_number,
+                                                     roughness_ratio);
+
+        if reverse_flow {
+            return -darcy;
+        }
+        return darcy;
+    }
+
+    /// custom K is fixed at 3.9
+    ///
+    /// reverse flow logic means K is -3.9
+    pub fn custom_k(reynolds_number: f64) -> f64 {
+
+        let custom_k_value = 3.9;
+
+        if reynolds_number < 0.0 {
+            return -custom_k_value
+        }
+
+        return custom_k_value;
+
+    }
+
+    /// returns an instance of dhx tube side
+    /// heat exchanger 30b
+    pub fn get(&self) -> TherminolCustomComponent<'static> {
+
+        let name = "dhx_tube_side_label_30b";
+
+        
+        let fluid_temp = ThermodynamicTemperature::new::<degree_celsius>(21.0);
+
+        let hydraulic_diameter = Length::new::<meter>(1.09e-2);
+        let component_length = Length::new::<meter>(1.18745);
+        let cross_sectional_area = Area::new::<square_meter>(1.0e-3);
+        // note that aboslute roughness doesn't matter here really
+        // because we are having laminar flow in the experimental data range
+        let absolute_roughness = Length::new::<millimeter>(0.015);
+        let incline_angle = Angle::new::<degree>(90.0 + 180.0);
+
+        let dhx_tube_side_label_30b: TherminolCustomComponent
+            = TherminolCustomComponent::new(
+                name, 
+                fluid_temp, 
+                incline_angle, 
+                component_length, 
+                cross_sectional_area, 
+                hydraulic_diameter, 
+                absolute_roughness, 
+                 
+                &Self::custom_k, 
+                &Self::custom_darcy);
+
+        return dhx_tube_side_label_30b;
+    }
+
+    pub fn new() -> Self {
+
+        return Self {  }
+
+    }
+}
+
+/// pipe number 30a in CIET's DRACS hot leg, at the bottom of
+/// the loop below the DHX tube side
+pub struct Pipe30a {
+    // vertical
+    
+}
+
+impl Pipe30a {
+
+    /// returns instance of pipe 30a
+    pub fn get(&self) -> TherminolPipe{
+
+
+        let name = "pipe_30a";
+
+        let fluid_temp = ThermodynamicTemperature::new::<degree_celsius>(21.0);
+        let hydraulic_diameter = Length::new::<meter>(2.79e-2);
+        let component_length = Length::new::<meter>(0.3);
+        // note that aboslute roughness doesn't matter here really
+        // because we are having laminar flow in the experimental data range
+        let absolute_roughness = Length::new::<millimeter>(0.015);
+        let incline_angle = Angle::new::<degree>(-90.0);
+        let form_loss_k = 0.45;
+
+
+        let pipe_30a = TherminolPipe::new(
+            name, 
+            fluid_temp, 
+            incline_angle, 
+            component_length, 
+            hydraulic_diameter, 
+            form_loss_k, 
+            absolute_roughness, 
+            );
+
+        return pipe_30a;
+    }
+
+    pub fn new() -> Self {
+
+        return Self {  }
+
+    }
+
+}
+
+/// TCHX (thermosyphon cooled heat exchanger) in the DRACS loop
+///
+/// label 35 on diagram
+///
+/// this is where the DRACS fluid is cooled by air, in natural
+/// convection, flow goes from top to bottom
+pub struct TCHX {
+    
+}
+
+impl TCHX {
+
+    /// darcy = 0
+    /// 
+    /// as fldk is empirical and indpendent of L/D
+    /// in that correlation
+    pub fn custom_darcy(_reynolds_number: f64, _roughness_ratio: f64) -> f64 {
+        return 0.0;
+    }
+
+    /// custom K = 400 + 52000/Re
+    ///
+    /// because
+    /// fldk = 400 + 52000/Re
+    pub fn custom_k(mut reynolds_number: f64) -> f64 {
+        let mut reverse_flow = false;
+
+        // the user account for reverse flow scenarios...
+        if reynolds_number < 0.0 {
+            reverse_flow = true;
+            reynolds_number = reynolds_number * -1.0;
+        }
+
+        let custom_k_value =
+            400.0 + 52000.0/reynolds_number;
+
+        if reverse_flow {
+            return -custom_k_value;
+        }
+
+        return custom_k_value;
+
+    }
+
+    /// returns an instance of the TCHX
+    pub fn get(&self) -> TherminolCustomComponent<'static> {
+
+        let name = "tchx_label_35";
+
+        
+        let fluid_temp = ThermodynamicTemperature::new::<degree_celsius>(21.0);
+
+        let hydraulic_diameter = Length::new::<meter>(1.19e-2);
+        let component_length = Length::new::<meter>(1.2);
+        let cross_sectional_area = Area::new::<square_meter>(1.33e-3);
+        // note that aboslute roughness doesn't matter here really
+        // because we are having laminar flow in the experimental data range
+        let absolute_roughness = Length::new::<millimeter>(0.015);
+        let incline_angle = Angle::new::<degree>(-90.0);
+
+        let tchx_label_35: TherminolCustomComponent
+            = TherminolCustomComponent::new(
+                name, 
+                fluid_temp, 
+                incline_angle, 
+                component_length, 
+                cross_sectional_area, 
+                hydraulic_diameter, 
+                absolute_roughness, 
+                &Self::custom_k, 
+                &Self::custom_darcy);
+
+        return tchx_label_35;
+    }
+
+    pub fn new() -> Self {
+
+        return Self {  }
+
+    }
+}
+
+/// pipe number 36a in CIET's DRACS cold leg, between MX-60
+/// and flowmeter 60
+pub struct Pipe36a {
+    // vertical
+    
+}
+
+impl Pipe36a {
+
+    /// returns instance of pipe 36a
+    pub fn get(&self) -> TherminolPipe{
+
+
+        let name = "pipe_36a";
+
+        let fluid_temp = ThermodynamicTemperature::new::<degree_celsius>(21.0);
+        let hydraulic_diameter = Length::new::<meter>(2.79e-2);
+        let component_length = Length::new::<meter>(0.4);
+        // note that aboslute roughness doesn't matter here really
+        // because we are having laminar flow in the experimental data range
+        let absolute_roughness = Length::new::<millimeter>(0.015);
+        let incline_angle = Angle::new::<degree>(-90.0);
+        let form_loss_k = 0.0;
+
+
+        let pipe_36a = TherminolPipe::new(
+            name, 
+            fluid_temp, 
+            incline_angle, 
+            component_length, 
+            hydraulic_diameter, 
+            form_loss_k, 
+            absolute_roughness, 
+            );
+
+        return pipe_36a;
+    }
+
+    pub fn new() -> Self {
+
+        return Self {  }
+
+    }
+
+}
+
+/// pipe number 37 in CIET's DRACS cold leg, this is the 
+/// downcomer
+///
+/// its length makes the cold leg drop by the same height as 
+/// the hot leg
+pub struct Pipe37 {
+    // vertical, in natural convection, flow goes down this pipe
+    
+}
+
+impl Pipe37 {
+
+    /// returns instance of pipe 37
+    pub fn get(&self) -> TherminolPipe{
+
+
+        let name = "pipe_37";
+
+        let fluid_temp = ThermodynamicTemperature::new::<degree_celsius>(21.0);
+        let hydraulic_diameter = Length::new::<meter>(2.79e-2);
+        let component_length = Length::new::<meter>(1.634593);
+        // note that aboslute roughness doesn't matter here really
+        // because we are having laminar flow in the experimental data range
+        let absolute_roughness = Length::new::<millimeter>(0.015);
+        let incline_angle = Angle::new::<degree>(-90.0);
+        let form_loss_k = 0.45;
+
+
+        let pipe_37 = TherminolPipe::new(
+            name, 
+            fluid_temp, 
+            incline_angle, 
+            component_length, 
+            hydraulic_diameter, 
+            form_loss_k, 
+            absolute_roughness, 
+            );
+
+        return pipe_37;
+    }
+
+    pub fn new() -> Self {
+
+        return Self {  }
+
+    }
+
+}
+
+/// pipe number 38 in CIET's DRACS cold leg, at the bottom of
+/// the loop leading back to the DHX
+pub struct Pipe38 {
+    // horizontal, bottom of the cold leg
+    
+}
+
+impl Pipe38 {
+
+    /// returns instance of pipe 38
+    pub fn get(&self) -> TherminolPipe{
+
+
+        let name = "pipe_38";
+
+        let fluid_temp = ThermodynamicTemperature::new::<degree_celsius>(21.0);
+        let hydraulic_diameter = Length::new::<meter>(2.79e-2);
+        let component_length = Length::new::<meter>(1.0);
+        // note that aboslute roughness doesn't matter here really
+        // because we are having laminar flow in the experimental data range
+        let absolute_roughness = Length::new::<millimeter>(0.015);
+        let incline_angle = Angle::new::<degree>(0.0);
+        let form_loss_k = 0.45;
+
+
+        let pipe_38 = TherminolPipe::new(
+            name, 
+            fluid_temp, 
+            incline_angle, 
+            component_length, 
+            hydraulic_diameter, 
+            form_loss_k, 
+            absolute_roughness, 
+            );
+
+        return pipe_38;
+    }
+
+    pub fn new() -> Self {
+
+        return Self {  }
+
+    }
+
+}
+
 // miscellaneous items are here
 //
 //
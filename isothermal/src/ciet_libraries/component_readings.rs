@@ -0,0 +1,125 @@
+// This application was developed for use in my PhD thesis under supervision 
+// of Professor Per F. Peterson. It is part of a thermal hydraulics
+// library in Rust that is released under the GNU General Public License
+// v 3.0. This is partly due to the fact that some of the libraries 
+// inherit from GeN-Foam and OpenFOAM, both licensed under GNU General
+// Public License v3.0.
+//
+// As such, the entire library is released under GNU GPL v3.0. It is a strong 
+// copyleft license which means you cannot use it in proprietary software.
+//
+//
+// License
+//    This application is part ciet_digital_twin_rust which uses
+//    fluid_mechanics_rust, a partial library of the
+//    thermal hydraulics library written in rust meant to help with the
+//    fluid mechanics aspects of the calculations
+//     
+//    Copyright (C) 2022-2023  Theodore Kay Chen Ong, Singapore Nuclear
+//    Research and Safety Initiative, Per F. Peterson, University of 
+//    California, Berkeley Thermal Hydraulics Laboratory
+//
+//    ciet_digital_twin_rust is free software; you can redistribute it and/or modify it
+//    under the terms of the GNU General Public License as published by the
+//    Free Software Foundation; either version 2 of the License, or (at your
+//    option) any later version.
+//
+//    ciet_digital_twin_rust is distributed in the hope that it will be useful, but WITHOUT
+//    ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or
+//    FITNESS FOR A PARTICULAR PURPOSE.  See the GNU General Public License
+//    for more details.
+//
+//    ciet_digital_twin_rust relies on a thermal hydraulics library
+//    also written in rust. The isothermal digital twin in particular
+//    relies on fluid_mechanics_rust.
+//    This library is part of a thermal hydraulics library in rust
+//    and contains some code copied from GeN-Foam, and OpenFOAM derivative.
+//    This offering is not approved or endorsed by the OpenFOAM Foundation nor
+//    OpenCFD Limited, producer and distributor of the OpenFOAM(R)software via
+//    www.openfoam.com, and owner of the OPENFOAM(R) and OpenCFD(R) trademarks.
+//    Nor is it endorsed by the authors and owners of GeN-Foam.
+//
+//    You should have received a copy of the GNU General Public License
+//    along with this program.  If not, see <http://www.gnu.org/licenses/>.
+//
+// © All rights reserved. Theodore Kay Chen Ong,
+// Singapore Nuclear Research and Safety Initiative,
+// Per F. Peterson,
+// University of California, Berkeley Thermal Hydraulics Laboratory
+//
+// Main author of the code: Theodore Kay Chen Ong, supervised by
+// Professor Per F. Peterson
+extern crate fluid_mechanics_rust;
+use fluid_mechanics_rust::prelude::*;
+
+use crate::therminol_pipe::TherminolPipe;
+use crate::branch::TherminolBranchComponent;
+
+/// the flow conditions and geometry of one component in a 
+/// branch, at the branch mass flowrate
+///
+/// the pressure loss is the frictional and form loss only, the 
+/// hydrostatic pressure change is from the top header end to 
+/// the bottom header end of the component (positive going 
+/// down)
+#[derive(Debug, Clone)]
+pub struct ComponentReading {
+    pub branch_name: String,
+    pub component_name: String,
+    pub mass_flowrate: MassRate,
+    pub pressure_loss: Pressure,
+    pub hydrostatic_pressure_change: Pressure,
+    pub reynolds_number: f64,
+    pub velocity_meters_per_second: f64,
+    pub component_length: Length,
+    pub hydraulic_diameter: Length,
+    pub incline_angle: Angle,
+    pub fluid_temperature: ThermodynamicTemperature,
+}
+
+/// returns the reading of a component in a branch at the given
+/// branch mass flowrate
+///
+/// Re = m D_h / (A mu) and v = m / (rho A), both take the sign 
+/// of the mass flowrate
+pub fn calculate_component_reading(
+    branch_name: &str,
+    component: &TherminolBranchComponent,
+    branch_mass_flowrate: MassRate) -> ComponentReading {
+
+    let fluid_component = component.as_fluid_component();
+
+    let cross_sectional_area = fluid_component.get_cross_sectional_area_immutable();
+    let hydraulic_diameter = fluid_component.get_hydraulic_diameter_immutable();
+    let component_length = fluid_component.get_component_length_immutable();
+    let incline_angle = fluid_component.get_incline_angle_immutable();
+    let density = fluid_component.get_fluid_density_immutable();
+    let viscosity = fluid_component.get_fluid_viscosity_immutable();
+
+    let hydrostatic_pressure_change = 
+        <TherminolPipe as FluidPipeCalcPressureChange>::
+        get_hydrostatic_pressure_change(
+            component_length,
+            incline_angle,
+            density);
+
+    let reynolds_number = branch_mass_flowrate.value * hydraulic_diameter.value / 
+        (cross_sectional_area.value * viscosity.value);
+
+    let velocity_meters_per_second = branch_mass_flowrate.value / 
+        (density.value * cross_sectional_area.value);
+
+    return ComponentReading { 
+        branch_name: branch_name.to_string(), 
+        component_name: component.get_name().to_string(), 
+        mass_flowrate: branch_mass_flowrate, 
+        pressure_loss: fluid_component.get_pressure_loss_immutable(branch_mass_flowrate), 
+        hydrostatic_pressure_change: hydrostatic_pressure_change, 
+        reynolds_number: reynolds_number, 
+        velocity_meters_per_second: velocity_meters_per_second, 
+        component_length: component_length, 
+        hydraulic_diameter: hydraulic_diameter, 
+        incline_angle: incline_angle, 
+        fluid_temperature: component.get_fluid_temperature(),
+    };
+}
@@ -0,0 +1,204 @@
+// This application was developed for use in my PhD thesis under supervision 
+// of Professor Per F. Peterson. It is part of a thermal hydraulics
+// library in Rust that is released under the GNU General Public License
+// v 3.0. This is partly due to the fact that some of the libraries 
+// inherit from GeN-Foam and OpenFOAM, both licensed under GNU General
+// Public License v3.0.
+//
+// As such, the entire library is released under GNU GPL v3.0. It is a strong 
+// copyleft license which means you cannot use it in proprietary software.
+//
+//
+// License
+//    This application is part ciet_digital_twin_rust which uses
+//    fluid_mechanics_rust, a partial library of the
+//    thermal hydraulics library written in rust meant to help with the
+//    fluid mechanics aspects of the calculations
+//     
+//    Copyright (C) 2022-2023  Theodore Kay Chen Ong, Singapore Nuclear
+//    Research and Safety Initiative, Per F. Peterson, University of 
+//    California, Berkeley Thermal Hydraulics Laboratory
+//
+//    ciet_digital_twin_rust is free software; you can redistribute it and/or modify it
+//    under the terms of the GNU General Public License as published by the
+//    Free Software Foundation; either version 2 of the License, or (at your
+//    option) any later version.
+//
+//    ciet_digital_twin_rust is distributed in the hope that it will be useful, but WITHOUT
+//    ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or
+//    FITNESS FOR A PARTICULAR PURPOSE.  See the GNU General Public License
+//    for more details.
+//
+//    ciet_digital_twin_rust relies on a thermal hydraulics library
+//    also written in rust. The isothermal digital twin in particular
+//    relies on fluid_mechanics_rust.
+//    This library is part of a thermal hydraulics library in rust
+//    and contains some code copied from GeN-Foam, and OpenFOAM derivative.
+//    This offering is not approved or endorsed by the OpenFOAM Foundation nor
+//    OpenCFD Limited, producer and distributor of the OpenFOAM(R)software via
+//    www.openfoam.com, and owner of the OPENFOAM(R) and OpenCFD(R) trademarks.
+//    Nor is it endorsed by the authors and owners of GeN-Foam.
+//
+//    You should have received a copy of the GNU General Public License
+//    along with this program.  If not, see <http://www.gnu.org/licenses/>.
+//
+// © All rights reserved. Theodore Kay Chen Ong,
+// Singapore Nuclear Research and Safety Initiative,
+// Per F. Peterson,
+// University of California, Berkeley Thermal Hydraulics Laboratory
+//
+// Main author of the code: Theodore Kay Chen Ong, supervised by
+// Professor Per F. Peterson
+extern crate fluid_mechanics_rust;
+use fluid_mechanics_rust::prelude::*;
+
+use std::time::{Instant, Duration};
+use crate::owned_ciet_facility::OwnedCIETFacility;
+use crate::owned_dracs_facility::OwnedDRACSFacility;
+use crate::dhx_heat_exchanger::DHXHeatExchanger;
+use crate::parallel_branch_network::MAX_TRANSIENT_SUBSTEP_SECONDS;
+use crate::ciet_solver::CIETSolverError;
+
+/// the flowrates of both loops after a coupled transient step
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CoupledCIETFlowrates {
+    pub ctah_branch_mass_flowrate: MassRate,
+    pub heater_branch_mass_flowrate: MassRate,
+    pub dhx_branch_mass_flowrate: MassRate,
+    pub dracs_loop_mass_flowrate: MassRate,
+}
+
+/// the primary loop and the DRACS loop, coupled through the DHX
+///
+/// the primary loop fluid on the DHX shell side heats the 
+/// DRACS fluid in the DHX tubes. The heat transferred comes 
+/// from DHXHeatExchanger and is taken out of the dhx shell side
+/// and put into the dhx tube side, so no heat is lost or made
+/// in the DHX
+///
+/// both loops run in thermal hydraulics mode, this is how 
+/// passive decay heat removal transients are simulated: the 
+/// ctah pump trips, and the heater power is taken away by 
+/// natural circulation through the DHX, the DRACS loop and 
+/// the TCHX
+pub struct CoupledCIETFacility {
+    primary_loop: OwnedCIETFacility,
+    dracs_loop: OwnedDRACSFacility,
+    dhx_heat_exchanger: DHXHeatExchanger,
+}
+
+impl CoupledCIETFacility {
+
+    /// constructor, builds both loops with thermal hydraulics 
+    /// switched on
+    pub fn new() -> Self {
+
+        let mut primary_loop = OwnedCIETFacility::new();
+        primary_loop.set_thermal_hydraulics_enabled(true);
+
+        let mut dracs_loop = OwnedDRACSFacility::new();
+        dracs_loop.set_thermal_hydraulics_enabled(true);
+
+        return Self { 
+            primary_loop: primary_loop, 
+            dracs_loop: dracs_loop, 
+            dhx_heat_exchanger: DHXHeatExchanger::ciet_dhx(),
+        };
+    }
+
+    pub fn get_primary_loop(&self) -> &OwnedCIETFacility {
+        return &self.primary_loop;
+    }
+
+    /// returns the primary loop so its pump, heater and ctah 
+    /// fan can be set
+    pub fn get_primary_loop_mut(&mut self) -> &mut OwnedCIETFacility {
+        return &mut self.primary_loop;
+    }
+
+    pub fn get_dracs_loop(&self) -> &OwnedDRACSFacility {
+        return &self.dracs_loop;
+    }
+
+    /// returns the DRACS loop so its tchx fan can be set
+    pub fn get_dracs_loop_mut(&mut self) -> &mut OwnedDRACSFacility {
+        return &mut self.dracs_loop;
+    }
+
+    pub fn get_dhx_heat_exchanger(&self) -> &DHXHeatExchanger {
+        return &self.dhx_heat_exchanger;
+    }
+
+    pub fn get_dhx_heat_exchanger_mut(&mut self) -> &mut DHXHeatExchanger {
+        return &mut self.dhx_heat_exchanger;
+    }
+
+    /// returns the heat transferred from the primary loop to 
+    /// the DRACS loop (watts) at the current temperatures and 
+    /// flowrates
+    pub fn get_dhx_heat_transfer_watts(&self) -> f64 {
+        return self.dhx_heat_exchanger.get_heat_transfer_watts(
+            self.primary_loop.get_fluid_properties(),
+            self.primary_loop.get_dhx_shell_side_inlet_temperature(),
+            self.primary_loop.get_dhx_branch_mass_flowrate(),
+            self.dracs_loop.get_fluid_properties(),
+            self.dracs_loop.get_dhx_tube_side_inlet_temperature(),
+            self.dracs_loop.get_dhx_tube_side_mass_flowrate());
+    }
+
+    /// advances both loops over a timestep
+    ///
+    /// the DHX heat transfer is updated from the DHX inlet 
+    /// temperatures every MAX_TRANSIENT_SUBSTEP_SECONDS, and 
+    /// each loop then steps its flowrates and temperatures 
+    /// forward on its own
+    ///
+    /// returns the calculation time and the flowrates of both 
+    /// loops
+    pub fn step_transient(&mut self, timestep: Duration) -> 
+        Result<(Duration, CoupledCIETFlowrates), CIETSolverError> {
+
+        let start = Instant::now();
+
+        let number_of_substeps: u32 = 
+            (timestep.as_secs_f64() / MAX_TRANSIENT_SUBSTEP_SECONDS).
+            ceil().max(1.0) as u32;
+
+        let substep = timestep / number_of_substeps;
+
+        for _ in 0..number_of_substeps {
+
+            let dhx_heat_transfer_watts = self.get_dhx_heat_transfer_watts();
+
+            self.primary_loop.set_dhx_heat_removal_watts(dhx_heat_transfer_watts);
+            self.dracs_loop.set_dhx_heat_input_watts(dhx_heat_transfer_watts);
+
+            self.primary_loop.step_transient(substep)?;
+            self.dracs_loop.step_transient(substep)?;
+        }
+
+        let elapsed_time: Duration = start.elapsed();
+
+        return Ok((elapsed_time, self.get_flowrates()));
+    }
+
+    /// returns the current flowrates of both loops
+    pub fn get_flowrates(&self) -> CoupledCIETFlowrates {
+        return CoupledCIETFlowrates { 
+            ctah_branch_mass_flowrate: 
+                self.primary_loop.get_ctah_branch_mass_flowrate(), 
+            heater_branch_mass_flowrate: 
+                self.primary_loop.get_heater_branch_mass_flowrate(), 
+            dhx_branch_mass_flowrate: 
+                self.primary_loop.get_dhx_branch_mass_flowrate(), 
+            dracs_loop_mass_flowrate: 
+                self.dracs_loop.get_loop_mass_flowrate(),
+        };
+    }
+
+    /// returns the time simulated so far, both loops are 
+    /// always stepped together
+    pub fn get_simulation_time(&self) -> Duration {
+        return self.primary_loop.get_simulation_time();
+    }
+}
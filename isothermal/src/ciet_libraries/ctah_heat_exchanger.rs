@@ -0,0 +1,285 @@
+// This application was developed for use in my PhD thesis under supervision 
+// of Professor Per F. Peterson. It is part of a thermal hydraulics
+// library in Rust that is released under the GNU General Public License
+// v 3.0. This is partly due to the fact that some of the libraries 
+// inherit from GeN-Foam and OpenFOAM, both licensed under GNU General
+// Public License v3.0.
+//
+// As such, the entire library is released under GNU GPL v3.0. It is a strong 
+// copyleft license which means you cannot use it in proprietary software.
+//
+//
+// License
+//    This application is part ciet_digital_twin_rust which uses
+//    fluid_mechanics_rust, a partial library of the
+//    thermal hydraulics library written in rust meant to help with the
+//    fluid mechanics aspects of the calculations
+//     
+//    Copyright (C) 2022-2023  Theodore Kay Chen Ong, Singapore Nuclear
+//    Research and Safety Initiative, Per F. Peterson, University of 
+//    California, Berkeley Thermal Hydraulics Laboratory
+//
+//    ciet_digital_twin_rust is free software; you can redistribute it and/or modify it
+//    under the terms of the GNU General Public License as published by the
+//    Free Software Foundation; either version 2 of the License, or (at your
+//    option) any later version.
+//
+//    ciet_digital_twin_rust is distributed in the hope that it will be useful, but WITHOUT
+//    ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or
+//    FITNESS FOR A PARTICULAR PURPOSE.  See the GNU General Public License
+//    for more details.
+//
+//    ciet_digital_twin_rust relies on a thermal hydraulics library
+//    also written in rust. The isothermal digital twin in particular
+//    relies on fluid_mechanics_rust.
+//    This library is part of a thermal hydraulics library in rust
+//    and contains some code copied from GeN-Foam, and OpenFOAM derivative.
+//    This offering is not approved or endorsed by the OpenFOAM Foundation nor
+//    OpenCFD Limited, producer and distributor of the OpenFOAM(R)software via
+//    www.openfoam.com, and owner of the OPENFOAM(R) and OpenCFD(R) trademarks.
+//    Nor is it endorsed by the authors and owners of GeN-Foam.
+//
+//    You should have received a copy of the GNU General Public License
+//    along with this program.  If not, see <http://www.gnu.org/licenses/>.
+//
+// © All rights reserved. Theodore Kay Chen Ong,
+// Singapore Nuclear Research and Safety Initiative,
+// Per F. Peterson,
+// University of California, Berkeley Thermal Hydraulics Laboratory
+//
+// Main author of the code: Theodore Kay Chen Ong, supervised by
+// Professor Per F. Peterson
+extern crate fluid_mechanics_rust;
+use fluid_mechanics_rust::prelude::*;
+
+use crate::energy_equation::get_temperature_degrees_c;
+use crate::fluid_properties::FluidProperties;
+
+/// specific heat capacity of air in J/(kg K), taken as constant
+pub const AIR_SPECIFIC_HEAT_CAPACITY_JOULE_PER_KG_KELVIN: f64 = 1005.0;
+
+/// heat transfer model for the coiled tube air heater (ctah),
+/// where fan driven air flows across the tubes carrying the oil
+///
+/// the overall conductance is made of an oil side and an air 
+/// side conductance in series:
+///
+/// 1/UA = 1/UA_oil + 1/UA_air
+///
+/// the air flowrate scales with fan speed (fan affinity law), 
+/// and the air side conductance with air flowrate to the 
+/// power 0.8 (turbulent crossflow over tubes). The oil side 
+/// conductance is taken as constant.
+///
+/// the heat removed then comes from the NTU-effectiveness method 
+/// for crossflow with both fluids unmixed:
+///
+/// Q = effectiveness * C_min * (T_oil_in - T_air_in)
+///
+/// with the fan off, no heat is removed
+///
+/// the oil side heat capacity comes from the fluid properties 
+/// passed in, so that the same ctah can be run with the 
+/// therminol in ciet or with some other working fluid
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CTAHHeatExchanger {
+    oil_side_ua_watts_per_kelvin: f64,
+    rated_air_side_ua_watts_per_kelvin: f64,
+    rated_fan_speed_rpm: f64,
+    rated_air_mass_flowrate: MassRate,
+    air_inlet_temperature: ThermodynamicTemperature,
+    fan_speed_rpm: f64,
+}
+
+impl CTAHHeatExchanger {
+
+    /// constructor, the air side conductance and air flowrate 
+    /// are given at the rated fan speed. The fan starts off
+    pub fn new(oil_side_ua_watts_per_kelvin: f64,
+               rated_air_side_ua_watts_per_kelvin: f64,
+               rated_fan_speed_rpm: f64,
+               rated_air_mass_flowrate: MassRate,
+               air_inlet_temperature: ThermodynamicTemperature) -> Self {
+
+        return Self { 
+            oil_side_ua_watts_per_kelvin: oil_side_ua_watts_per_kelvin, 
+            rated_air_side_ua_watts_per_kelvin: rated_air_side_ua_watts_per_kelvin, 
+            rated_fan_speed_rpm: rated_fan_speed_rpm, 
+            rated_air_mass_flowrate: rated_air_mass_flowrate, 
+            air_inlet_temperature: air_inlet_temperature, 
+            fan_speed_rpm: 0.0,
+        };
+    }
+
+    /// returns a heat exchanger for the ciet ctah, with air 
+    /// drawn in at 21C
+    ///
+    /// the values are sized to remove about 10 kW (the heater
+    /// rating) at full fan speed and the usual operating 
+    /// temperatures, they are illustrative and have not been 
+    /// fitted to ctah data yet
+    pub fn ciet_ctah() -> Self {
+        return Self::new(
+            1500.0,
+            800.0,
+            1750.0,
+            MassRate::new::<kilogram_per_second>(1.0),
+            ThermodynamicTemperature::new::<degree_celsius>(21.0));
+    }
+
+    /// returns a heat exchanger for the TCHX in the DRACS loop,
+    /// which is also a fan cooled coil, with air drawn in at 21C
+    ///
+    /// the values are illustrative and have not been fitted to 
+    /// TCHX data yet
+    pub fn ciet_tchx() -> Self {
+        return Self::new(
+            800.0,
+            500.0,
+            1750.0,
+            MassRate::new::<kilogram_per_second>(0.6),
+            ThermodynamicTemperature::new::<degree_celsius>(21.0));
+    }
+
+    pub fn get_rated_fan_speed_rpm(&self) -> f64 {
+        return self.rated_fan_speed_rpm;
+    }
+
+    /// sets the fan speed (rpm), negative speeds are taken as 
+    /// zero
+    pub fn set_fan_speed_rpm(&mut self, fan_speed_rpm: f64){
+        self.fan_speed_rpm = fan_speed_rpm.max(0.0);
+    }
+
+    pub fn get_fan_speed_rpm(&self) -> f64 {
+        return self.fan_speed_rpm;
+    }
+
+    pub fn set_air_inlet_temperature(&mut self, 
+                                     air_inlet_temperature: ThermodynamicTemperature){
+        self.air_inlet_temperature = air_inlet_temperature;
+    }
+
+    pub fn get_air_inlet_temperature(&self) -> ThermodynamicTemperature {
+        return self.air_inlet_temperature;
+    }
+
+    /// returns the air flowrate across the tubes, which is 
+    /// proportional to fan speed
+    pub fn get_air_mass_flowrate(&self) -> MassRate {
+        return self.rated_air_mass_flowrate * 
+            (self.fan_speed_rpm / self.rated_fan_speed_rpm);
+    }
+
+    /// returns the overall conductance UA (W/K) at the current
+    /// fan speed
+    pub fn get_ua_watts_per_kelvin(&self) -> f64 {
+
+        let speed_ratio = self.fan_speed_rpm / self.rated_fan_speed_rpm;
+
+        if speed_ratio <= 0.0 {
+            return 0.0;
+        }
+
+        let air_side_ua_watts_per_kelvin = 
+            self.rated_air_side_ua_watts_per_kelvin * speed_ratio.powf(0.8);
+
+        return 1.0 / (1.0 / self.oil_side_ua_watts_per_kelvin 
+                      + 1.0 / air_side_ua_watts_per_kelvin);
+    }
+
+    /// returns the effectiveness, the fraction of the largest 
+    /// possible heat transfer which actually happens
+    pub fn get_effectiveness(&self, 
+                             fluid_properties: &dyn FluidProperties,
+                             oil_inlet_temperature: ThermodynamicTemperature,
+                             oil_mass_flowrate: MassRate) -> f64 {
+
+        let oil_heat_capacity_rate = 
+            oil_mass_flowrate.value.abs() * 
+            fluid_properties.get_specific_heat_capacity_joule_per_kg_kelvin(
+                oil_inlet_temperature);
+        let air_heat_capacity_rate = 
+            self.get_air_mass_flowrate().value * 
+            AIR_SPECIFIC_HEAT_CAPACITY_JOULE_PER_KG_KELVIN;
+
+        let minimum_heat_capacity_rate = 
+            oil_heat_capacity_rate.min(air_heat_capacity_rate);
+        let maximum_heat_capacity_rate = 
+            oil_heat_capacity_rate.max(air_heat_capacity_rate);
+
+        // no flow on either side, no heat transfer
+        if minimum_heat_capacity_rate <= 0.0 {
+            return 0.0;
+        }
+
+        let number_of_transfer_units = 
+            self.get_ua_watts_per_kelvin() / minimum_heat_capacity_rate;
+        let heat_capacity_ratio = 
+            minimum_heat_capacity_rate / maximum_heat_capacity_rate;
+
+        // this is the limit of the crossflow correlation as the 
+        // heat capacity ratio goes to zero
+        if heat_capacity_ratio < 1.0e-9 {
+            return 1.0 - (-number_of_transfer_units).exp();
+        }
+
+        return 1.0 - (
+            number_of_transfer_units.powf(0.22) / heat_capacity_ratio
+            * ((-heat_capacity_ratio * number_of_transfer_units.powf(0.78)).exp() 
+               - 1.0)
+            ).exp();
+    }
+
+    /// returns the heat removed from the oil (watts), this is 
+    /// negative if the air is hotter than the oil
+    pub fn get_heat_removal_watts(&self, 
+                                  fluid_properties: &dyn FluidProperties,
+                                  oil_inlet_temperature: ThermodynamicTemperature,
+                                  oil_mass_flowrate: MassRate) -> f64 {
+
+        let oil_heat_capacity_rate = 
+            oil_mass_flowrate.value.abs() * 
+            fluid_properties.get_specific_heat_capacity_joule_per_kg_kelvin(
+                oil_inlet_temperature);
+        let air_heat_capacity_rate = 
+            self.get_air_mass_flowrate().value * 
+            AIR_SPECIFIC_HEAT_CAPACITY_JOULE_PER_KG_KELVIN;
+
+        let minimum_heat_capacity_rate = 
+            oil_heat_capacity_rate.min(air_heat_capacity_rate);
+
+        let temperature_difference = 
+            get_temperature_degrees_c(oil_inlet_temperature) 
+            - get_temperature_degrees_c(self.air_inlet_temperature);
+
+        return self.get_effectiveness(
+            fluid_properties, oil_inlet_temperature, oil_mass_flowrate)
+            * minimum_heat_capacity_rate * temperature_difference;
+    }
+
+    /// returns the oil outlet temperature given the oil inlet 
+    /// temperature and flowrate at the current fan speed
+    pub fn get_oil_outlet_temperature(
+        &self,
+        fluid_properties: &dyn FluidProperties,
+        oil_inlet_temperature: ThermodynamicTemperature,
+        oil_mass_flowrate: MassRate) -> ThermodynamicTemperature {
+
+        let oil_heat_capacity_rate = 
+            oil_mass_flowrate.value.abs() * 
+            fluid_properties.get_specific_heat_capacity_joule_per_kg_kelvin(
+                oil_inlet_temperature);
+
+        if oil_heat_capacity_rate <= 0.0 {
+            return oil_inlet_temperature;
+        }
+
+        let heat_removal_watts = self.get_heat_removal_watts(
+            fluid_properties, oil_inlet_temperature, oil_mass_flowrate);
+
+        return ThermodynamicTemperature::new::<degree_celsius>(
+            get_temperature_degrees_c(oil_inlet_temperature) 
+            - heat_removal_watts / oil_heat_capacity_rate);
+    }
+}
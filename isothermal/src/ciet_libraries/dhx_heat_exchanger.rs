@@ -0,0 +1,164 @@
+// This application was developed for use in my PhD thesis under supervision 
+// of Professor Per F. Peterson. It is part of a thermal hydraulics
+// library in Rust that is released under the GNU General Public License
+// v 3.0. This is partly due to the fact that some of the libraries 
+// inherit from GeN-Foam and OpenFOAM, both licensed under GNU General
+// Public License v3.0.
+//
+// As such, the entire library is released under GNU GPL v3.0. It is a strong 
+// copyleft license which means you cannot use it in proprietary software.
+//
+//
+// License
+//    This application is part ciet_digital_twin_rust which uses
+//    fluid_mechanics_rust, a partial library of the
+//    thermal hydraulics library written in rust meant to help with the
+//    fluid mechanics aspects of the calculations
+//     
+//    Copyright (C) 2022-2023  Theodore Kay Chen Ong, Singapore Nuclear
+//    Research and Safety Initiative, Per F. Peterson, University of 
+//    California, Berkeley Thermal Hydraulics Laboratory
+//
+//    ciet_digital_twin_rust is free software; you can redistribute it and/or modify it
+//    under the terms of the GNU General Public License as published by the
+//    Free Software Foundation; either version 2 of the License, or (at your
+//    option) any later version.
+//
+//    ciet_digital_twin_rust is distributed in the hope that it will be useful, but WITHOUT
+//    ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or
+//    FITNESS FOR A PARTICULAR PURPOSE.  See the GNU General Public License
+//    for more details.
+//
+//    ciet_digital_twin_rust relies on a thermal hydraulics library
+//    also written in rust. The isothermal digital twin in particular
+//    relies on fluid_mechanics_rust.
+//    This library is part of a thermal hydraulics library in rust
+//    and contains some code copied from GeN-Foam, and OpenFOAM derivative.
+//    This offering is not approved or endorsed by the OpenFOAM Foundation nor
+//    OpenCFD Limited, producer and distributor of the OpenFOAM(R)software via
+//    www.openfoam.com, and owner of the OPENFOAM(R) and OpenCFD(R) trademarks.
+//    Nor is it endorsed by the authors and owners of GeN-Foam.
+//
+//    You should have received a copy of the GNU General Public License
+//    along with this program.  If not, see <http://www.gnu.org/licenses/>.
+//
+// © All rights reserved. Theodore Kay Chen Ong,
+// Singapore Nuclear Research and Safety Initiative,
+// Per F. Peterson,
+// University of California, Berkeley Thermal Hydraulics Laboratory
+//
+// Main author of the code: Theodore Kay Chen Ong, supervised by
+// Professor Per F. Peterson
+extern crate fluid_mechanics_rust;
+use fluid_mechanics_rust::prelude::*;
+
+use crate::energy_equation::get_temperature_degrees_c;
+use crate::fluid_properties::FluidProperties;
+
+/// heat transfer model for the DHX, where primary loop fluid 
+/// on the shell side heats DRACS loop fluid in the tubes
+///
+/// in natural circulation, primary fluid flows down the shell
+/// side and DRACS fluid flows up the tubes, so the DHX is 
+/// taken as a counterflow heat exchanger:
+///
+/// Q = effectiveness * C_min * (T_shell_in - T_tube_in)
+///
+/// effectiveness = (1 - exp(-NTU (1 - Cr))) / (1 - Cr exp(-NTU (1 - Cr)))
+///
+/// the overall conductance UA is taken as constant
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DHXHeatExchanger {
+    ua_watts_per_kelvin: f64,
+}
+
+impl DHXHeatExchanger {
+
+    pub fn new(ua_watts_per_kelvin: f64) -> Self {
+        return Self { ua_watts_per_kelvin: ua_watts_per_kelvin };
+    }
+
+    /// returns a heat exchanger for the ciet DHX
+    ///
+    /// the conductance is illustrative and has not been fitted
+    /// to DHX data yet
+    pub fn ciet_dhx() -> Self {
+        return Self::new(250.0);
+    }
+
+    pub fn get_ua_watts_per_kelvin(&self) -> f64 {
+        return self.ua_watts_per_kelvin;
+    }
+
+    pub fn set_ua_watts_per_kelvin(&mut self, ua_watts_per_kelvin: f64){
+        self.ua_watts_per_kelvin = ua_watts_per_kelvin;
+    }
+
+    /// returns the heat transferred from the shell side to the
+    /// tube side (watts), this is negative if the tube side is
+    /// hotter
+    ///
+    /// the flowrates may have either sign, only their 
+    /// magnitudes are used. The heat capacities come from the
+    /// fluid on each side
+    pub fn get_heat_transfer_watts(
+        &self,
+        shell_side_fluid_properties: &dyn FluidProperties,
+        shell_side_inlet_temperature: ThermodynamicTemperature,
+        shell_side_mass_flowrate: MassRate,
+        tube_side_fluid_properties: &dyn FluidProperties,
+        tube_side_inlet_temperature: ThermodynamicTemperature,
+        tube_side_mass_flowrate: MassRate) -> f64 {
+
+        let shell_side_heat_capacity_rate = 
+            shell_side_mass_flowrate.value.abs() * 
+            shell_side_fluid_properties.get_specific_heat_capacity_joule_per_kg_kelvin(
+                shell_side_inlet_temperature);
+        let tube_side_heat_capacity_rate = 
+            tube_side_mass_flowrate.value.abs() * 
+            tube_side_fluid_properties.get_specific_heat_capacity_joule_per_kg_kelvin(
+                tube_side_inlet_temperature);
+
+        let minimum_heat_capacity_rate = 
+            shell_side_heat_capacity_rate.min(tube_side_heat_capacity_rate);
+        let maximum_heat_capacity_rate = 
+            shell_side_heat_capacity_rate.max(tube_side_heat_capacity_rate);
+
+        // no flow on either side, no heat transfer
+        if minimum_heat_capacity_rate <= 0.0 {
+            return 0.0;
+        }
+
+        let number_of_transfer_units = 
+            self.ua_watts_per_kelvin / minimum_heat_capacity_rate;
+        let heat_capacity_ratio = 
+            minimum_heat_capacity_rate / maximum_heat_capacity_rate;
+
+        let effectiveness = 
+            Self::get_counterflow_effectiveness(
+                number_of_transfer_units, heat_capacity_ratio);
+
+        let temperature_difference = 
+            get_temperature_degrees_c(shell_side_inlet_temperature) 
+            - get_temperature_degrees_c(tube_side_inlet_temperature);
+
+        return effectiveness * minimum_heat_capacity_rate * temperature_difference;
+    }
+
+    /// returns the effectiveness of a counterflow heat exchanger
+    pub fn get_counterflow_effectiveness(number_of_transfer_units: f64,
+                                         heat_capacity_ratio: f64) -> f64 {
+
+        // with equal heat capacity rates, the general formula 
+        // is 0/0, so i use its limit instead
+        if (1.0 - heat_capacity_ratio).abs() < 1.0e-9 {
+            return number_of_transfer_units / (1.0 + number_of_transfer_units);
+        }
+
+        let exponential_term = 
+            (-number_of_transfer_units * (1.0 - heat_capacity_ratio)).exp();
+
+        return (1.0 - exponential_term) 
+            / (1.0 - heat_capacity_ratio * exponential_term);
+    }
+}
@@ -0,0 +1,175 @@
+// This application was developed for use in my PhD thesis under supervision 
+// of Professor Per F. Peterson. It is part of a thermal hydraulics
+// library in Rust that is released under the GNU General Public License
+// v 3.0. This is partly due to the fact that some of the libraries 
+// inherit from GeN-Foam and OpenFOAM, both licensed under GNU General
+// Public License v3.0.
+//
+// As such, the entire library is released under GNU GPL v3.0. It is a strong 
+// copyleft license which means you cannot use it in proprietary software.
+//
+//
+// License
+//    This application is part ciet_digital_twin_rust which uses
+//    fluid_mechanics_rust, a partial library of the
+//    thermal hydraulics library written in rust meant to help with the
+//    fluid mechanics aspects of the calculations
+//     
+//    Copyright (C) 2022-2023  Theodore Kay Chen Ong, Singapore Nuclear
+//    Research and Safety Initiative, Per F. Peterson, University of 
+//    California, Berkeley Thermal Hydraulics Laboratory
+//
+//    ciet_digital_twin_rust is free software; you can redistribute it and/or modify it
+//    under the terms of the GNU General Public License as published by the
+//    Free Software Foundation; either version 2 of the License, or (at your
+//    option) any later version.
+//
+//    ciet_digital_twin_rust is distributed in the hope that it will be useful, but WITHOUT
+//    ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or
+//    FITNESS FOR A PARTICULAR PURPOSE.  See the GNU General Public License
+//    for more details.
+//
+//    ciet_digital_twin_rust relies on a thermal hydraulics library
+//    also written in rust. The isothermal digital twin in particular
+//    relies on fluid_mechanics_rust.
+//    This library is part of a thermal hydraulics library in rust
+//    and contains some code copied from GeN-Foam, and OpenFOAM derivative.
+//    This offering is not approved or endorsed by the OpenFOAM Foundation nor
+//    OpenCFD Limited, producer and distributor of the OpenFOAM(R)software via
+//    www.openfoam.com, and owner of the OPENFOAM(R) and OpenCFD(R) trademarks.
+//    Nor is it endorsed by the authors and owners of GeN-Foam.
+//
+//    You should have received a copy of the GNU General Public License
+//    along with this program.  If not, see <http://www.gnu.org/licenses/>.
+//
+// © All rights reserved. Theodore Kay Chen Ong,
+// Singapore Nuclear Research and Safety Initiative,
+// Per F. Peterson,
+// University of California, Berkeley Thermal Hydraulics Laboratory
+//
+// Main author of the code: Theodore Kay Chen Ong, supervised by
+// Professor Per F. Peterson
+extern crate fluid_mechanics_rust;
+use fluid_mechanics_rust::prelude::*;
+
+use std::time::Duration;
+
+// TherminolVP1Properties only gives density and viscosity, 
+// so the specific heat capacity for the energy equation lives 
+// here
+//
+// this is a linear fit to the manufacturer's data for 
+// Therminol VP-1, which is good enough between 20C and 180C
+// (the range CIET operates in)
+
+/// returns the specific heat capacity of therminol VP-1 
+/// in J/(kg K)
+pub fn get_therminol_specific_heat_capacity_joule_per_kg_kelvin(
+    fluid_temp: ThermodynamicTemperature) -> f64 {
+
+    let fluid_temp_degrees_c = get_temperature_degrees_c(fluid_temp);
+
+    return 1504.0 + 2.72 * fluid_temp_degrees_c;
+}
+
+/// returns the thermal conductivity of therminol VP-1 in 
+/// W/(m K), this is also a linear fit to the manufacturer's 
+/// data between 20C and 180C
+pub fn get_therminol_thermal_conductivity_watts_per_meter_kelvin(
+    fluid_temp: ThermodynamicTemperature) -> f64 {
+
+    let fluid_temp_degrees_c = get_temperature_degrees_c(fluid_temp);
+
+    return 0.1381 - 1.101e-4 * fluid_temp_degrees_c;
+}
+
+/// converts a ThermodynamicTemperature into degrees C as an f64
+pub fn get_temperature_degrees_c(fluid_temp: ThermodynamicTemperature) -> f64 {
+    // the value is stored in kelvin
+    return fluid_temp.value - 273.15;
+}
+
+/// solves the energy equation for one control volume (one 
+/// pipe or component) using first order upwinding in space 
+/// and backward euler in time:
+///
+/// M cp (T - T_old)/dt = |m| cp (T_upstream - T) + Q
+///
+/// M is the mass of fluid in the control volume, m is the 
+/// mass flowrate through it and Q is the heat added to the 
+/// fluid. The fluid leaves at the control volume temperature T
+///
+/// Because it is implicit, it is stable for any timestep, but
+/// T_upstream must be the new temperature of the control 
+/// volume upstream, so the control volumes must be solved in 
+/// the direction of flow
+pub fn calculate_upwind_control_volume_temperature(
+    old_temperature: ThermodynamicTemperature,
+    upstream_temperature: ThermodynamicTemperature,
+    fluid_mass_kg: f64,
+    mass_flowrate: MassRate,
+    heat_input_watts: f64,
+    timestep: Duration) -> ThermodynamicTemperature {
+
+    return calculate_upwind_control_volume_temperature_with_ambient_loss(
+        old_temperature, 
+        upstream_temperature, 
+        fluid_mass_kg, 
+        get_therminol_specific_heat_capacity_joule_per_kg_kelvin(old_temperature),
+        mass_flowrate, 
+        heat_input_watts, 
+        0.0, 
+        old_temperature, 
+        timestep);
+}
+
+/// same as calculate_upwind_control_volume_temperature, but 
+/// the control volume also loses heat to ambient through a 
+/// conductance UA (eg. through the pipe insulation):
+///
+/// M cp (T - T_old)/dt = |m| cp (T_upstream - T) + Q 
+/// - UA (T - T_ambient)
+///
+/// the heat loss is taken at the new temperature, so this 
+/// stays stable for any timestep
+///
+/// the specific heat capacity is given, so this works for any
+/// working fluid
+pub fn calculate_upwind_control_volume_temperature_with_ambient_loss(
+    old_temperature: ThermodynamicTemperature,
+    upstream_temperature: ThermodynamicTemperature,
+    fluid_mass_kg: f64,
+    specific_heat_capacity_joule_per_kg_kelvin: f64,
+    mass_flowrate: MassRate,
+    heat_input_watts: f64,
+    ambient_conductance_watts_per_kelvin: f64,
+    ambient_temperature: ThermodynamicTemperature,
+    timestep: Duration) -> ThermodynamicTemperature {
+
+    let specific_heat_capacity = specific_heat_capacity_joule_per_kg_kelvin;
+
+    let timestep_seconds = timestep.as_secs_f64();
+
+    // with no timestep, nothing changes
+    if timestep_seconds <= 0.0 {
+        return old_temperature;
+    }
+
+    // heat capacity of the fluid in the control volume per 
+    // unit time, and the heat capacity rate of the flow
+    let storage_coefficient = 
+        fluid_mass_kg * specific_heat_capacity / timestep_seconds;
+    let advection_coefficient = 
+        mass_flowrate.value.abs() * specific_heat_capacity;
+    let ambient_coefficient = ambient_conductance_watts_per_kelvin.max(0.0);
+
+    let new_temperature_degrees_c = 
+        (storage_coefficient * get_temperature_degrees_c(old_temperature)
+         + advection_coefficient * get_temperature_degrees_c(upstream_temperature)
+         + ambient_coefficient * get_temperature_degrees_c(ambient_temperature)
+         + heat_input_watts)
+        / (storage_coefficient + advection_coefficient + ambient_coefficient);
+
+    return ThermodynamicTemperature::new::<degree_celsius>(
+        new_temperature_degrees_c);
+}
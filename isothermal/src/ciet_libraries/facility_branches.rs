@@ -0,0 +1,99 @@
+// This application was developed for use in my PhD thesis under supervision 
+// of Professor Per F. Peterson. It is part of a thermal hydraulics
+// library in Rust that is released under the GNU General Public License
+// v 3.0. This is partly due to the fact that some of the libraries 
+// inherit from GeN-Foam and OpenFOAM, both licensed under GNU General
+// Public License v3.0.
+//
+// As such, the entire library is released under GNU GPL v3.0. It is a strong 
+// copyleft license which means you cannot use it in proprietary software.
+//
+//
+// License
+//    This application is part ciet_digital_twin_rust which uses
+//    fluid_mechanics_rust, a partial library of the
+//    thermal hydraulics library written in rust meant to help with the
+//    fluid mechanics aspects of the calculations
+//     
+//    Copyright (C) 2022-2023  Theodore Kay Chen Ong, Singapore Nuclear
+//    Research and Safety Initiative, Per F. Peterson, University of 
+//    California, Berkeley Thermal Hydraulics Laboratory
+//
+//    ciet_digital_twin_rust is free software; you can redistribute it and/or modify it
+//    under the terms of the GNU General Public License as published by the
+//    Free Software Foundation; either version 2 of the License, or (at your
+//    option) any later version.
+//
+//    ciet_digital_twin_rust is distributed in the hope that it will be useful, but WITHOUT
+//    ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or
+//    FITNESS FOR A PARTICULAR PURPOSE.  See the GNU General Public License
+//    for more details.
+//
+//    ciet_digital_twin_rust relies on a thermal hydraulics library
+//    also written in rust. The isothermal digital twin in particular
+//    relies on fluid_mechanics_rust.
+//    This library is part of a thermal hydraulics library in rust
+//    and contains some code copied from GeN-Foam, and OpenFOAM derivative.
+//    This offering is not approved or endorsed by the OpenFOAM Foundation nor
+//    OpenCFD Limited, producer and distributor of the OpenFOAM(R)software via
+//    www.openfoam.com, and owner of the OPENFOAM(R) and OpenCFD(R) trademarks.
+//    Nor is it endorsed by the authors and owners of GeN-Foam.
+//
+//    You should have received a copy of the GNU General Public License
+//    along with this program.  If not, see <http://www.gnu.org/licenses/>.
+//
+// © All rights reserved. Theodore Kay Chen Ong,
+// Singapore Nuclear Research and Safety Initiative,
+// Per F. Peterson,
+// University of California, Berkeley Thermal Hydraulics Laboratory
+//
+// Main author of the code: Theodore Kay Chen Ong, supervised by
+// Professor Per F. Peterson
+extern crate fluid_mechanics_rust;
+use crate::branch::TherminolBranch;
+use crate::parallel_branch_network::ParallelBranchNetwork;
+
+use fluid_mechanics_rust::prelude::*;
+
+// the owned facilities are built with all their branches and 
+// branches cannot be removed from a network, so the branch 
+// and component indices they store are always valid
+//
+// these take the network rather than the facility so that the 
+// component indices stored in the facility can still be read 
+// while the branch is borrowed
+
+const FACILITY_BRANCH_MESSAGE: &str = 
+    "the facility always has all of its branches";
+
+pub(crate) fn get_facility_branch(
+    network: &ParallelBranchNetwork,
+    branch_index: usize) -> &TherminolBranch {
+    return network.get_branch(branch_index).
+        expect(FACILITY_BRANCH_MESSAGE);
+}
+
+pub(crate) fn get_facility_branch_mut(
+    network: &mut ParallelBranchNetwork,
+    branch_index: usize) -> &mut TherminolBranch {
+    return network.get_branch_mut(branch_index).
+        expect(FACILITY_BRANCH_MESSAGE);
+}
+
+pub(crate) fn get_facility_branch_mass_flowrate(
+    network: &ParallelBranchNetwork,
+    branch_index: usize) -> MassRate {
+    return network.get_branch_mass_flowrate(branch_index).
+        expect(FACILITY_BRANCH_MESSAGE);
+}
+
+pub(crate) fn get_facility_component_inlet_outlet_temperatures(
+    network: &ParallelBranchNetwork,
+    branch_index: usize,
+    first_component_index: usize,
+    last_component_index: usize) -> 
+    (ThermodynamicTemperature, ThermodynamicTemperature) {
+    return network.get_component_range_inlet_outlet_temperatures(
+        branch_index, first_component_index, last_component_index).
+        expect("the facility always has all of its components");
+}
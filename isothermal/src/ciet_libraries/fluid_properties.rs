@@ -0,0 +1,276 @@
+// This application was developed for use in my PhD thesis under supervision 
+// of Professor Per F. Peterson. It is part of a thermal hydraulics
+// library in Rust that is released under the GNU General Public License
+// v 3.0. This is partly due to the fact that some of the libraries 
+// inherit from GeN-Foam and OpenFOAM, both licensed under GNU General
+// Public License v3.0.
+//
+// As such, the entire library is released under GNU GPL v3.0. It is a strong 
+// copyleft license which means you cannot use it in proprietary software.
+//
+//
+// License
+//    This application is part ciet_digital_twin_rust which uses
+//    fluid_mechanics_rust, a partial library of the
+//    thermal hydraulics library written in rust meant to help with the
+//    fluid mechanics aspects of the calculations
+//     
+//    Copyright (C) 2022-2023  Theodore Kay Chen Ong, Singapore Nuclear
+//    Research and Safety Initiative, Per F. Peterson, University of 
+//    California, Berkeley Thermal Hydraulics Laboratory
+//
+//    ciet_digital_twin_rust is free software; you can redistribute it and/or modify it
+//    under the terms of the GNU General Public License as published by the
+//    Free Software Foundation; either version 2 of the License, or (at your
+//    option) any later version.
+//
+//    ciet_digital_twin_rust is distributed in the hope that it will be useful, but WITHOUT
+//    ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or
+//    FITNESS FOR A PARTICULAR PURPOSE.  See the GNU General Public License
+//    for more details.
+//
+//    ciet_digital_twin_rust relies on a thermal hydraulics library
+//    also written in rust. The isothermal digital twin in particular
+//    relies on fluid_mechanics_rust.
+//    This library is part of a thermal hydraulics library in rust
+//    and contains some code copied from GeN-Foam, and OpenFOAM derivative.
+//    This offering is not approved or endorsed by the OpenFOAM Foundation nor
+//    OpenCFD Limited, producer and distributor of the OpenFOAM(R)software via
+//    www.openfoam.com, and owner of the OPENFOAM(R) and OpenCFD(R) trademarks.
+//    Nor is it endorsed by the authors and owners of GeN-Foam.
+//
+//    You should have received a copy of the GNU General Public License
+//    along with this program.  If not, see <http://www.gnu.org/licenses/>.
+//
+// © All rights reserved. Theodore Kay Chen Ong,
+// Singapore Nuclear Research and Safety Initiative,
+// Per F. Peterson,
+// University of California, Berkeley Thermal Hydraulics Laboratory
+//
+// Main author of the code: Theodore Kay Chen Ong, supervised by
+// Professor Per F. Peterson
+extern crate fluid_mechanics_rust;
+use fluid_mechanics_rust::prelude::*;
+
+use crate::energy_equation::{get_temperature_degrees_c,
+    get_therminol_specific_heat_capacity_joule_per_kg_kelvin,
+    get_therminol_thermal_conductivity_watts_per_meter_kelvin};
+use crate::ciet_solver::{THERMINOL_MIN_VALIDATED_TEMPERATURE_DEGC,
+    THERMINOL_MAX_VALIDATED_TEMPERATURE_DEGC};
+
+/// properties of the working fluid in a pipe or component as 
+/// functions of temperature
+///
+/// pipes and components hold one of these behind an Arc, so 
+/// the same loop geometry can be run with therminol (as in 
+/// ciet) or with the fluid ciet is meant to represent, eg. 
+/// FLiBe
+pub trait FluidProperties: Send + Sync {
+
+    /// name of the fluid, eg. "therminol_vp1"
+    fn get_fluid_name(&self) -> &str;
+
+    fn get_density(&self, fluid_temp: ThermodynamicTemperature) -> MassDensity;
+
+    fn get_viscosity(&self, fluid_temp: ThermodynamicTemperature) -> DynamicViscosity;
+
+    fn get_specific_heat_capacity_joule_per_kg_kelvin(
+        &self, fluid_temp: ThermodynamicTemperature) -> f64;
+
+    fn get_thermal_conductivity_watts_per_meter_kelvin(
+        &self, fluid_temp: ThermodynamicTemperature) -> f64;
+
+    /// returns the lowest and highest temperature (degrees C) 
+    /// the correlations are valid for, outside this range the
+    /// properties are extrapolated
+    fn get_validated_temperature_range_degrees_c(&self) -> (f64, f64);
+}
+
+/// therminol VP-1, the oil used in ciet
+///
+/// density and viscosity come from TherminolVP1Properties in 
+/// fluid_mechanics_rust, as they always have
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct TherminolVP1;
+
+impl FluidProperties for TherminolVP1 {
+
+    fn get_fluid_name(&self) -> &str {
+        return "therminol_vp1";
+    }
+
+    fn get_density(&self, fluid_temp: ThermodynamicTemperature) -> MassDensity {
+        return TherminolVP1Properties::new().density(fluid_temp);
+    }
+
+    fn get_viscosity(&self, fluid_temp: ThermodynamicTemperature) -> DynamicViscosity {
+        return TherminolVP1Properties::new().viscosity(fluid_temp);
+    }
+
+    fn get_specific_heat_capacity_joule_per_kg_kelvin(
+        &self, fluid_temp: ThermodynamicTemperature) -> f64 {
+        return get_therminol_specific_heat_capacity_joule_per_kg_kelvin(fluid_temp);
+    }
+
+    fn get_thermal_conductivity_watts_per_meter_kelvin(
+        &self, fluid_temp: ThermodynamicTemperature) -> f64 {
+        return get_therminol_thermal_conductivity_watts_per_meter_kelvin(fluid_temp);
+    }
+
+    fn get_validated_temperature_range_degrees_c(&self) -> (f64, f64) {
+        return (THERMINOL_MIN_VALIDATED_TEMPERATURE_DEGC, 
+                THERMINOL_MAX_VALIDATED_TEMPERATURE_DEGC);
+    }
+}
+
+/// liquid water at atmospheric pressure
+///
+/// density is the Thiesen type fit and viscosity is the Vogel 
+/// type fit, both are good to well under a percent between 
+/// 5C and 95C
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct LiquidWater;
+
+impl FluidProperties for LiquidWater {
+
+    fn get_fluid_name(&self) -> &str {
+        return "water";
+    }
+
+    fn get_density(&self, fluid_temp: ThermodynamicTemperature) -> MassDensity {
+
+        let temperature_degrees_c = get_temperature_degrees_c(fluid_temp);
+
+        let density = 1000.0 * (1.0 - 
+            (temperature_degrees_c + 288.9414) 
+            * (temperature_degrees_c - 3.9863).powi(2)
+            / (508929.2 * (temperature_degrees_c + 68.12963)));
+
+        return MassDensity::new::<kilogram_per_cubic_meter>(density);
+    }
+
+    fn get_viscosity(&self, fluid_temp: ThermodynamicTemperature) -> DynamicViscosity {
+
+        // fluid_temp.value is in kelvin
+        let viscosity = 2.414e-5 * 10.0_f64.powf(247.8 / (fluid_temp.value - 140.0));
+
+        return DynamicViscosity::new::<pascal_second>(viscosity);
+    }
+
+    fn get_specific_heat_capacity_joule_per_kg_kelvin(
+        &self, fluid_temp: ThermodynamicTemperature) -> f64 {
+
+        let temperature_degrees_c = get_temperature_degrees_c(fluid_temp);
+
+        return 4217.4 
+            - 3.720283 * temperature_degrees_c 
+            + 0.1412855 * temperature_degrees_c.powi(2) 
+            - 2.654387e-3 * temperature_degrees_c.powi(3) 
+            + 2.093236e-5 * temperature_degrees_c.powi(4);
+    }
+
+    fn get_thermal_conductivity_watts_per_meter_kelvin(
+        &self, fluid_temp: ThermodynamicTemperature) -> f64 {
+
+        let temperature_degrees_c = get_temperature_degrees_c(fluid_temp);
+
+        return 0.5706 
+            + 1.756e-3 * temperature_degrees_c 
+            - 6.46e-6 * temperature_degrees_c.powi(2);
+    }
+
+    fn get_validated_temperature_range_degrees_c(&self) -> (f64, f64) {
+        return (5.0, 95.0);
+    }
+}
+
+/// Dowtherm A, the simulant oil used in the earlier ciet 
+/// design work, its properties are close to therminol VP-1
+///
+/// the correlations are the ones Zweibaum used for the ciet 
+/// design, temperatures in degrees C
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct DowthermA;
+
+impl FluidProperties for DowthermA {
+
+    fn get_fluid_name(&self) -> &str {
+        return "dowtherm_a";
+    }
+
+    fn get_density(&self, fluid_temp: ThermodynamicTemperature) -> MassDensity {
+
+        let temperature_degrees_c = get_temperature_degrees_c(fluid_temp);
+
+        return MassDensity::new::<kilogram_per_cubic_meter>(
+            1078.0 - 0.85 * temperature_degrees_c);
+    }
+
+    fn get_viscosity(&self, fluid_temp: ThermodynamicTemperature) -> DynamicViscosity {
+
+        let temperature_degrees_c = get_temperature_degrees_c(fluid_temp);
+
+        return DynamicViscosity::new::<pascal_second>(
+            0.130 / temperature_degrees_c.powf(1.072));
+    }
+
+    fn get_specific_heat_capacity_joule_per_kg_kelvin(
+        &self, fluid_temp: ThermodynamicTemperature) -> f64 {
+
+        return 1518.0 + 2.82 * get_temperature_degrees_c(fluid_temp);
+    }
+
+    fn get_thermal_conductivity_watts_per_meter_kelvin(
+        &self, fluid_temp: ThermodynamicTemperature) -> f64 {
+
+        return 0.142 - 0.00016 * get_temperature_degrees_c(fluid_temp);
+    }
+
+    fn get_validated_temperature_range_degrees_c(&self) -> (f64, f64) {
+        return (20.0, 180.0);
+    }
+}
+
+/// FLiBe (2LiF-BeF2) molten salt, the coolant of the 
+/// fluoride salt cooled high temperature reactor which ciet 
+/// is scaled from
+///
+/// these are the correlations recommended by Romatoski and Hu 
+/// (2017), temperatures in kelvin. FLiBe freezes at 459C, so it
+/// is only validated well above that
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct FLiBe;
+
+impl FluidProperties for FLiBe {
+
+    fn get_fluid_name(&self) -> &str {
+        return "flibe";
+    }
+
+    fn get_density(&self, fluid_temp: ThermodynamicTemperature) -> MassDensity {
+
+        // fluid_temp.value is in kelvin
+        return MassDensity::new::<kilogram_per_cubic_meter>(
+            2413.0 - 0.488 * fluid_temp.value);
+    }
+
+    fn get_viscosity(&self, fluid_temp: ThermodynamicTemperature) -> DynamicViscosity {
+
+        return DynamicViscosity::new::<pascal_second>(
+            1.16e-4 * (3755.0 / fluid_temp.value).exp());
+    }
+
+    fn get_specific_heat_capacity_joule_per_kg_kelvin(
+        &self, _fluid_temp: ThermodynamicTemperature) -> f64 {
+        return 2386.0;
+    }
+
+    fn get_thermal_conductivity_watts_per_meter_kelvin(
+        &self, _fluid_temp: ThermodynamicTemperature) -> f64 {
+        return 1.1;
+    }
+
+    fn get_validated_temperature_range_degrees_c(&self) -> (f64, f64) {
+        return (470.0, 700.0);
+    }
+}
@@ -0,0 +1,182 @@
+// This application was developed for use in my PhD thesis under supervision 
+// of Professor Per F. Peterson. It is part of a thermal hydraulics
+// library in Rust that is released under the GNU General Public License
+// v 3.0. This is partly due to the fact that some of the libraries 
+// inherit from GeN-Foam and OpenFOAM, both licensed under GNU General
+// Public License v3.0.
+//
+// As such, the entire library is released under GNU GPL v3.0. It is a strong 
+// copyleft license which means you cannot use it in proprietary software.
+//
+//
+// License
+//    This application is part ciet_digital_twin_rust which uses
+//    fluid_mechanics_rust, a partial library of the
+//    thermal hydraulics library written in rust meant to help with the
+//    fluid mechanics aspects of the calculations
+//     
+//    Copyright (C) 2022-2023  Theodore Kay Chen Ong, Singapore Nuclear
+//    Research and Safety Initiative, Per F. Peterson, University of 
+//    California, Berkeley Thermal Hydraulics Laboratory
+//
+//    ciet_digital_twin_rust is free software; you can redistribute it and/or modify it
+//    under the terms of the GNU General Public License as published by the
+//    Free Software Foundation; either version 2 of the License, or (at your
+//    option) any later version.
+//
+//    ciet_digital_twin_rust is distributed in the hope that it will be useful, but WITHOUT
+//    ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or
+//    FITNESS FOR A PARTICULAR PURPOSE.  See the GNU General Public License
+//    for more details.
+//
+//    ciet_digital_twin_rust relies on a thermal hydraulics library
+//    also written in rust. The isothermal digital twin in particular
+//    relies on fluid_mechanics_rust.
+//    This library is part of a thermal hydraulics library in rust
+//    and contains some code copied from GeN-Foam, and OpenFOAM derivative.
+//    This offering is not approved or endorsed by the OpenFOAM Foundation nor
+//    OpenCFD Limited, producer and distributor of the OpenFOAM(R)software via
+//    www.openfoam.com, and owner of the OPENFOAM(R) and OpenCFD(R) trademarks.
+//    Nor is it endorsed by the authors and owners of GeN-Foam.
+//
+//    You should have received a copy of the GNU General Public License
+//    along with this program.  If not, see <http://www.gnu.org/licenses/>.
+//
+// © All rights reserved. Theodore Kay Chen Ong,
+// Singapore Nuclear Research and Safety Initiative,
+// Per F. Peterson,
+// University of California, Berkeley Thermal Hydraulics Laboratory
+//
+// Main author of the code: Theodore Kay Chen Ong, supervised by
+// Professor Per F. Peterson
+extern crate fluid_mechanics_rust;
+use fluid_mechanics_rust::prelude::*;
+
+use std::f64::consts::PI;
+
+/// insulation wrapped around a pipe or component, heat is 
+/// lost from the fluid by conduction through the insulation 
+/// and then by convection to the ambient air
+///
+/// the thermal resistances are in series, for a cylinder of 
+/// length L:
+///
+/// 1/UA = ln(r_o/r_i)/(2 pi k L) + 1/(h 2 pi r_o L)
+///
+/// r_i is the inner radius of the insulation and r_o = r_i + 
+/// thickness. The pipe wall is thin and conducts well, so its
+/// resistance is neglected, and r_i is taken as the radius of 
+/// a circle with the same area as the flow area
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct InsulationParameters {
+    insulation_thickness: Length,
+    insulation_thermal_conductivity_watts_per_meter_kelvin: f64,
+    ambient_heat_transfer_coefficient_watts_per_square_meter_kelvin: f64,
+}
+
+impl InsulationParameters {
+
+    /// constructor, the conductivity is in W/(m K) and the 
+    /// ambient heat transfer coefficient (natural convection
+    /// and radiation to the room) is in W/(m^2 K)
+    ///
+    /// negative values are clamped to zero
+    pub fn new(insulation_thickness: Length,
+               insulation_thermal_conductivity_watts_per_meter_kelvin: f64,
+               ambient_heat_transfer_coefficient_watts_per_square_meter_kelvin: f64) 
+        -> Self {
+
+        let insulation_thickness = if insulation_thickness.value < 0.0 {
+            Length::new::<meter>(0.0)
+        } else {
+            insulation_thickness
+        };
+
+        return Self { 
+            insulation_thickness: insulation_thickness, 
+            insulation_thermal_conductivity_watts_per_meter_kelvin: 
+                insulation_thermal_conductivity_watts_per_meter_kelvin.max(0.0), 
+            ambient_heat_transfer_coefficient_watts_per_square_meter_kelvin: 
+                ambient_heat_transfer_coefficient_watts_per_square_meter_kelvin.max(0.0),
+        };
+    }
+
+    /// returns insulation like that on the ciet pipes, about 
+    /// an inch of fiberglass in still room air
+    ///
+    /// the values are typical textbook ones and have not been 
+    /// fitted to ciet heat loss data
+    pub fn ciet_fiberglass() -> Self {
+        return Self::new(
+            Length::new::<meter>(0.0254),
+            0.04,
+            6.0);
+    }
+
+    pub fn get_insulation_thickness(&self) -> Length {
+        return self.insulation_thickness;
+    }
+
+    pub fn get_insulation_thermal_conductivity_watts_per_meter_kelvin(&self) -> f64 {
+        return self.insulation_thermal_conductivity_watts_per_meter_kelvin;
+    }
+
+    pub fn get_ambient_heat_transfer_coefficient_watts_per_square_meter_kelvin(
+        &self) -> f64 {
+        return self.ambient_heat_transfer_coefficient_watts_per_square_meter_kelvin;
+    }
+
+    /// returns the conductance UA (W/K) from the fluid to 
+    /// ambient for a component with the given flow area and 
+    /// length
+    pub fn get_conductance_watts_per_kelvin(
+        &self,
+        cross_sectional_area: Area,
+        component_length: Length) -> f64 {
+
+        let inner_radius = (cross_sectional_area.value.abs() / PI).sqrt();
+
+        return self.get_conductance_watts_per_kelvin_from_inner_radius(
+            Length::new::<meter>(inner_radius), 
+            component_length);
+    }
+
+    /// returns the conductance UA (W/K) from the inner surface 
+    /// of the insulation to ambient, eg. when the insulation 
+    /// is wrapped around a pipe wall of known outer radius
+    pub fn get_conductance_watts_per_kelvin_from_inner_radius(
+        &self,
+        inner_radius: Length,
+        component_length: Length) -> f64 {
+
+        let length = component_length.value.abs();
+        let inner_radius = inner_radius.value.abs();
+        let outer_radius = inner_radius + self.insulation_thickness.value;
+
+        let conductivity = 
+            self.insulation_thermal_conductivity_watts_per_meter_kelvin;
+        let heat_transfer_coefficient = 
+            self.ambient_heat_transfer_coefficient_watts_per_square_meter_kelvin;
+
+        // no area, or either resistance is infinite, so no heat 
+        // is lost
+        if length <= 0.0 || outer_radius <= 0.0 || heat_transfer_coefficient <= 0.0 {
+            return 0.0;
+        }
+
+        let convection_resistance = 
+            1.0 / (heat_transfer_coefficient * 2.0 * PI * outer_radius * length);
+
+        // with no thickness, only the convection resistance is 
+        // left
+        let conduction_resistance = if self.insulation_thickness.value <= 0.0 {
+            0.0
+        } else if conductivity <= 0.0 || inner_radius <= 0.0 {
+            return 0.0;
+        } else {
+            (outer_radius / inner_radius).ln() / (2.0 * PI * conductivity * length)
+        };
+
+        return 1.0 / (conduction_resistance + convection_resistance);
+    }
+}
@@ -53,11 +53,11 @@ extern crate fluid_mechanics_rust;
 use std::time::{Instant, Duration};
 use crate::{ctah_branch::*, therminol_component::TherminolCustomComponent, HeaterBranch, 
     DHXBranch};
+use crate::manometer::*;
 
 use fluid_mechanics_rust::prelude::*;
-extern crate roots;
-use roots::find_root_brent;
-use roots::SimpleConvergency;
+use std::cell::RefCell;
+use crate::ciet_solver::{CIETSolverError, find_root_brent_expanding};
 
 /// This is a struct representing the 
 /// CIET facility in isothermal operation
@@ -72,6 +72,10 @@ pub struct CIETIsothermalFacility<'ciet_collection_lifetime> {
     pub dhx_branch_mass_flowrate: MassRate,
     pub heater_branch_mass_flowrate: MassRate,
 
+    // pressure change from top header to bottom header
+    // across all three branches
+    header_pressure_change: Pressure,
+
     super_collection_vector_immutable: 
         Vec<&'ciet_collection_lifetime dyn FluidComponentCollectionMethods>,
 
@@ -106,10 +110,8 @@ FluidComponentSuperCollectionParallelAssociatedFunctions {}
 /// (4) the user should be able to execute value calculation via a function is called
 ///
 ///
-/// i would have liked to, but an not doing:
-/// (1) attain manometer readings if possible
-/// This is not quite critical for dissertation purposes
-/// and would take quite some effort
+/// (5) obtain manometer readings at every junction between
+/// components after calculate() is called
 ///
 ///
 impl<'ciet_collection_lifetime> CIETIsothermalFacility<'ciet_collection_lifetime> {
@@ -143,53 +145,148 @@ impl<'ciet_collection_lifetime> CIETIsothermalFacility<'ciet_collection_lifetime
         return self.heater_branch_mass_flowrate;
     }
 
+    /// returns the pressure change from the top header to the
+    /// bottom header, this is the same for all three branches
+    /// since they are in parallel
+    pub fn get_header_pressure_change(&self) -> Pressure {
+        return self.header_pressure_change;
+    }
 
-    pub fn calculate(&'ciet_collection_lifetime mut self) -> 
-        (Duration,MassRate,MassRate,MassRate)
-        {
+    /// returns manometer readings at every junction between 
+    /// components in the heater, dhx and ctah branches
+    /// (in that order)
+    ///
+    /// the readings are gauge pressures relative to the
+    /// reference node chosen by the user,
+    /// eg. the top header shared by all three branches
+    ///
+    /// calculate() must be called first, otherwise the readings
+    /// will correspond to zero flow in all branches
+    pub fn get_manometer_readings(
+        &self,
+        reference: ManometerReference) -> Vec<ManometerReading> {
+
+        let top_header_gauge_pressure = 
+            get_top_header_gauge_pressure(
+                reference, 
+                self.header_pressure_change);
+
+        let mut manometer_readings: Vec<ManometerReading> = vec![];
+
+        let mut heater_branch_readings = 
+            calculate_branch_manometer_readings(
+                "heater_branch", 
+                &self.heater_branch.get_component_names(), 
+                self.heater_branch.get_immutable_fluid_component_vector(), 
+                self.heater_branch_mass_flowrate, 
+                top_header_gauge_pressure);
+
+        let mut dhx_branch_readings = 
+            calculate_branch_manometer_readings(
+                "dhx_branch", 
+                &self.dhx_branch.get_component_names(), 
+                self.dhx_branch.get_immutable_fluid_component_vector(), 
+                self.dhx_branch_mass_flowrate, 
+                top_header_gauge_pressure);
+
+        let mut ctah_branch_readings = 
+            calculate_branch_manometer_readings(
+                "ctah_branch", 
+                &self.ctah_branch.get_component_names(), 
+                self.ctah_branch.get_immutable_fluid_component_vector(), 
+                self.ctah_branch_mass_flowrate, 
+                top_header_gauge_pressure);
+
+        manometer_readings.append(&mut heater_branch_readings);
+        manometer_readings.append(&mut dhx_branch_readings);
+        manometer_readings.append(&mut ctah_branch_readings);
+
+        return manometer_readings;
+    }
+
+    /// returns labels for all the manometer readings in the
+    /// same order as get_manometer_readings
+    ///
+    /// this doesn't require any calculation to be done,
+    /// so it is useful for setting up opcua nodes beforehand
+    pub fn get_manometer_tap_labels() -> Vec<String> {
+
+        let mut tap_labels: Vec<String> = vec![];
+
+        tap_labels.append(&mut get_branch_manometer_tap_labels(
+                "heater_branch", 
+                &HeaterBranch::new().get_component_names()));
+
+        tap_labels.append(&mut get_branch_manometer_tap_labels(
+                "dhx_branch", 
+                &DHXBranch::new().get_component_names()));
+
+        tap_labels.append(&mut get_branch_manometer_tap_labels(
+                "ctah_branch", 
+                &CTAHBranch::new().get_component_names()));
 
+        return tap_labels;
+    }
+
+
+    /// calculates the flowrates in all three branches
+    ///
+    /// returns a CIETSolverError if the header pressure change
+    /// or any of the branch flowrates cannot be found
+    pub fn calculate(&mut self) -> 
+        Result<(Duration,MassRate,MassRate,MassRate), CIETSolverError>
+        {
             // start the timer
             let start = Instant::now();
 
             // i'm using the same algorithm from ciet digital twin v1
 
-            let pressure_change_root = 
-                |pressure_change_pascals: f64| -> f64 {
-
-                    // let's get heater branch mass flowrate
-                    // given the iterated pressure change
-                    //
-                    let test_pressure_change = 
-                        Pressure::new::<pascal>(pressure_change_pascals);
+            let total_mass_flowrate = 
+                |test_pressure_change: Pressure| 
+                -> Result<MassRate, CIETSolverError> {
 
                     let heater_branch_mass_flowrate = 
                         self.heater_branch.
-                        get_mass_flowrate_from_pressure_change(
-                            test_pressure_change);
-
-
+                        try_get_mass_flowrate_from_pressure_change(
+                            test_pressure_change)?;
 
                     let dhx_branch_mass_flowrate = 
                         self.dhx_branch.
-                        get_mass_flowrate_from_pressure_change(
-                            test_pressure_change);
-
-
+                        try_get_mass_flowrate_from_pressure_change(
+                            test_pressure_change)?;
 
-
-                    
                     let ctah_branch_mass_flowrate = 
                         self.ctah_branch.
-                        get_mass_flowrate_from_pressure_change(
-                            test_pressure_change);
+                        try_get_mass_flowrate_from_pressure_change(
+                            test_pressure_change)?;
 
-                    let total_mass_flowrate = 
-                        heater_branch_mass_flowrate 
+                    return Ok(heater_branch_mass_flowrate 
                         + dhx_branch_mass_flowrate
-                        + ctah_branch_mass_flowrate;
+                        + ctah_branch_mass_flowrate);
+                };
 
-                    return total_mass_flowrate.value;
+            // brent's method only works with f64, so if a branch 
+            // solver fails within the root find, i store its error 
+            // here and return NaN
+            let branch_solver_error: RefCell<Option<CIETSolverError>> = 
+                RefCell::new(None);
 
+            let pressure_change_root = 
+                |pressure_change_pascals: f64| -> f64 {
+
+                    let test_pressure_change = 
+                        Pressure::new::<pascal>(pressure_change_pascals);
+
+                    match total_mass_flowrate(test_pressure_change) {
+                        Ok(total_mass_flowrate) => {
+                            return total_mass_flowrate.value;
+                        },
+                        Err(solver_error) => {
+                            branch_solver_error.borrow_mut().
+                                get_or_insert(solver_error);
+                            return f64::NAN;
+                        },
+                    }
 
                 };
 
@@ -203,49 +300,49 @@ impl<'ciet_collection_lifetime> CIETIsothermalFacility<'ciet_collection_lifetime
                 get_pressure_change(zero_flowrate) +
                 Pressure::new::<pascal>(-50000_f64);
 
-
-            let mut convergency = SimpleConvergency { eps:1e-9_f64, max_iter:30 };
-
-            let pressure_change_value 
-                = find_root_brent(
-                    upper_bound.value,
+            // the +/- 50 kPa bracket around the heater branch 
+            // hydrostatic head is only a starting point, it is 
+            // expanded if the root is not within it
+            let pressure_change_result 
+                = find_root_brent_expanding(
+                    "header_pressure_change_pa",
                     lower_bound.value,
-                    &pressure_change_root,
-                    &mut convergency).unwrap();
+                    upper_bound.value,
+                    pressure_change_root);
 
-            //let pressure_change_value = 0.0;
+            // the branch error is more useful than the NaN it caused
+            if let Some(solver_error) = branch_solver_error.into_inner() {
+                return Err(solver_error);
+            }
 
             let pressure_change = 
-                Pressure::new::<pascal>(pressure_change_value);
+                Pressure::new::<pascal>(pressure_change_result?);
 
             let ctah_branch_flowrate = self.ctah_branch.
-                get_mass_flowrate_from_pressure_change(pressure_change);
+                try_get_mass_flowrate_from_pressure_change(pressure_change)?;
 
             let heater_branch_flowrate = self.heater_branch.
-                get_mass_flowrate_from_pressure_change(pressure_change);
+                try_get_mass_flowrate_from_pressure_change(pressure_change)?;
 
             // again, check valve behaviour algorithm
 
             let dhx_branch_flowrate = self.dhx_branch.
-                get_mass_flowrate_from_pressure_change(pressure_change);
-
+                try_get_mass_flowrate_from_pressure_change(pressure_change)?;
 
             self.ctah_branch_mass_flowrate = ctah_branch_flowrate;
             self.heater_branch_mass_flowrate = heater_branch_flowrate;
             self.dhx_branch_mass_flowrate = dhx_branch_flowrate;
-
-
+            self.header_pressure_change = pressure_change;
 
             // now that i've gotten all the calculations, i can return the
             // elapsed time to the environment
 
-
             let elapsed_time: Duration= start.elapsed();
 
-            return (elapsed_time,
+            return Ok((elapsed_time,
                     ctah_branch_flowrate,
                     heater_branch_flowrate,
-                    dhx_branch_flowrate);
+                    dhx_branch_flowrate));
 
         }
 
@@ -266,6 +363,7 @@ impl<'ciet_collection_lifetime> CIETIsothermalFacility<'ciet_collection_lifetime
             ctah_branch_mass_flowrate: MassRate::new::<kilogram_per_second>(0.0), 
             dhx_branch_mass_flowrate: MassRate::new::<kilogram_per_second>(0.0), 
             heater_branch_mass_flowrate: MassRate::new::<kilogram_per_second>(0.0), 
+            header_pressure_change: Pressure::new::<pascal>(0.0),
             super_collection_vector_immutable: vec![], 
             ctah_branch: ctah_branch, 
             heater_branch: heater_branch, 
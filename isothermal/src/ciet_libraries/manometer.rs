@@ -0,0 +1,231 @@
+// This application was developed for use in my PhD thesis under supervision 
+// of Professor Per F. Peterson. It is part of a thermal hydraulics
+// library in Rust that is released under the GNU General Public License
+// v 3.0. This is partly due to the fact that some of the libraries 
+// inherit from GeN-Foam and OpenFOAM, both licensed under GNU General
+// Public License v3.0.
+//
+// As such, the entire library is released under GNU GPL v3.0. It is a strong 
+// copyleft license which means you cannot use it in proprietary software.
+//
+//
+// License
+//    This application is part ciet_digital_twin_rust which uses
+//    fluid_mechanics_rust, a partial library of the
+//    thermal hydraulics library written in rust meant to help with the
+//    fluid mechanics aspects of the calculations
+//     
+//    Copyright (C) 2022-2023  Theodore Kay Chen Ong, Singapore Nuclear
+//    Research and Safety Initiative, Per F. Peterson, University of 
+//    California, Berkeley Thermal Hydraulics Laboratory
+//
+//    ciet_digital_twin_rust is free software; you can redistribute it and/or modify it
+//    under the terms of the GNU General Public License as published by the
+//    Free Software Foundation; either version 2 of the License, or (at your
+//    option) any later version.
+//
+//    ciet_digital_twin_rust is distributed in the hope that it will be useful, but WITHOUT
+//    ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or
+//    FITNESS FOR A PARTICULAR PURPOSE.  See the GNU General Public License
+//    for more details.
+//
+//    ciet_digital_twin_rust relies on a thermal hydraulics library
+//    also written in rust. The isothermal digital twin in particular
+//    relies on fluid_mechanics_rust.
+//    This library is part of a thermal hydraulics library in rust
+//    and contains some code copied from GeN-Foam, and OpenFOAM derivative.
+//    This offering is not approved or endorsed by the OpenFOAM Foundation nor
+//    OpenCFD Limited, producer and distributor of the OpenFOAM(R)software via
+//    www.openfoam.com, and owner of the OPENFOAM(R) and OpenCFD(R) trademarks.
+//    Nor is it endorsed by the authors and owners of GeN-Foam.
+//
+//    You should have received a copy of the GNU General Public License
+//    along with this program.  If not, see <http://www.gnu.org/licenses/>.
+//
+// © All rights reserved. Theodore Kay Chen Ong,
+// Singapore Nuclear Research and Safety Initiative,
+// Per F. Peterson,
+// University of California, Berkeley Thermal Hydraulics Laboratory
+//
+// Main author of the code: Theodore Kay Chen Ong, supervised by
+// Professor Per F. Peterson
+extern crate fluid_mechanics_rust;
+use fluid_mechanics_rust::prelude::*;
+
+/// the reference node for manometer readings
+///
+/// all gauge pressures are measured relative to the pressure
+/// at this node
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ManometerReference {
+    /// the top header shared by the heater, dhx and ctah branches
+    TopHeader,
+    /// the bottom header shared by the heater, dhx and ctah branches
+    BottomHeader,
+}
+
+/// a pressure tap reading at the junction between two components
+/// in a branch
+///
+/// the gauge pressure is relative to whichever reference node
+/// was chosen when the reading was taken
+#[derive(Debug, Clone)]
+pub struct ManometerReading {
+    pub branch_name: String,
+    pub upstream_component_name: String,
+    pub downstream_component_name: String,
+    pub gauge_pressure: Pressure,
+}
+
+impl ManometerReading {
+
+    /// returns a label unique to this pressure tap, 
+    /// suitable for use as an opcua node id
+    pub fn get_label(&self) -> String {
+        return get_manometer_tap_label(
+            &self.branch_name,
+            &self.upstream_component_name,
+            &self.downstream_component_name);
+    }
+}
+
+/// returns a label for a pressure tap between two components
+/// in a branch
+pub fn get_manometer_tap_label(
+    branch_name: &str,
+    upstream_component_name: &str,
+    downstream_component_name: &str) -> String {
+
+    return format!("{}_manometer_{}_to_{}",
+                   branch_name,
+                   upstream_component_name,
+                   downstream_component_name);
+}
+
+/// returns the labels of every pressure tap in a branch given the
+/// names of its components in order
+///
+/// this is handy if you want to know which taps exist before
+/// any calculation has been done
+pub fn get_branch_manometer_tap_labels(
+    branch_name: &str,
+    component_names: &Vec<String>) -> Vec<String> {
+
+    let mut tap_labels: Vec<String> = vec![];
+
+    // a branch with n components has n-1 junctions
+    for index in 1..component_names.len() {
+        tap_labels.push(get_manometer_tap_label(
+                branch_name,
+                &component_names[index-1],
+                &component_names[index]));
+    }
+
+    return tap_labels;
+}
+
+/// calculates gauge pressures at every junction between
+/// components in a branch
+///
+/// the branch is assumed to start at the top header and end at the
+/// bottom header, as all CIET branches in this library do
+///
+/// top_header_gauge_pressure is the pressure of the top header
+/// relative to the chosen reference node
+///
+/// component names are matched to the fluid component vector by 
+/// position, if the lengths do not match, generic names are used
+/// instead
+pub fn calculate_branch_manometer_readings(
+    branch_name: &str,
+    component_names: &Vec<String>,
+    fluid_component_vector: &Vec<&dyn FluidComponent>,
+    branch_mass_flowrate: MassRate,
+    top_header_gauge_pressure: Pressure) -> Vec<ManometerReading> {
+
+    let component_pressure_changes: Vec<Pressure> = 
+        fluid_component_vector.iter().
+        map(|fluid_component| fluid_component.
+            get_pressure_change_immutable(branch_mass_flowrate)).
+        collect();
+
+    return calculate_branch_manometer_readings_from_pressure_changes(
+        branch_name, 
+        component_names, 
+        &component_pressure_changes, 
+        top_header_gauge_pressure);
+}
+
+/// same as calculate_branch_manometer_readings, but the pressure
+/// change across each component is already known
+///
+/// this is for components which are not plain fluid components,
+/// such as pumps running on a pump curve
+pub fn calculate_branch_manometer_readings_from_pressure_changes(
+    branch_name: &str,
+    component_names: &Vec<String>,
+    component_pressure_changes: &Vec<Pressure>,
+    top_header_gauge_pressure: Pressure) -> Vec<ManometerReading> {
+
+    let mut manometer_readings: Vec<ManometerReading> = vec![];
+
+    let names_match = 
+        component_names.len() == component_pressure_changes.len();
+
+    let get_name = |index: usize| -> String {
+        if names_match {
+            return component_names[index].clone();
+        }
+        return format!("component_{}", index);
+    };
+
+    let mut gauge_pressure = top_header_gauge_pressure;
+
+    // i walk down the branch from top to bottom, adding up the
+    // pressure change of each component as i go
+    //
+    // the last component ends at the bottom header, so it 
+    // has no junction after it
+    let number_of_components = component_pressure_changes.len();
+
+    for index in 0..number_of_components {
+
+        gauge_pressure = gauge_pressure + 
+            component_pressure_changes[index];
+
+        if index + 1 == number_of_components {
+            break;
+        }
+
+        manometer_readings.push(ManometerReading { 
+            branch_name: branch_name.to_string(), 
+            upstream_component_name: get_name(index), 
+            downstream_component_name: get_name(index+1), 
+            gauge_pressure: gauge_pressure });
+    }
+
+    return manometer_readings;
+}
+
+/// returns the gauge pressure of the top header relative to the
+/// reference node
+///
+/// header_pressure_change is the pressure change going from the 
+/// top header to the bottom header, ie. the pressure change 
+/// across every branch
+pub fn get_top_header_gauge_pressure(
+    reference: ManometerReference,
+    header_pressure_change: Pressure) -> Pressure {
+
+    match reference {
+        ManometerReference::TopHeader => {
+            return Pressure::new::<pascal>(0.0);
+        },
+        ManometerReference::BottomHeader => {
+            // p_bottom = p_top + header_pressure_change
+            // so relative to the bottom header, 
+            // p_top - p_bottom = -header_pressure_change
+            return -header_pressure_change;
+        },
+    }
+}
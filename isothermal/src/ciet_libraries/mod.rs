@@ -69,7 +69,54 @@ pub use component_libraries::*;
 
 /// contains class or struct for isothermal branches in ciet
 /// a branch is a series of pipes in ciet
+pub mod ciet_solver;
+pub use ciet_solver::*;
+
+/// contains the specific heat capacity of therminol and the 
+/// upwinded energy equation for a single control volume
+pub mod energy_equation;
+pub use energy_equation::*;
+
+/// contains the working fluid property trait, with therminol 
+/// VP-1, water, Dowtherm A and FLiBe
+pub mod fluid_properties;
+pub use fluid_properties::*;
+
+/// contains the insulation around pipes and components, and 
+/// the conductance through it for heat lost to ambient
+pub mod insulation;
+pub use insulation::*;
+
+/// contains the walls of pipes and components, which store 
+/// heat and pass it between the fluid and the insulation
+pub mod pipe_wall;
+pub use pipe_wall::*;
+
+/// contains the point kinetics model of a reactor core, with 
+/// decay heat and temperature feedback, which can drive the 
+/// heater power
+pub mod point_kinetics;
+pub use point_kinetics::*;
+
+/// contains the NTU-effectiveness heat transfer model for 
+/// the coiled tube air heater (ctah) with its air side fan
+pub mod ctah_heat_exchanger;
+pub use ctah_heat_exchanger::*;
+
+/// contains the NTU-effectiveness heat transfer model for the 
+/// DHX, between the primary loop (shell side) and the DRACS 
+/// loop (tube side)
+pub mod dhx_heat_exchanger;
+pub use dhx_heat_exchanger::*;
+
+pub mod pump;
+pub use pump::*;
+
+pub mod check_valve;
+pub use check_valve::*;
+
 pub mod branch;
+pub use branch::*;
 
 /// contains the class representing ciet facility in isothermal operation
 /// primary loop only
@@ -77,5 +124,39 @@ pub mod branch;
 pub mod isothermal_ciet_facility;
 pub use isothermal_ciet_facility::*;
 
+/// contains the network of parallel branches between two 
+/// common headers, which the owned facilities solve with
+pub mod parallel_branch_network;
+pub use parallel_branch_network::*;
+
+/// contains the branch accessors shared by the owned facilities, 
+/// which always hold all of their branches
+pub(crate) mod facility_branches;
+
+/// contains the class representing ciet facility, isothermal or
+/// thermal hydraulic, which owns all its components, 
+/// so it can be constructed once and reused
+pub mod owned_ciet_facility;
+pub use owned_ciet_facility::*;
+
+/// contains the DRACS loop, solved on its own
+pub mod owned_dracs_facility;
+pub use owned_dracs_facility::*;
+
+/// contains the primary loop and DRACS loop coupled through 
+/// the DHX
+pub mod coupled_ciet_facility;
+pub use coupled_ciet_facility::*;
+
+/// contains structs and functions for manometer (pressure tap) 
+/// readings between components in ciet branches
+pub mod manometer;
+pub use manometer::*;
+
+/// contains the flow conditions and geometry of each component
+/// in a branch, for publishing component by component
+pub mod component_readings;
+pub use component_readings::*;
+
 
 
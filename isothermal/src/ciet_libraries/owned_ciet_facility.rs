@@ -0,0 +1,979 @@
+// This application was developed for use in my PhD thesis under supervision 
+// of Professor Per F. Peterson. It is part of a thermal hydraulics
+// library in Rust that is released under the GNU General Public License
+// v 3.0. This is partly due to the fact that some of the libraries 
+// inherit from GeN-Foam and OpenFOAM, both licensed under GNU General
+// Public License v3.0.
+//
+// As such, the entire library is released under GNU GPL v3.0. It is a strong 
+// copyleft license which means you cannot use it in proprietary software.
+//
+//
+// License
+//    This application is part ciet_digital_twin_rust which uses
+//    fluid_mechanics_rust, a partial library of the
+//    thermal hydraulics library written in rust meant to help with the
+//    fluid mechanics aspects of the calculations
+//     
+//    Copyright (C) 2022-2023  Theodore Kay Chen Ong, Singapore Nuclear
+//    Research and Safety Initiative, Per F. Peterson, University of 
+//    California, Berkeley Thermal Hydraulics Laboratory
+//
+//    ciet_digital_twin_rust is free software; you can redistribute it and/or modify it
+//    under the terms of the GNU General Public License as published by the
+//    Free Software Foundation; either version 2 of the License, or (at your
+//    option) any later version.
+//
+//    ciet_digital_twin_rust is distributed in the hope that it will be useful, but WITHOUT
+//    ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or
+//    FITNESS FOR A PARTICULAR PURPOSE.  See the GNU General Public License
+//    for more details.
+//
+//    ciet_digital_twin_rust relies on a thermal hydraulics library
+//    also written in rust. The isothermal digital twin in particular
+//    relies on fluid_mechanics_rust.
+//    This library is part of a thermal hydraulics library in rust
+//    and contains some code copied from GeN-Foam, and OpenFOAM derivative.
+//    This offering is not approved or endorsed by the OpenFOAM Foundation nor
+//    OpenCFD Limited, producer and distributor of the OpenFOAM(R)software via
+//    www.openfoam.com, and owner of the OPENFOAM(R) and OpenCFD(R) trademarks.
+//    Nor is it endorsed by the authors and owners of GeN-Foam.
+//
+//    You should have received a copy of the GNU General Public License
+//    along with this program.  If not, see <http://www.gnu.org/licenses/>.
+//
+// © All rights reserved. Theodore Kay Chen Ong,
+// Singapore Nuclear Research and Safety Initiative,
+// Per F. Peterson,
+// University of California, Berkeley Thermal Hydraulics Laboratory
+//
+// Main author of the code: Theodore Kay Chen Ong, supervised by
+// Professor Per F. Peterson
+extern crate fluid_mechanics_rust;
+use std::time::{Instant, Duration};
+use crate::{CTAHBranch, HeaterBranch, DHXBranch, CietHeaterVersion};
+use crate::branch::{TherminolBranch, TherminolBranchComponent};
+use crate::pump::PumpControl;
+use crate::manometer::*;
+use crate::component_readings::ComponentReading;
+use crate::parallel_branch_network::{ParallelBranchNetwork, 
+    MAX_TRANSIENT_SUBSTEP_SECONDS};
+use crate::ctah_heat_exchanger::CTAHHeatExchanger;
+use crate::insulation::InsulationParameters;
+use crate::pipe_wall::WallParameters;
+use crate::point_kinetics::{PointKinetics, PointKineticsParameters};
+use crate::energy_equation::get_temperature_degrees_c;
+use crate::fluid_properties::FluidProperties;
+use crate::facility_branches::*;
+
+use fluid_mechanics_rust::prelude::*;
+use std::collections::HashMap;
+use std::sync::Arc;
+use crate::ciet_solver::{CIETSolverError, CIETSolverIterationCounts, 
+    TemperatureRangePolicy};
+
+/// position of the heater branch within the facility network
+pub const HEATER_BRANCH_INDEX: usize = 0;
+
+/// position of the dhx branch within the facility network
+pub const DHX_BRANCH_INDEX: usize = 1;
+
+/// position of the ctah branch within the facility network
+pub const CTAH_BRANCH_INDEX: usize = 2;
+
+/// This is a struct representing the 
+/// CIET facility, isothermal by default, with the option of 
+/// solving for heat transfer as well (thermal hydraulics mode)
+///
+/// Unlike CIETIsothermalFacility, it owns all its branches and 
+/// components, so it can be constructed once and kept alive 
+/// (eg. behind an Arc<Mutex<>>) for as long as you like.
+///
+/// The heater, dhx and ctah branches are held in a 
+/// ParallelBranchNetwork, which does the actual solving
+///
+/// temperature is 21C all round by default, but the loop 
+/// temperature can be changed, and individual components can 
+/// be given their own temperature
+///
+/// in thermal hydraulics mode, the heater adds its electrical 
+/// power to the fluid, the ctah removes heat depending on its 
+/// fan speed, and step_transient (or advance_temperatures)
+/// carries the temperatures around the loop with the flow.
+/// Otherwise no heat transfer equations are solved
+///
+/// the heater power can also come from a point kinetics model
+/// of a reactor core (see enable_point_kinetics)
+pub struct OwnedCIETFacility {
+
+    ctah_pump_pressure: Pressure,
+
+    // uniform loop temperature, and the components which 
+    // are held at some other temperature
+    loop_temperature: ThermodynamicTemperature,
+    component_temperature_overrides: HashMap<String, ThermodynamicTemperature>,
+
+    // heater, dhx and ctah branches in that order
+    network: ParallelBranchNetwork,
+
+    // position of the ctah pump within the ctah branch
+    ctah_pump_index: usize,
+
+    // if true, step_transient also advances the temperatures
+    thermal_hydraulics_enabled: bool,
+
+    // version of the heater, its position within the heater
+    // branch, and the position of the first and last halves
+    // of the ctah within the ctah branch
+    ciet_heater_version: CietHeaterVersion,
+    heater_index: usize,
+    ctah_first_index: usize,
+    ctah_last_index: usize,
+
+    // position of the dhx shell side within the dhx branch
+    dhx_shell_side_index: usize,
+
+    // air side of the ctah
+    ctah_heat_exchanger: CTAHHeatExchanger,
+
+    // if present, sets the heater power whenever the 
+    // temperatures are advanced
+    point_kinetics: Option<PointKinetics>,
+
+}
+
+impl OwnedCIETFacility {
+
+    /// constructor, builds every component in ciet once,
+    /// with the first version of the heater
+    pub fn new() -> Self {
+        return Self::new_with_heater_version(CietHeaterVersion::Version1);
+    }
+
+    /// constructor, builds every component in ciet once,
+    /// with the given version of the heater
+    pub fn new_with_heater_version(ciet_heater_version: CietHeaterVersion) -> Self {
+
+        let heater_branch_factory = 
+            HeaterBranch::new_with_heater_version(ciet_heater_version);
+        let heater_name = heater_branch_factory.get_ciet_heater().
+            get_name().to_string();
+
+        let ctah_branch = CTAHBranch::new().get_owned_branch();
+        let heater_branch = heater_branch_factory.get_owned_branch();
+        let dhx_branch = DHXBranch::new().get_owned_branch();
+
+        let ctah_pump_index = ctah_branch.
+            get_component_index("ctah_pump").unwrap();
+
+        let heater_index = heater_branch.
+            get_component_index(&heater_name).unwrap();
+
+        let dhx_shell_side_index = dhx_branch.
+            get_component_index("dhx_shell_side_label_24").unwrap();
+
+        let ctah_vertical_index = ctah_branch.
+            get_component_index("ctah_vertical_label_7a").unwrap();
+        let ctah_horizontal_index = ctah_branch.
+            get_component_index("ctah_horizontal_label_7b").unwrap();
+
+        // the heater branch goes first, since the cold start 
+        // bracket is centred on the hydrostatic head of the 
+        // first branch
+        let network = ParallelBranchNetwork::new(
+            vec![heater_branch, dhx_branch, ctah_branch]);
+
+        return Self { 
+            ctah_pump_pressure: Pressure::new::<pascal>(0.0), 
+            loop_temperature: ThermodynamicTemperature::new::<degree_celsius>(21.0),
+            component_temperature_overrides: HashMap::new(),
+            network: network,
+            ctah_pump_index: ctah_pump_index,
+            thermal_hydraulics_enabled: false,
+            ciet_heater_version: ciet_heater_version,
+            heater_index: heater_index,
+            ctah_first_index: ctah_vertical_index.min(ctah_horizontal_index),
+            ctah_last_index: ctah_vertical_index.max(ctah_horizontal_index),
+            ctah_heat_exchanger: CTAHHeatExchanger::ciet_ctah(),
+            dhx_shell_side_index: dhx_shell_side_index,
+            point_kinetics: None,
+        };
+    }
+
+    /// returns the pressure rise across the ctah pump, 
+    ///
+    /// if the pump runs on its curve, this is the pressure rise
+    /// at the last calculated ctah branch flowrate
+    pub fn get_ctah_pump_pressure(&self) -> Pressure {
+
+        match self.get_ctah_branch().get_components()[self.ctah_pump_index] {
+            TherminolBranchComponent::Pump(ref pump) => {
+                return pump.get_pressure_rise(
+                    self.get_ctah_branch_mass_flowrate());
+            },
+            _ => {
+                return self.ctah_pump_pressure;
+            },
+        }
+    }
+
+    /// sets the ctah pump pressure in place,
+    /// no components are reconstructed
+    ///
+    /// the pump then acts as a fixed pressure source
+    pub fn set_ctah_pump_pressure(
+        &mut self, 
+        user_specified_pressure: Pressure){
+
+        self.ctah_pump_pressure = user_specified_pressure;
+        get_facility_branch_mut(&mut self.network, CTAH_BRANCH_INDEX).
+            set_component_internal_pressure_source(
+                self.ctah_pump_index, 
+                user_specified_pressure);
+    }
+
+    /// runs the ctah pump at a set speed (rpm) along its pump 
+    /// curve, calculate() then finds where the pump curve meets
+    /// the system curve
+    pub fn set_ctah_pump_speed(&mut self, speed_rpm: f64){
+        get_facility_branch_mut(&mut self.network, CTAH_BRANCH_INDEX).
+            set_component_pump_speed(
+                self.ctah_pump_index, 
+                speed_rpm);
+    }
+
+    /// sets the speed the ctah pump approaches in transients,
+    /// the pump speed follows with a first order lag
+    /// (see CentrifugalPump)
+    ///
+    /// this is how pump start-up is simulated
+    pub fn set_ctah_pump_target_speed(&mut self, target_speed_rpm: f64){
+        get_facility_branch_mut(&mut self.network, CTAH_BRANCH_INDEX).
+            set_component_pump_target_speed(
+                self.ctah_pump_index, 
+                target_speed_rpm);
+    }
+
+    /// trips the ctah pump, in transients it then coasts down
+    pub fn trip_ctah_pump(&mut self){
+        self.set_ctah_pump_target_speed(0.0);
+    }
+
+    /// returns the ctah pump control (fixed pressure or speed)
+    pub fn get_ctah_pump_control(&self) -> PumpControl {
+
+        match self.get_ctah_branch().get_components()[self.ctah_pump_index] {
+            TherminolBranchComponent::Pump(ref pump) => {
+                return pump.get_control();
+            },
+            _ => {
+                return PumpControl::PressureSource(self.ctah_pump_pressure);
+            },
+        }
+    }
+
+    pub fn get_ctah_branch_mass_flowrate(&self) -> MassRate {
+        return get_facility_branch_mass_flowrate(&self.network, CTAH_BRANCH_INDEX);
+    }
+
+    pub fn get_dhx_branch_mass_flowrate(&self) -> MassRate {
+        return get_facility_branch_mass_flowrate(&self.network, DHX_BRANCH_INDEX);
+    }
+
+    pub fn get_heater_branch_mass_flowrate(&self) -> MassRate {
+        return get_facility_branch_mass_flowrate(&self.network, HEATER_BRANCH_INDEX);
+    }
+
+    /// returns the pressure change from the top header to the
+    /// bottom header, this is the same for all three branches
+    /// since they are in parallel
+    pub fn get_header_pressure_change(&self) -> Pressure {
+        return self.network.get_header_pressure_change();
+    }
+
+    pub fn get_ctah_branch(&self) -> &TherminolBranch {
+        return get_facility_branch(&self.network, CTAH_BRANCH_INDEX);
+    }
+
+    pub fn get_heater_branch(&self) -> &TherminolBranch {
+        return get_facility_branch(&self.network, HEATER_BRANCH_INDEX);
+    }
+
+    pub fn get_dhx_branch(&self) -> &TherminolBranch {
+        return get_facility_branch(&self.network, DHX_BRANCH_INDEX);
+    }
+
+    /// sets the uniform loop temperature, every component 
+    /// without its own temperature override is set to this
+    ///
+    /// in thermal hydraulics mode, this resets the temperatures
+    /// the transient starts from
+    pub fn set_loop_temperature(&mut self, 
+                                loop_temperature: ThermodynamicTemperature){
+        self.loop_temperature = loop_temperature;
+        self.apply_fluid_temperatures();
+    }
+
+    pub fn get_loop_temperature(&self) -> ThermodynamicTemperature {
+        return self.loop_temperature;
+    }
+
+    /// holds one component at its own temperature regardless of 
+    /// the loop temperature, returns false if there is no 
+    /// component by that name
+    ///
+    /// in thermal hydraulics mode, only this component (and its
+    /// wall) is set, the rest of the loop keeps the temperatures
+    /// the transient has reached
+    pub fn set_component_temperature(
+        &mut self,
+        component_name: &str,
+        component_temperature: ThermodynamicTemperature) -> bool {
+
+        if self.network.get_component_fluid_temperature(component_name).is_none() {
+            return false;
+        }
+
+        self.component_temperature_overrides.insert(
+            component_name.to_string(), component_temperature);
+
+        if self.thermal_hydraulics_enabled {
+            self.network.set_component_fluid_temperature(
+                component_name, component_temperature);
+        } else {
+            self.apply_fluid_temperatures();
+        }
+
+        return true;
+    }
+
+    /// returns the component to the loop temperature
+    ///
+    /// in thermal hydraulics mode, the override is dropped but no
+    /// temperatures are changed, the component carries on from 
+    /// the temperature it is at
+    pub fn clear_component_temperature(&mut self, component_name: &str){
+        self.component_temperature_overrides.remove(component_name);
+
+        if !self.thermal_hydraulics_enabled {
+            self.apply_fluid_temperatures();
+        }
+    }
+
+    /// returns every component to the loop temperature
+    ///
+    /// in thermal hydraulics mode, the overrides are dropped but 
+    /// no temperatures are changed
+    pub fn clear_all_component_temperatures(&mut self){
+        self.component_temperature_overrides.clear();
+
+        if !self.thermal_hydraulics_enabled {
+            self.apply_fluid_temperatures();
+        }
+    }
+
+    /// returns the fluid temperature of the component with the 
+    /// given name, if there is one
+    pub fn get_component_temperature(
+        &self, 
+        component_name: &str) -> Option<ThermodynamicTemperature> {
+        return self.network.get_component_fluid_temperature(component_name);
+    }
+
+    fn apply_fluid_temperatures(&mut self){
+
+        self.network.set_uniform_fluid_temperature(self.loop_temperature);
+
+        for (component_name, component_temperature) in 
+            self.component_temperature_overrides.iter() {
+            self.network.set_component_fluid_temperature(
+                component_name, *component_temperature);
+        }
+    }
+
+    /// if enabled, step_transient advances the temperatures 
+    /// along with the flowrates
+    pub fn set_thermal_hydraulics_enabled(&mut self, 
+                                          thermal_hydraulics_enabled: bool){
+        self.thermal_hydraulics_enabled = thermal_hydraulics_enabled;
+    }
+
+    pub fn get_thermal_hydraulics_enabled(&self) -> bool {
+        return self.thermal_hydraulics_enabled;
+    }
+
+    /// sets the electrical power of the heater (watts)
+    ///
+    /// if point kinetics is enabled, the power is set by the 
+    /// kinetics instead the next time the temperatures advance
+    ///
+    /// if the heater has a wall, the power is generated in the 
+    /// wall and reaches the fluid through it, otherwise all of
+    /// it goes straight into the fluid
+    pub fn set_heater_power_watts(&mut self, heater_power_watts: f64){
+
+        let heater_branch = get_facility_branch_mut(&mut self.network, HEATER_BRANCH_INDEX);
+
+        if heater_branch.set_component_wall_heat_input_watts(
+            self.heater_index, heater_power_watts) {
+
+            heater_branch.set_component_heat_input_watts(self.heater_index, 0.0);
+            return;
+        }
+
+        heater_branch.set_component_heat_input_watts(
+            self.heater_index, 
+            heater_power_watts);
+    }
+
+    pub fn get_heater_power_watts(&self) -> f64 {
+        let heater_branch = self.get_heater_branch();
+
+        return heater_branch.get_component_heat_input_watts(self.heater_index) + 
+            heater_branch.get_component_wall_heat_input_watts(self.heater_index);
+    }
+
+    /// returns the version of the heater the facility was
+    /// constructed with
+    pub fn get_ciet_heater_version(&self) -> CietHeaterVersion {
+        return self.ciet_heater_version;
+    }
+
+    /// returns the name of the heater within the heater branch
+    pub fn get_heater_name(&self) -> &str {
+        return self.get_heater_branch().get_components()[self.heater_index].
+            get_name();
+    }
+
+    /// fills the loop with another working fluid, eg. FLiBe, to
+    /// compare against ciet's therminol results in the same 
+    /// geometry. The loop temperature should then be set within
+    /// the validated range of the new fluid
+    ///
+    /// the ctah heat exchanger model takes the heat capacity of
+    /// the new fluid too
+    pub fn set_fluid_properties(&mut self, 
+                                fluid_properties: Arc<dyn FluidProperties>){
+        self.network.set_fluid_properties(fluid_properties);
+    }
+
+    /// returns the working fluid in the loop
+    pub fn get_fluid_properties(&self) -> &dyn FluidProperties {
+        return self.network.get_fluid_properties();
+    }
+
+    /// sets the ambient (room) temperature that the insulated 
+    /// pipes and components lose heat to
+    pub fn set_ambient_temperature(&mut self, 
+                                   ambient_temperature: ThermodynamicTemperature){
+        self.network.set_ambient_temperature(ambient_temperature);
+    }
+
+    pub fn get_ambient_temperature(&self) -> ThermodynamicTemperature {
+        return self.network.get_ambient_temperature();
+    }
+
+    /// wraps every pipe and component in the loop in the same
+    /// insulation, by default nothing is insulated and no heat 
+    /// is lost
+    pub fn set_insulation(&mut self, insulation: InsulationParameters){
+        self.network.set_insulation(insulation);
+    }
+
+    /// wraps the component with the given name in insulation, 
+    /// returns false if there is no component by that name
+    pub fn set_component_insulation(
+        &mut self,
+        component_name: &str,
+        insulation: InsulationParameters) -> bool {
+        return self.network.set_component_insulation(component_name, insulation);
+    }
+
+    /// removes the insulation from every pipe and component
+    pub fn clear_insulation(&mut self){
+        self.network.clear_insulation();
+    }
+
+    /// gives every pipe and component in the loop a wall which 
+    /// stores heat, with the ciet pipe walls everywhere and a 
+    /// radially discretised wall for the heater
+    ///
+    /// by default there are no walls, so the fluid temperatures 
+    /// respond faster than they do in ciet
+    pub fn set_ciet_walls(&mut self){
+
+        let heater_power_watts = self.get_heater_power_watts();
+
+        self.network.set_wall(WallParameters::ciet_pipe_wall());
+        get_facility_branch_mut(&mut self.network, HEATER_BRANCH_INDEX).set_component_wall(
+            self.heater_index, WallParameters::ciet_heater_wall());
+
+        // the heater power now goes into the heater wall
+        self.set_heater_power_watts(heater_power_watts);
+    }
+
+    /// gives the component with the given name a wall, returns
+    /// false if there is no component by that name
+    pub fn set_component_wall(
+        &mut self,
+        component_name: &str,
+        wall_parameters: WallParameters) -> bool {
+
+        let heater_power_watts = self.get_heater_power_watts();
+
+        if !self.network.set_component_wall(component_name, wall_parameters) {
+            return false;
+        }
+
+        // in case this was the heater
+        self.set_heater_power_watts(heater_power_watts);
+        return true;
+    }
+
+    /// removes the walls from every pipe and component
+    pub fn clear_walls(&mut self){
+
+        let heater_power_watts = self.get_heater_power_watts();
+
+        self.network.clear_walls();
+
+        // the heater power goes back into the fluid
+        self.set_heater_power_watts(heater_power_watts);
+    }
+
+    /// returns the wall node temperatures of the component with
+    /// the given name from the inside outwards, if it has a wall
+    pub fn get_component_wall_temperatures(
+        &self,
+        component_name: &str) -> Option<Vec<ThermodynamicTemperature>> {
+        return self.network.get_component_wall_temperatures(component_name);
+    }
+
+    /// returns the heat lost to ambient (watts) from the 
+    /// component with the given name, if there is one
+    pub fn get_component_heat_loss_watts(&self, component_name: &str) -> Option<f64> {
+        return self.network.get_component_heat_loss_watts(component_name);
+    }
+
+    /// returns the heat lost to ambient (watts) from the whole 
+    /// loop at the current temperatures
+    pub fn get_heat_loss_watts(&self) -> f64 {
+        return self.network.get_heat_loss_watts();
+    }
+
+    /// advances the temperatures around the loop over a 
+    /// timestep at the current flowrates
+    /// (see ParallelBranchNetwork::advance_temperatures)
+    ///
+    /// step_transient already does this in thermal hydraulics 
+    /// mode, this is for when the flowrates come from calculate()
+    ///
+    /// the heat removed by the ctah is updated from its inlet 
+    /// temperature every MAX_TRANSIENT_SUBSTEP_SECONDS
+    pub fn advance_temperatures(&mut self, timestep: Duration){
+
+        let number_of_substeps: u32 = 
+            (timestep.as_secs_f64() / MAX_TRANSIENT_SUBSTEP_SECONDS).
+            ceil().max(1.0) as u32;
+
+        let substep = timestep / number_of_substeps;
+
+        for _ in 0..number_of_substeps {
+            self.update_ctah_heat_removal();
+            self.advance_point_kinetics(substep);
+            self.network.advance_temperatures(substep);
+        }
+    }
+
+    /// lets a point kinetics model of a reactor core drive the 
+    /// heater power, the core starts critical at the nominal 
+    /// power, and the temperature feedback is zero at the 
+    /// current heater temperatures
+    ///
+    /// the kinetics only advance along with the temperatures, 
+    /// ie. in thermal hydraulics mode or through 
+    /// advance_temperatures. set_heater_power_watts is 
+    /// overwritten every substep until point kinetics is 
+    /// disabled
+    pub fn enable_point_kinetics(&mut self, 
+                                 parameters: PointKineticsParameters,
+                                 nominal_power_watts: f64){
+
+        let point_kinetics = PointKinetics::new(
+            parameters, 
+            nominal_power_watts, 
+            self.get_heater_average_temperature(), 
+            self.get_heater_wall_average_temperature());
+
+        self.set_heater_power_watts(point_kinetics.get_power_watts());
+        self.point_kinetics = Some(point_kinetics);
+    }
+
+    /// hands the heater power back to the operator, the heater
+    /// stays at the last power from the kinetics
+    pub fn disable_point_kinetics(&mut self){
+        self.point_kinetics = None;
+    }
+
+    pub fn get_point_kinetics(&self) -> Option<&PointKinetics> {
+        return self.point_kinetics.as_ref();
+    }
+
+    /// for inserting reactivity or scramming the core
+    pub fn get_point_kinetics_mut(&mut self) -> Option<&mut PointKinetics> {
+        return self.point_kinetics.as_mut();
+    }
+
+    // advances the point kinetics (if enabled) with feedback 
+    // from the heater temperatures at the start of the timestep,
+    // and sets the heater power from it
+    fn advance_point_kinetics(&mut self, timestep: Duration){
+
+        let coolant_temperature = self.get_heater_average_temperature();
+        let fuel_temperature = self.get_heater_wall_average_temperature();
+
+        let heater_power_watts = match self.point_kinetics {
+            Some(ref mut point_kinetics) => {
+                point_kinetics.advance(coolant_temperature, fuel_temperature, timestep);
+                point_kinetics.get_power_watts()
+            },
+            None => return,
+        };
+
+        self.set_heater_power_watts(heater_power_watts);
+    }
+
+    /// returns the mean of the heater inlet and outlet 
+    /// temperatures
+    pub fn get_heater_average_temperature(&self) -> ThermodynamicTemperature {
+        let average_temperature_degrees_c = 0.5 * (
+            get_temperature_degrees_c(self.get_heater_inlet_temperature()) + 
+            get_temperature_degrees_c(self.get_heater_outlet_temperature()));
+
+        return ThermodynamicTemperature::new::<degree_celsius>(
+            average_temperature_degrees_c);
+    }
+
+    // mean temperature of the heater wall nodes, this stands in
+    // for the fuel temperature. Without a wall, the fluid 
+    // temperature in the heater is used
+    fn get_heater_wall_average_temperature(&self) -> ThermodynamicTemperature {
+
+        let heater = &self.get_heater_branch().get_components()[self.heater_index];
+
+        match heater.get_wall() {
+            Some(wall) => {
+                let node_temperatures = wall.get_node_temperatures();
+
+                let average_temperature_degrees_c: f64 = node_temperatures.iter().
+                    map(|node_temperature| get_temperature_degrees_c(*node_temperature)).
+                    sum::<f64>() / node_temperatures.len() as f64;
+
+                return ThermodynamicTemperature::new::<degree_celsius>(
+                    average_temperature_degrees_c);
+            },
+            None => return heater.get_fluid_temperature(),
+        }
+    }
+
+    /// sets the ctah fan speed (rpm), the faster the fan, the 
+    /// more heat the ctah removes
+    pub fn set_ctah_fan_speed_rpm(&mut self, fan_speed_rpm: f64){
+        self.ctah_heat_exchanger.set_fan_speed_rpm(fan_speed_rpm);
+    }
+
+    pub fn get_ctah_fan_speed_rpm(&self) -> f64 {
+        return self.ctah_heat_exchanger.get_fan_speed_rpm();
+    }
+
+    /// sets the temperature of the air drawn into the ctah
+    pub fn set_ctah_air_inlet_temperature(
+        &mut self, 
+        air_inlet_temperature: ThermodynamicTemperature){
+        self.ctah_heat_exchanger.set_air_inlet_temperature(air_inlet_temperature);
+    }
+
+    pub fn get_ctah_heat_exchanger(&self) -> &CTAHHeatExchanger {
+        return &self.ctah_heat_exchanger;
+    }
+
+    /// returns the heat the ctah removes from the oil (watts) at
+    /// the current ctah inlet temperature and flowrate
+    pub fn get_ctah_heat_removal_watts(&self) -> f64 {
+        return self.ctah_heat_exchanger.get_heat_removal_watts(
+            self.get_fluid_properties(),
+            self.get_ctah_inlet_temperature(), 
+            self.get_ctah_branch_mass_flowrate());
+    }
+
+    // the heat removed by the ctah is split between its 
+    // components by length
+    fn update_ctah_heat_removal(&mut self){
+
+        let heat_removal_watts = self.get_ctah_heat_removal_watts();
+
+        let ctah_component_indices = self.ctah_first_index..=self.ctah_last_index;
+
+        let ctah_branch = get_facility_branch_mut(&mut self.network, CTAH_BRANCH_INDEX);
+
+        let component_lengths: Vec<f64> = ctah_component_indices.clone().
+            map(|component_index| ctah_branch.get_components()[component_index].
+                as_fluid_component().get_component_length_immutable().value).
+            collect();
+
+        let total_length: f64 = component_lengths.iter().sum();
+
+        for (component_index, component_length) in 
+            ctah_component_indices.zip(component_lengths.iter()) {
+
+            ctah_branch.set_component_heat_input_watts(
+                component_index, 
+                -heat_removal_watts * component_length / total_length);
+        }
+    }
+
+    /// returns the temperature of the fluid entering the heater
+    pub fn get_heater_inlet_temperature(&self) -> ThermodynamicTemperature {
+        return get_facility_component_inlet_outlet_temperatures(
+            &self.network, HEATER_BRANCH_INDEX, self.heater_index, self.heater_index).0;
+    }
+
+    /// returns the temperature of the fluid leaving the heater
+    pub fn get_heater_outlet_temperature(&self) -> ThermodynamicTemperature {
+        return get_facility_component_inlet_outlet_temperatures(
+            &self.network, HEATER_BRANCH_INDEX, self.heater_index, self.heater_index).1;
+    }
+
+    /// returns the temperature of the fluid entering the ctah
+    pub fn get_ctah_inlet_temperature(&self) -> ThermodynamicTemperature {
+        return get_facility_component_inlet_outlet_temperatures(
+            &self.network, CTAH_BRANCH_INDEX, self.ctah_first_index, self.ctah_last_index).0;
+    }
+
+    /// returns the temperature of the fluid leaving the ctah
+    pub fn get_ctah_outlet_temperature(&self) -> ThermodynamicTemperature {
+        return get_facility_component_inlet_outlet_temperatures(
+            &self.network, CTAH_BRANCH_INDEX, self.ctah_first_index, self.ctah_last_index).1;
+    }
+
+    /// sets the heat taken out of the primary fluid in the dhx 
+    /// shell side (watts), eg. by the DRACS loop
+    pub fn set_dhx_heat_removal_watts(&mut self, heat_removal_watts: f64){
+        get_facility_branch_mut(&mut self.network, DHX_BRANCH_INDEX).
+            set_component_heat_input_watts(
+                self.dhx_shell_side_index, 
+                -heat_removal_watts);
+    }
+
+    pub fn get_dhx_heat_removal_watts(&self) -> f64 {
+        return -self.get_dhx_branch().
+            get_component_heat_input_watts(self.dhx_shell_side_index);
+    }
+
+    /// returns the temperature of the primary fluid entering 
+    /// the dhx shell side
+    pub fn get_dhx_shell_side_inlet_temperature(&self) -> ThermodynamicTemperature {
+        return get_facility_component_inlet_outlet_temperatures(
+            &self.network, DHX_BRANCH_INDEX, self.dhx_shell_side_index, self.dhx_shell_side_index).0;
+    }
+
+    /// returns the temperature of the primary fluid leaving 
+    /// the dhx shell side
+    pub fn get_dhx_shell_side_outlet_temperature(&self) -> ThermodynamicTemperature {
+        return get_facility_component_inlet_outlet_temperatures(
+            &self.network, DHX_BRANCH_INDEX, self.dhx_shell_side_index, self.dhx_shell_side_index).1;
+    }
+
+    /// returns the buoyancy head driving fluid up the heater 
+    /// branch and down the dhx branch
+    ///
+    /// this is the hydrostatic pressure change down the dhx 
+    /// branch less that down the heater branch. It is positive 
+    /// when the heater branch is hotter, which is what drives 
+    /// natural circulation when the ctah pump is off
+    pub fn get_natural_circulation_driving_head(&self) -> Pressure {
+        return self.get_dhx_branch().get_hydrostatic_pressure_change() 
+            - self.get_heater_branch().get_hydrostatic_pressure_change();
+    }
+
+    /// returns true if the dhx branch check valve was open after
+    /// the last calculation
+    pub fn get_dhx_check_valve_open(&self) -> bool {
+        return self.get_dhx_branch().get_check_valve_states().iter().
+            all(|(_check_valve_name, open)| *open);
+    }
+
+    /// returns the name and open (true) or closed (false) state
+    /// of every check valve in ciet
+    pub fn get_check_valve_states(&self) -> Vec<(String, bool)> {
+        return self.network.get_check_valve_states();
+    }
+
+    /// returns the parallel branch network holding the heater,
+    /// dhx and ctah branches
+    pub fn get_network(&self) -> &ParallelBranchNetwork {
+        return &self.network;
+    }
+
+    /// if enabled (the default), every solve after the first one
+    /// starts from the previous operating point
+    pub fn set_warm_start_enabled(&mut self, warm_start_enabled: bool){
+        self.network.set_warm_start_enabled(warm_start_enabled);
+    }
+
+    pub fn get_warm_start_enabled(&self) -> bool {
+        return self.network.get_warm_start_enabled();
+    }
+
+    /// forgets the previous operating point, so the next solve
+    /// starts from the default brackets
+    pub fn reset_warm_start(&mut self){
+        self.network.reset_warm_start();
+    }
+
+    /// returns the iteration counts of the last successful solve
+    pub fn get_last_iteration_counts(&self) -> CIETSolverIterationCounts {
+        return self.network.get_last_iteration_counts();
+    }
+
+    /// returns the iteration counts of the most recent cold start,
+    /// this is the baseline which warm starts are compared against
+    pub fn get_cold_start_iteration_counts(&self) -> 
+        Option<CIETSolverIterationCounts> {
+        return self.network.get_cold_start_iteration_counts();
+    }
+
+    /// sets what calculate and step_transient do when a 
+    /// component's temperature leaves the validated range of 
+    /// the therminol property correlations
+    /// (see ParallelBranchNetwork::set_temperature_range_policy)
+    pub fn set_temperature_range_policy(
+        &mut self, 
+        temperature_range_policy: TemperatureRangePolicy){
+        self.network.set_temperature_range_policy(temperature_range_policy);
+    }
+
+    pub fn get_temperature_range_policy(&self) -> TemperatureRangePolicy {
+        return self.network.get_temperature_range_policy();
+    }
+
+    /// returns a TemperatureOutOfRange error for every component
+    /// outside the validated temperature range at the last check
+    pub fn get_temperature_range_violations(&self) -> &Vec<CIETSolverError> {
+        return self.network.get_temperature_range_violations();
+    }
+
+    /// returns how many times a component has left the 
+    /// validated temperature range
+    pub fn get_out_of_range_temperature_events(&self) -> usize {
+        return self.network.get_out_of_range_temperature_events();
+    }
+
+    pub fn reset_out_of_range_temperature_events(&mut self){
+        self.network.reset_out_of_range_temperature_events();
+    }
+
+    /// returns manometer readings at every junction between 
+    /// components in the heater, dhx and ctah branches
+    /// (in that order)
+    ///
+    /// the readings are gauge pressures relative to the
+    /// reference node chosen by the user
+    pub fn get_manometer_readings(
+        &self,
+        reference: ManometerReference) -> Vec<ManometerReading> {
+        return self.network.get_manometer_readings(reference);
+    }
+
+    /// returns labels for all the manometer readings in the
+    /// same order as get_manometer_readings
+    pub fn get_manometer_tap_labels(&self) -> Vec<String> {
+        return self.network.get_manometer_tap_labels();
+    }
+
+    /// returns the flow conditions and geometry of every 
+    /// component in every branch at the last calculated 
+    /// flowrates
+    pub fn get_component_readings(&self) -> Vec<ComponentReading> {
+        return self.network.get_component_readings();
+    }
+
+    /// returns the time simulated by step_transient so far
+    pub fn get_simulation_time(&self) -> Duration {
+        return self.network.get_simulation_time();
+    }
+
+    /// sets all branch flowrates to zero and the simulation time 
+    /// back to zero, so a transient can start from rest
+    pub fn reset_to_rest(&mut self){
+        let zero_flowrate = MassRate::new::<kilogram_per_second>(0.0);
+        self.network.set_branch_mass_flowrates(
+            vec![zero_flowrate; self.network.get_branches().len()]).
+            expect("there is one flowrate per branch");
+        self.network.reset_simulation_time();
+    }
+
+    /// advances the flowrates in all three branches over a 
+    /// timestep, accounting for the inertia of the fluid 
+    /// (see ParallelBranchNetwork::step_transient)
+    ///
+    /// in thermal hydraulics mode, the temperatures are then 
+    /// advanced at the new flowrates
+    ///
+    /// returns the calculation time and the ctah, heater and dhx
+    /// branch flowrates (in that order), same as calculate()
+    pub fn step_transient(&mut self, timestep: Duration) -> 
+        Result<(Duration,MassRate,MassRate,MassRate), CIETSolverError>
+        {
+            let start = Instant::now();
+
+            let branch_mass_flowrates = 
+                self.network.step_transient(timestep)?;
+
+            if self.thermal_hydraulics_enabled {
+                self.advance_temperatures(timestep);
+            }
+
+            let elapsed_time: Duration= start.elapsed();
+
+            return Ok((elapsed_time,
+                    branch_mass_flowrates[CTAH_BRANCH_INDEX],
+                    branch_mass_flowrates[HEATER_BRANCH_INDEX],
+                    branch_mass_flowrates[DHX_BRANCH_INDEX]));
+        }
+
+    /// calculates the flowrates in all three branches given the
+    /// current ctah pump pressure
+    ///
+    /// returns the calculation time and the ctah, heater and dhx
+    /// branch flowrates (in that order), same as 
+    /// CIETIsothermalFacility
+    ///
+    /// returns a CIETSolverError if the header pressure change
+    /// or any of the branch flowrates cannot be found
+    ///
+    /// if warm starts are enabled and a previous solve converged, 
+    /// the brackets are centred on the previous header pressure
+    /// change and branch flowrates. They still expand if the 
+    /// operating point moved further than that
+    pub fn calculate(&mut self) -> 
+        Result<(Duration,MassRate,MassRate,MassRate), CIETSolverError>
+        {
+            // start the timer
+            let start = Instant::now();
+
+            let branch_mass_flowrates = self.network.solve()?;
+
+            // now that i've gotten all the calculations, i can return the
+            // elapsed time to the environment
+
+            let elapsed_time: Duration= start.elapsed();
+
+            return Ok((elapsed_time,
+                    branch_mass_flowrates[CTAH_BRANCH_INDEX],
+                    branch_mass_flowrates[HEATER_BRANCH_INDEX],
+                    branch_mass_flowrates[DHX_BRANCH_INDEX]));
+
+        }
+
+}
@@ -0,0 +1,436 @@
+// This application was developed for use in my PhD thesis under supervision 
+// of Professor Per F. Peterson. It is part of a thermal hydraulics
+// library in Rust that is released under the GNU General Public License
+// v 3.0. This is partly due to the fact that some of the libraries 
+// inherit from GeN-Foam and OpenFOAM, both licensed under GNU General
+// Public License v3.0.
+//
+// As such, the entire library is released under GNU GPL v3.0. It is a strong 
+// copyleft license which means you cannot use it in proprietary software.
+//
+//
+// License
+//    This application is part ciet_digital_twin_rust which uses
+//    fluid_mechanics_rust, a partial library of the
+//    thermal hydraulics library written in rust meant to help with the
+//    fluid mechanics aspects of the calculations
+//     
+//    Copyright (C) 2022-2023  Theodore Kay Chen Ong, Singapore Nuclear
+//    Research and Safety Initiative, Per F. Peterson, University of 
+//    California, Berkeley Thermal Hydraulics Laboratory
+//
+//    ciet_digital_twin_rust is free software; you can redistribute it and/or modify it
+//    under the terms of the GNU General Public License as published by the
+//    Free Software Foundation; either version 2 of the License, or (at your
+//    option) any later version.
+//
+//    ciet_digital_twin_rust is distributed in the hope that it will be useful, but WITHOUT
+//    ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or
+//    FITNESS FOR A PARTICULAR PURPOSE.  See the GNU General Public License
+//    for more details.
+//
+//    ciet_digital_twin_rust relies on a thermal hydraulics library
+//    also written in rust. The isothermal digital twin in particular
+//    relies on fluid_mechanics_rust.
+//    This library is part of a thermal hydraulics library in rust
+//    and contains some code copied from GeN-Foam, and OpenFOAM derivative.
+//    This offering is not approved or endorsed by the OpenFOAM Foundation nor
+//    OpenCFD Limited, producer and distributor of the OpenFOAM(R)software via
+//    www.openfoam.com, and owner of the OPENFOAM(R) and OpenCFD(R) trademarks.
+//    Nor is it endorsed by the authors and owners of GeN-Foam.
+//
+//    You should have received a copy of the GNU General Public License
+//    along with this program.  If not, see <http://www.gnu.org/licenses/>.
+//
+// © All rights reserved. Theodore Kay Chen Ong,
+// Singapore Nuclear Research and Safety Initiative,
+// Per F. Peterson,
+// University of California, Berkeley Thermal Hydraulics Laboratory
+//
+// Main author of the code: Theodore Kay Chen Ong, supervised by
+// Professor Per F. Peterson
+extern crate fluid_mechanics_rust;
+use fluid_mechanics_rust::prelude::*;
+
+use std::time::{Instant, Duration};
+use crate::{DRACSHotBranch, DRACSColdBranch};
+use crate::branch::TherminolBranch;
+use crate::manometer::*;
+use crate::component_readings::ComponentReading;
+use crate::parallel_branch_network::{ParallelBranchNetwork, 
+    MAX_TRANSIENT_SUBSTEP_SECONDS};
+use crate::ctah_heat_exchanger::CTAHHeatExchanger;
+use crate::insulation::InsulationParameters;
+use crate::fluid_properties::FluidProperties;
+
+use std::sync::Arc;
+use crate::ciet_solver::{CIETSolverError, CIETSolverIterationCounts};
+use crate::facility_branches::*;
+
+/// position of the hot leg within the DRACS network
+pub const DRACS_HOT_BRANCH_INDEX: usize = 0;
+
+/// position of the cold leg within the DRACS network
+pub const DRACS_COLD_BRANCH_INDEX: usize = 1;
+
+/// This is a struct representing the DRACS loop (the secondary
+/// natural circulation loop which takes decay heat from the 
+/// primary loop through the DHX and rejects it at the TCHX)
+///
+/// The loop is a hot leg and a cold leg joined at the top and 
+/// the bottom of the loop. These are held in a 
+/// ParallelBranchNetwork like the primary loop branches, with
+/// both legs oriented from top to bottom. So the loop 
+/// flowrate is the cold leg flowrate, and the hot leg carries 
+/// the same flow the other way
+///
+/// The flow can be driven by:
+///
+/// 1. a specified driving head, which acts like a pump in the 
+/// cold leg, pushing fluid down it
+/// 2. a hot leg hotter than the cold leg, which gives a 
+/// hydrostatic (buoyancy) head
+///
+/// With neither, there is no flow
+///
+/// in thermal hydraulics mode, heat put into the DHX tube side
+/// (eg. from the primary loop) is carried around the loop and
+/// removed by the TCHX, so the leg temperatures, and therefore 
+/// the buoyancy head, come out of the energy equation instead
+pub struct OwnedDRACSFacility {
+
+    driving_head: Pressure,
+
+    // hot leg and cold leg in that order
+    network: ParallelBranchNetwork,
+
+    // position of the pipe in the cold leg which carries the 
+    // driving head
+    driving_head_component_index: usize,
+
+    // if true, step_transient also advances the temperatures
+    thermal_hydraulics_enabled: bool,
+
+    // position of the dhx tube side within the hot leg, and of
+    // the tchx within the cold leg
+    dhx_tube_side_index: usize,
+    tchx_index: usize,
+
+    // air side of the tchx, which is also a fan cooled coil
+    tchx_heat_exchanger: CTAHHeatExchanger,
+}
+
+impl OwnedDRACSFacility {
+
+    /// constructor, builds every component in the DRACS loop once
+    pub fn new() -> Self {
+
+        let hot_branch = DRACSHotBranch::new().get_owned_branch();
+        let cold_branch = DRACSColdBranch::new().get_owned_branch();
+
+        let driving_head_component_index = cold_branch.
+            get_component_index("pipe_37").unwrap();
+
+        let dhx_tube_side_index = hot_branch.
+            get_component_index("dhx_tube_side_label_30b").unwrap();
+        let tchx_index = cold_branch.
+            get_component_index("tchx_label_35").unwrap();
+
+        let network = ParallelBranchNetwork::new(
+            vec![hot_branch, cold_branch]);
+
+        return Self { 
+            driving_head: Pressure::new::<pascal>(0.0), 
+            network: network, 
+            driving_head_component_index: driving_head_component_index,
+            thermal_hydraulics_enabled: false,
+            dhx_tube_side_index: dhx_tube_side_index,
+            tchx_index: tchx_index,
+            tchx_heat_exchanger: CTAHHeatExchanger::ciet_tchx(),
+        };
+    }
+
+    /// sets the driving head in place, positive values push
+    /// fluid down the cold leg and up the hot leg, which is the 
+    /// natural circulation direction
+    pub fn set_driving_head(&mut self, driving_head: Pressure){
+
+        self.driving_head = driving_head;
+        get_facility_branch_mut(&mut self.network, DRACS_COLD_BRANCH_INDEX).
+            set_component_internal_pressure_source(
+                self.driving_head_component_index, 
+                driving_head);
+    }
+
+    pub fn get_driving_head(&self) -> Pressure {
+        return self.driving_head;
+    }
+
+    /// sets every component in the hot leg to one temperature
+    pub fn set_hot_branch_temperature(&mut self, 
+                                      hot_branch_temperature: ThermodynamicTemperature){
+        get_facility_branch_mut(&mut self.network, DRACS_HOT_BRANCH_INDEX).
+            set_fluid_temperature(hot_branch_temperature);
+    }
+
+    /// sets every component in the cold leg to one temperature
+    pub fn set_cold_branch_temperature(&mut self, 
+                                       cold_branch_temperature: ThermodynamicTemperature){
+        get_facility_branch_mut(&mut self.network, DRACS_COLD_BRANCH_INDEX).
+            set_fluid_temperature(cold_branch_temperature);
+    }
+
+    /// sets every component in the loop to one temperature
+    ///
+    /// in thermal hydraulics mode, this resets the temperatures
+    /// the transient starts from
+    pub fn set_loop_temperature(&mut self, 
+                                loop_temperature: ThermodynamicTemperature){
+        self.network.set_uniform_fluid_temperature(loop_temperature);
+    }
+
+    /// if enabled, step_transient advances the temperatures 
+    /// along with the flowrate
+    pub fn set_thermal_hydraulics_enabled(&mut self, 
+                                          thermal_hydraulics_enabled: bool){
+        self.thermal_hydraulics_enabled = thermal_hydraulics_enabled;
+    }
+
+    pub fn get_thermal_hydraulics_enabled(&self) -> bool {
+        return self.thermal_hydraulics_enabled;
+    }
+
+    /// sets the heat put into the DRACS fluid in the dhx tube 
+    /// side (watts)
+    pub fn set_dhx_heat_input_watts(&mut self, heat_input_watts: f64){
+        get_facility_branch_mut(&mut self.network, DRACS_HOT_BRANCH_INDEX).
+            set_component_heat_input_watts(
+                self.dhx_tube_side_index, 
+                heat_input_watts);
+    }
+
+    pub fn get_dhx_heat_input_watts(&self) -> f64 {
+        return self.get_hot_branch().
+            get_component_heat_input_watts(self.dhx_tube_side_index);
+    }
+
+    /// returns the temperature of the DRACS fluid entering the 
+    /// dhx tube side
+    pub fn get_dhx_tube_side_inlet_temperature(&self) -> ThermodynamicTemperature {
+        return get_facility_component_inlet_outlet_temperatures(
+            &self.network, DRACS_HOT_BRANCH_INDEX, self.dhx_tube_side_index, self.dhx_tube_side_index).0;
+    }
+
+    /// returns the temperature of the DRACS fluid leaving the 
+    /// dhx tube side
+    pub fn get_dhx_tube_side_outlet_temperature(&self) -> ThermodynamicTemperature {
+        return get_facility_component_inlet_outlet_temperatures(
+            &self.network, DRACS_HOT_BRANCH_INDEX, self.dhx_tube_side_index, self.dhx_tube_side_index).1;
+    }
+
+    /// returns the DRACS flowrate through the dhx tube side
+    pub fn get_dhx_tube_side_mass_flowrate(&self) -> MassRate {
+        return get_facility_branch_mass_flowrate(&self.network, DRACS_HOT_BRANCH_INDEX);
+    }
+
+    /// sets the tchx fan speed (rpm)
+    pub fn set_tchx_fan_speed_rpm(&mut self, fan_speed_rpm: f64){
+        self.tchx_heat_exchanger.set_fan_speed_rpm(fan_speed_rpm);
+    }
+
+    pub fn get_tchx_fan_speed_rpm(&self) -> f64 {
+        return self.tchx_heat_exchanger.get_fan_speed_rpm();
+    }
+
+    /// returns the temperature of the fluid entering the tchx
+    pub fn get_tchx_inlet_temperature(&self) -> ThermodynamicTemperature {
+        return get_facility_component_inlet_outlet_temperatures(
+            &self.network, DRACS_COLD_BRANCH_INDEX, self.tchx_index, self.tchx_index).0;
+    }
+
+    /// returns the temperature of the fluid leaving the tchx
+    pub fn get_tchx_outlet_temperature(&self) -> ThermodynamicTemperature {
+        return get_facility_component_inlet_outlet_temperatures(
+            &self.network, DRACS_COLD_BRANCH_INDEX, self.tchx_index, self.tchx_index).1;
+    }
+
+    /// returns the heat the tchx removes (watts) at the current 
+    /// tchx inlet temperature and loop flowrate
+    pub fn get_tchx_heat_removal_watts(&self) -> f64 {
+        return self.tchx_heat_exchanger.get_heat_removal_watts(
+            self.get_fluid_properties(),
+            self.get_tchx_inlet_temperature(), 
+            self.get_loop_mass_flowrate());
+    }
+
+    /// fills the loop with another working fluid, by default it 
+    /// is therminol VP-1
+    pub fn set_fluid_properties(&mut self, 
+                                fluid_properties: Arc<dyn FluidProperties>){
+        self.network.set_fluid_properties(fluid_properties);
+    }
+
+    /// returns the working fluid in the loop
+    pub fn get_fluid_properties(&self) -> &dyn FluidProperties {
+        return self.network.get_fluid_properties();
+    }
+
+    /// sets the ambient (room) temperature that the insulated 
+    /// pipes and components lose heat to
+    pub fn set_ambient_temperature(&mut self, 
+                                   ambient_temperature: ThermodynamicTemperature){
+        self.network.set_ambient_temperature(ambient_temperature);
+    }
+
+    pub fn get_ambient_temperature(&self) -> ThermodynamicTemperature {
+        return self.network.get_ambient_temperature();
+    }
+
+    /// wraps every pipe and component in the loop in the same
+    /// insulation, by default nothing is insulated
+    pub fn set_insulation(&mut self, insulation: InsulationParameters){
+        self.network.set_insulation(insulation);
+    }
+
+    /// returns the heat lost to ambient (watts) from the whole 
+    /// loop at the current temperatures
+    pub fn get_heat_loss_watts(&self) -> f64 {
+        return self.network.get_heat_loss_watts();
+    }
+
+    /// advances the temperatures around the loop over a 
+    /// timestep at the current flowrate
+    ///
+    /// the heat removed by the tchx is updated from its inlet 
+    /// temperature every MAX_TRANSIENT_SUBSTEP_SECONDS
+    pub fn advance_temperatures(&mut self, timestep: Duration){
+
+        let number_of_substeps: u32 = 
+            (timestep.as_secs_f64() / MAX_TRANSIENT_SUBSTEP_SECONDS).
+            ceil().max(1.0) as u32;
+
+        let substep = timestep / number_of_substeps;
+
+        for _ in 0..number_of_substeps {
+
+            let tchx_heat_removal_watts = self.get_tchx_heat_removal_watts();
+
+            get_facility_branch_mut(&mut self.network, DRACS_COLD_BRANCH_INDEX).
+                set_component_heat_input_watts(
+                    self.tchx_index, 
+                    -tchx_heat_removal_watts);
+
+            self.network.advance_temperatures(substep);
+        }
+    }
+
+    /// returns the buoyancy head driving fluid up the hot leg 
+    /// and down the cold leg
+    ///
+    /// this is the hydrostatic pressure change down the cold 
+    /// leg less that down the hot leg, it is positive when the 
+    /// hot leg is hotter
+    pub fn get_natural_circulation_driving_head(&self) -> Pressure {
+        return self.get_cold_branch().get_hydrostatic_pressure_change() 
+            - self.get_hot_branch().get_hydrostatic_pressure_change();
+    }
+
+    /// returns the DRACS loop flowrate, positive in the natural
+    /// circulation direction (down the cold leg, up the hot leg)
+    pub fn get_loop_mass_flowrate(&self) -> MassRate {
+        return get_facility_branch_mass_flowrate(&self.network, DRACS_COLD_BRANCH_INDEX);
+    }
+
+    pub fn get_hot_branch(&self) -> &TherminolBranch {
+        return get_facility_branch(&self.network, DRACS_HOT_BRANCH_INDEX);
+    }
+
+    pub fn get_cold_branch(&self) -> &TherminolBranch {
+        return get_facility_branch(&self.network, DRACS_COLD_BRANCH_INDEX);
+    }
+
+    pub fn get_network(&self) -> &ParallelBranchNetwork {
+        return &self.network;
+    }
+
+    /// returns the iteration counts of the last successful solve
+    pub fn get_last_iteration_counts(&self) -> CIETSolverIterationCounts {
+        return self.network.get_last_iteration_counts();
+    }
+
+    /// returns manometer readings at every junction between 
+    /// components in the hot and cold legs (in that order)
+    pub fn get_manometer_readings(
+        &self,
+        reference: ManometerReference) -> Vec<ManometerReading> {
+        return self.network.get_manometer_readings(reference);
+    }
+
+    /// returns labels for all the manometer readings in the
+    /// same order as get_manometer_readings
+    pub fn get_manometer_tap_labels(&self) -> Vec<String> {
+        return self.network.get_manometer_tap_labels();
+    }
+
+    /// returns the flow conditions and geometry of every 
+    /// component in every branch at the last calculated 
+    /// flowrates
+    pub fn get_component_readings(&self) -> Vec<ComponentReading> {
+        return self.network.get_component_readings();
+    }
+
+    /// returns the time simulated by step_transient so far
+    pub fn get_simulation_time(&self) -> Duration {
+        return self.network.get_simulation_time();
+    }
+
+    /// sets the loop flowrate to zero and the simulation time 
+    /// back to zero, so a transient can start from rest
+    pub fn reset_to_rest(&mut self){
+        let zero_flowrate = MassRate::new::<kilogram_per_second>(0.0);
+        self.network.set_branch_mass_flowrates(
+            vec![zero_flowrate; self.network.get_branches().len()]).
+            expect("there is one flowrate per branch");
+        self.network.reset_simulation_time();
+    }
+
+    /// advances the loop flowrate over a timestep, accounting 
+    /// for the inertia of the fluid 
+    /// (see ParallelBranchNetwork::step_transient)
+    ///
+    /// in thermal hydraulics mode, the temperatures are then 
+    /// advanced at the new flowrate
+    ///
+    /// returns the calculation time and the loop flowrate
+    pub fn step_transient(&mut self, timestep: Duration) -> 
+        Result<(Duration,MassRate), CIETSolverError>
+        {
+            let start = Instant::now();
+
+            self.network.step_transient(timestep)?;
+
+            if self.thermal_hydraulics_enabled {
+                self.advance_temperatures(timestep);
+            }
+
+            let elapsed_time: Duration= start.elapsed();
+
+            return Ok((elapsed_time, self.get_loop_mass_flowrate()));
+        }
+
+    /// calculates the loop flowrate given the driving head and
+    /// the hot and cold leg temperatures
+    ///
+    /// returns the calculation time and the loop flowrate
+    pub fn calculate(&mut self) -> 
+        Result<(Duration,MassRate), CIETSolverError>
+        {
+            let start = Instant::now();
+
+            self.network.solve()?;
+
+            let elapsed_time: Duration= start.elapsed();
+
+            return Ok((elapsed_time, self.get_loop_mass_flowrate()));
+        }
+}
@@ -0,0 +1,1090 @@
+// This application was developed for use in my PhD thesis under supervision 
+// of Professor Per F. Peterson. It is part of a thermal hydraulics
+// library in Rust that is released under the GNU General Public License
+// v 3.0. This is partly due to the fact that some of the libraries 
+// inherit from GeN-Foam and OpenFOAM, both licensed under GNU General
+// Public License v3.0.
+//
+// As such, the entire library is released under GNU GPL v3.0. It is a strong 
+// copyleft license which means you cannot use it in proprietary software.
+//
+//
+// License
+//    This application is part ciet_digital_twin_rust which uses
+//    fluid_mechanics_rust, a partial library of the
+//    thermal hydraulics library written in rust meant to help with the
+//    fluid mechanics aspects of the calculations
+//     
+//    Copyright (C) 2022-2023  Theodore Kay Chen Ong, Singapore Nuclear
+//    Research and Safety Initiative, Per F. Peterson, University of 
+//    California, Berkeley Thermal Hydraulics Laboratory
+//
+//    ciet_digital_twin_rust is free software; you can redistribute it and/or modify it
+//    under the terms of the GNU General Public License as published by the
+//    Free Software Foundation; either version 2 of the License, or (at your
+//    option) any later version.
+//
+//    ciet_digital_twin_rust is distributed in the hope that it will be useful, but WITHOUT
+//    ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or
+//    FITNESS FOR A PARTICULAR PURPOSE.  See the GNU General Public License
+//    for more details.
+//
+//    ciet_digital_twin_rust relies on a thermal hydraulics library
+//    also written in rust. The isothermal digital twin in particular
+//    relies on fluid_mechanics_rust.
+//    This library is part of a thermal hydraulics library in rust
+//    and contains some code copied from GeN-Foam, and OpenFOAM derivative.
+//    This offering is not approved or endorsed by the OpenFOAM Foundation nor
+//    OpenCFD Limited, producer and distributor of the OpenFOAM(R)software via
+//    www.openfoam.com, and owner of the OPENFOAM(R) and OpenCFD(R) trademarks.
+//    Nor is it endorsed by the authors and owners of GeN-Foam.
+//
+//    You should have received a copy of the GNU General Public License
+//    along with this program.  If not, see <http://www.gnu.org/licenses/>.
+//
+// © All rights reserved. Theodore Kay Chen Ong,
+// Singapore Nuclear Research and Safety Initiative,
+// Per F. Peterson,
+// University of California, Berkeley Thermal Hydraulics Laboratory
+//
+// Main author of the code: Theodore Kay Chen Ong, supervised by
+// Professor Per F. Peterson
+extern crate fluid_mechanics_rust;
+use fluid_mechanics_rust::prelude::*;
+
+use std::cell::{Cell, RefCell};
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::branch::TherminolBranch;
+use crate::manometer::*;
+use crate::component_readings::ComponentReading;
+use crate::energy_equation::get_temperature_degrees_c;
+use crate::insulation::InsulationParameters;
+use crate::pipe_wall::WallParameters;
+use crate::fluid_properties::{FluidProperties, TherminolVP1};
+use crate::ciet_solver::{CIETSolverError, CIETSolverIterationCounts, 
+    TemperatureRangePolicy, find_root_brent_expanding};
+
+/// half width of the header pressure change bracket used for 
+/// warm starts, centred on the previous header pressure change
+pub const WARM_START_HEADER_PRESSURE_BRACKET_PA: f64 = 500.0;
+
+/// half width of the branch flowrate bracket used for warm starts,
+/// centred on the previous branch flowrate
+pub const WARM_START_BRANCH_FLOWRATE_BRACKET_KG_PER_S: f64 = 0.05;
+
+/// longest timestep used within step_transient, longer steps 
+/// are split into substeps no longer than this
+pub const MAX_TRANSIENT_SUBSTEP_SECONDS: f64 = 0.05;
+
+/// mass flowrate perturbation used to estimate the slope of each
+/// branch pressure change curve in transients
+pub const BRANCH_SLOPE_PERTURBATION_KG_PER_S: f64 = 1.0e-6;
+
+/// any number of branches in parallel, joined at a common top 
+/// header and a common bottom header
+///
+/// every branch runs from the top header to the bottom header, 
+/// so they all see the same pressure change. The network 
+/// finds the header pressure change where the net flow into 
+/// the headers is zero
+///
+/// CIET (heater, dhx and ctah branches) is one example, but 
+/// you can add more branches (eg. a bypass line) without 
+/// writing a new facility
+pub struct ParallelBranchNetwork {
+
+    branches: Vec<TherminolBranch>,
+    branch_mass_flowrates: Vec<MassRate>,
+
+    // pressure change from top header to bottom header
+    header_pressure_change: Pressure,
+
+    // if true, solve() starts from the last converged 
+    // header pressure change and branch flowrates
+    warm_start_enabled: bool,
+    has_converged_solution: bool,
+
+    last_iteration_counts: CIETSolverIterationCounts,
+    cold_start_iteration_counts: Option<CIETSolverIterationCounts>,
+
+    // time simulated by step_transient so far
+    simulation_time: Duration,
+
+    // temperatures of the fluid mixed in each header
+    top_header_temperature: ThermodynamicTemperature,
+    bottom_header_temperature: ThermodynamicTemperature,
+
+    // temperature of the room that insulated components lose 
+    // heat to
+    ambient_temperature: ThermodynamicTemperature,
+
+    // what to do when a component leaves the validated 
+    // temperature range, the components out of it as of the 
+    // last check, and how many times a component has left it
+    temperature_range_policy: TemperatureRangePolicy,
+    temperature_range_violations: Vec<CIETSolverError>,
+    out_of_range_temperature_events: usize,
+}
+
+impl ParallelBranchNetwork {
+
+    /// constructor, the branches must all run from the top 
+    /// header to the bottom header
+    pub fn new(branches: Vec<TherminolBranch>) -> Self {
+
+        let branch_mass_flowrates = 
+            vec![MassRate::new::<kilogram_per_second>(0.0); branches.len()];
+
+        return Self { 
+            branches: branches, 
+            branch_mass_flowrates: branch_mass_flowrates, 
+            header_pressure_change: Pressure::new::<pascal>(0.0), 
+            warm_start_enabled: true, 
+            has_converged_solution: false, 
+            last_iteration_counts: CIETSolverIterationCounts::default(), 
+            cold_start_iteration_counts: None,
+            simulation_time: Duration::ZERO,
+            top_header_temperature: 
+                ThermodynamicTemperature::new::<degree_celsius>(21.0),
+            bottom_header_temperature: 
+                ThermodynamicTemperature::new::<degree_celsius>(21.0),
+            ambient_temperature: 
+                ThermodynamicTemperature::new::<degree_celsius>(21.0),
+            temperature_range_policy: TemperatureRangePolicy::default(),
+            temperature_range_violations: vec![],
+            out_of_range_temperature_events: 0,
+        };
+    }
+
+    /// adds a branch in parallel with the others and returns its
+    /// position in the network
+    ///
+    /// the branch is filled with the same fluid as the rest of 
+    /// the network
+    pub fn add_branch(&mut self, mut branch: TherminolBranch) -> usize {
+
+        if let Some(first_branch) = self.branches.first() {
+            if let Some(first_component) = first_branch.get_components().first() {
+                branch.set_fluid_properties(
+                    first_component.get_fluid_properties_arc());
+            }
+        }
+
+        self.branches.push(branch);
+        self.branch_mass_flowrates.push(
+            MassRate::new::<kilogram_per_second>(0.0));
+
+        // the previous operating point no longer applies
+        self.has_converged_solution = false;
+
+        return self.branches.len() - 1;
+    }
+
+    pub fn get_branches(&self) -> &Vec<TherminolBranch> {
+        return &self.branches;
+    }
+
+    /// returns the branch at the given index, or None if there
+    /// is no branch at that index
+    pub fn get_branch(&self, branch_index: usize) -> Option<&TherminolBranch> {
+        return self.branches.get(branch_index);
+    }
+
+    /// returns a branch so that its pump pressures or check valve
+    /// behaviour can be changed, or None if there is no branch
+    /// at that index
+    pub fn get_branch_mut(&mut self, branch_index: usize) -> Option<&mut TherminolBranch> {
+        return self.branches.get_mut(branch_index);
+    }
+
+    /// returns the position of the branch with the given name
+    /// within the network, if there is one
+    pub fn get_branch_index(&self, branch_name: &str) -> Option<usize> {
+        return self.branches.iter().
+            position(|branch| branch.get_name() == branch_name);
+    }
+
+    /// returns the branch flowrates from the last successful 
+    /// solve, in the same order as the branches
+    pub fn get_branch_mass_flowrates(&self) -> &Vec<MassRate> {
+        return &self.branch_mass_flowrates;
+    }
+
+    /// returns the flowrate of the branch at the given index 
+    /// from the last successful solve, or None if there is no 
+    /// branch at that index
+    pub fn get_branch_mass_flowrate(&self, branch_index: usize) -> Option<MassRate> {
+        return self.branch_mass_flowrates.get(branch_index).copied();
+    }
+
+    /// returns the pressure change from the top header to the
+    /// bottom header, this is the same for all branches
+    /// since they are in parallel
+    pub fn get_header_pressure_change(&self) -> Pressure {
+        return self.header_pressure_change;
+    }
+
+    /// if enabled (the default), every solve after the first one
+    /// starts from the previous operating point
+    pub fn set_warm_start_enabled(&mut self, warm_start_enabled: bool){
+        self.warm_start_enabled = warm_start_enabled;
+    }
+
+    pub fn get_warm_start_enabled(&self) -> bool {
+        return self.warm_start_enabled;
+    }
+
+    /// forgets the previous operating point, so the next solve
+    /// starts from the default brackets
+    pub fn reset_warm_start(&mut self){
+        self.has_converged_solution = false;
+    }
+
+    /// returns the iteration counts of the last successful solve
+    pub fn get_last_iteration_counts(&self) -> CIETSolverIterationCounts {
+        return self.last_iteration_counts;
+    }
+
+    /// returns the iteration counts of the most recent cold start,
+    /// this is the baseline which warm starts are compared against
+    pub fn get_cold_start_iteration_counts(&self) -> 
+        Option<CIETSolverIterationCounts> {
+        return self.cold_start_iteration_counts;
+    }
+
+    /// sets every component in every branch to the same fluid 
+    /// temperature, along with their walls
+    pub fn set_uniform_fluid_temperature(&mut self, 
+                                         fluid_temp: ThermodynamicTemperature){
+        for branch in self.branches.iter_mut() {
+            branch.set_fluid_temperature(fluid_temp);
+        }
+
+        self.top_header_temperature = fluid_temp;
+        self.bottom_header_temperature = fluid_temp;
+    }
+
+    /// sets the fluid temperature of the component with the given
+    /// name, along with its wall, returns false if no branch has 
+    /// a component by that name
+    pub fn set_component_fluid_temperature(
+        &mut self,
+        component_name: &str,
+        fluid_temp: ThermodynamicTemperature) -> bool {
+
+        for branch in self.branches.iter_mut() {
+            if let Some(component_index) = branch.get_component_index(component_name) {
+                branch.set_component_fluid_temperature(component_index, fluid_temp);
+                return true;
+            }
+        }
+
+        return false;
+    }
+
+    /// returns the fluid temperature of the component with the 
+    /// given name, if there is one
+    pub fn get_component_fluid_temperature(
+        &self,
+        component_name: &str) -> Option<ThermodynamicTemperature> {
+
+        for branch in self.branches.iter() {
+            if let Some(component_index) = branch.get_component_index(component_name) {
+                return Some(branch.get_components()[component_index].
+                            get_fluid_temperature());
+            }
+        }
+
+        return None;
+    }
+
+    /// returns the hydrostatic pressure change from the top 
+    /// header to the bottom header of every branch, in the same
+    /// order as the branches
+    ///
+    /// if these differ, the branches with the denser (colder) 
+    /// fluid push fluid down, and the others carry it up, even
+    /// with no pumps running
+    pub fn get_branch_hydrostatic_pressure_changes(&self) -> Vec<Pressure> {
+        return self.branches.iter().
+            map(|branch| branch.get_hydrostatic_pressure_change()).
+            collect();
+    }
+
+    /// returns the temperature of the fluid mixed in the top 
+    /// header
+    pub fn get_top_header_temperature(&self) -> ThermodynamicTemperature {
+        return self.top_header_temperature;
+    }
+
+    /// returns the temperature of the fluid mixed in the bottom 
+    /// header
+    pub fn get_bottom_header_temperature(&self) -> ThermodynamicTemperature {
+        return self.bottom_header_temperature;
+    }
+
+    /// returns the temperature of the fluid entering a component
+    /// given the current branch flowrate, or None if there is no
+    /// such branch or component
+    pub fn get_component_inlet_temperature(
+        &self,
+        branch_index: usize,
+        component_index: usize) -> Option<ThermodynamicTemperature> {
+
+        let branch = self.branches.get(branch_index)?;
+        let branch_mass_flowrate = self.get_branch_mass_flowrate(branch_index)?;
+
+        if component_index >= branch.get_components().len() {
+            return None;
+        }
+
+        return Some(branch.get_component_inlet_temperature(
+            component_index, 
+            branch_mass_flowrate, 
+            self.top_header_temperature, 
+            self.bottom_header_temperature));
+    }
+
+    /// returns the inlet and outlet temperatures of a run of 
+    /// components within a branch (eg. the two halves of the 
+    /// ctah), from first_component_index to last_component_index
+    /// inclusive
+    ///
+    /// which end is the inlet depends on the direction of flow,
+    /// None is returned if there is no such branch or component
+    pub fn get_component_range_inlet_outlet_temperatures(
+        &self,
+        branch_index: usize,
+        first_component_index: usize,
+        last_component_index: usize) -> 
+        Option<(ThermodynamicTemperature, ThermodynamicTemperature)> {
+
+        let branch = self.branches.get(branch_index)?;
+
+        let (inlet_component_index, outlet_component_index) = 
+            if self.get_branch_mass_flowrate(branch_index)?.value >= 0.0 {
+                (first_component_index, last_component_index)
+            } else {
+                (last_component_index, first_component_index)
+            };
+
+        let inlet_temperature = self.get_component_inlet_temperature(
+            branch_index, inlet_component_index)?;
+
+        let outlet_temperature = branch.get_components().
+            get(outlet_component_index)?.
+            get_fluid_temperature();
+
+        return Some((inlet_temperature, outlet_temperature));
+    }
+
+    /// sets the working fluid of every component in every 
+    /// branch
+    pub fn set_fluid_properties(&mut self, 
+                                fluid_properties: Arc<dyn FluidProperties>){
+        for branch in self.branches.iter_mut() {
+            branch.set_fluid_properties(fluid_properties.clone());
+        }
+    }
+
+    /// returns the working fluid, taken from the first 
+    /// component of the first branch. An empty network is 
+    /// taken to hold therminol VP-1
+    pub fn get_fluid_properties(&self) -> &dyn FluidProperties {
+
+        match self.branches.first().
+            and_then(|branch| branch.get_components().first()) {
+            Some(first_component) => return first_component.get_fluid_properties(),
+            None => return &TherminolVP1,
+        }
+    }
+
+    /// sets the ambient (room) temperature that insulated 
+    /// components lose heat to
+    pub fn set_ambient_temperature(&mut self, 
+                                   ambient_temperature: ThermodynamicTemperature){
+        self.ambient_temperature = ambient_temperature;
+    }
+
+    pub fn get_ambient_temperature(&self) -> ThermodynamicTemperature {
+        return self.ambient_temperature;
+    }
+
+    /// wraps every component in every branch in the same 
+    /// insulation
+    pub fn set_insulation(&mut self, insulation: InsulationParameters){
+        for branch in self.branches.iter_mut() {
+            branch.set_insulation(insulation);
+        }
+    }
+
+    /// wraps the component with the given name in insulation, 
+    /// returns false if no branch has a component by that name
+    pub fn set_component_insulation(
+        &mut self,
+        component_name: &str,
+        insulation: InsulationParameters) -> bool {
+
+        for branch in self.branches.iter_mut() {
+            if let Some(component_index) = branch.get_component_index(component_name) {
+                branch.set_component_insulation(component_index, insulation);
+                return true;
+            }
+        }
+
+        return false;
+    }
+
+    /// removes the insulation from every component
+    pub fn clear_insulation(&mut self){
+        for branch in self.branches.iter_mut() {
+            branch.clear_insulation();
+        }
+    }
+
+    /// gives every component in every branch the same kind of 
+    /// wall
+    pub fn set_wall(&mut self, wall_parameters: WallParameters){
+        for branch in self.branches.iter_mut() {
+            branch.set_wall(wall_parameters);
+        }
+    }
+
+    /// gives the component with the given name a wall, returns 
+    /// false if no branch has a component by that name
+    pub fn set_component_wall(
+        &mut self,
+        component_name: &str,
+        wall_parameters: WallParameters) -> bool {
+
+        for branch in self.branches.iter_mut() {
+            if let Some(component_index) = branch.get_component_index(component_name) {
+                branch.set_component_wall(component_index, wall_parameters);
+                return true;
+            }
+        }
+
+        return false;
+    }
+
+    /// removes the walls from every component
+    pub fn clear_walls(&mut self){
+        for branch in self.branches.iter_mut() {
+            branch.clear_walls();
+        }
+    }
+
+    /// returns the wall node temperatures of the component with
+    /// the given name, if there is one and it has a wall
+    pub fn get_component_wall_temperatures(
+        &self,
+        component_name: &str) -> Option<Vec<ThermodynamicTemperature>> {
+
+        for branch in self.branches.iter() {
+            if let Some(component_index) = branch.get_component_index(component_name) {
+                return branch.get_component_wall_temperatures(component_index);
+            }
+        }
+
+        return None;
+    }
+
+    /// returns the heat lost to ambient (watts) from the 
+    /// component with the given name, if there is one
+    pub fn get_component_heat_loss_watts(
+        &self,
+        component_name: &str) -> Option<f64> {
+
+        for branch in self.branches.iter() {
+            if let Some(component_index) = branch.get_component_index(component_name) {
+                return Some(branch.get_component_heat_loss_watts(
+                        component_index, self.ambient_temperature));
+            }
+        }
+
+        return None;
+    }
+
+    /// returns the heat lost to ambient (watts) from every 
+    /// component in the network
+    pub fn get_heat_loss_watts(&self) -> f64 {
+        return self.branches.iter().
+            map(|branch| branch.get_heat_loss_watts(self.ambient_temperature)).
+            sum();
+    }
+
+    /// sets what solve and step_transient do when a component's
+    /// fluid temperature is outside the validated range of the 
+    /// property correlations, by default they carry on
+    pub fn set_temperature_range_policy(
+        &mut self, 
+        temperature_range_policy: TemperatureRangePolicy){
+        self.temperature_range_policy = temperature_range_policy;
+    }
+
+    pub fn get_temperature_range_policy(&self) -> TemperatureRangePolicy {
+        return self.temperature_range_policy;
+    }
+
+    /// returns a TemperatureOutOfRange error for every component 
+    /// which was outside the validated temperature range at the
+    /// last check
+    pub fn get_temperature_range_violations(&self) -> &Vec<CIETSolverError> {
+        return &self.temperature_range_violations;
+    }
+
+    /// returns how many times a component has left the 
+    /// validated temperature range, a component which stays out
+    /// of range only counts once
+    pub fn get_out_of_range_temperature_events(&self) -> usize {
+        return self.out_of_range_temperature_events;
+    }
+
+    pub fn reset_out_of_range_temperature_events(&mut self){
+        self.out_of_range_temperature_events = 0;
+    }
+
+    /// checks every component's fluid temperature against the 
+    /// validated range of the property correlations, and counts
+    /// the components which have newly left it
+    ///
+    /// with TemperatureRangePolicy::Error, the first violation
+    /// is returned as an error
+    pub fn check_fluid_temperatures(&mut self) -> Result<(), CIETSolverError> {
+
+        self.update_temperature_range_violations();
+
+        if self.temperature_range_policy == TemperatureRangePolicy::Error {
+            if let Some(violation) = self.temperature_range_violations.first() {
+                return Err(violation.clone());
+            }
+        }
+
+        return Ok(());
+    }
+
+    fn update_temperature_range_violations(&mut self){
+
+        let get_component_name = |violation: &CIETSolverError| -> String {
+            match violation {
+                CIETSolverError::TemperatureOutOfRange { component_name, .. } => 
+                    component_name.clone(),
+                _ => String::new(),
+            }
+        };
+
+        let previous_component_names: Vec<String> = 
+            self.temperature_range_violations.iter().
+            map(get_component_name).
+            collect();
+
+        let violations: Vec<CIETSolverError> = self.branches.iter().
+            flat_map(|branch| branch.get_temperature_range_violations()).
+            collect();
+
+        self.out_of_range_temperature_events += violations.iter().
+            filter(|violation| 
+                   !previous_component_names.contains(&get_component_name(*violation))).
+            count();
+
+        self.temperature_range_violations = violations;
+    }
+
+    /// advances the temperatures in every branch over a timestep
+    /// at the current branch flowrates, the flowrates themselves 
+    /// are not changed
+    ///
+    /// fluid leaving each branch is mixed in the header it flows 
+    /// into, and enters the other branches at the mixed 
+    /// temperature. The headers hold no fluid, and their 
+    /// temperatures lag by one substep
+    ///
+    /// timesteps longer than MAX_TRANSIENT_SUBSTEP_SECONDS are 
+    /// split into equal substeps
+    pub fn advance_temperatures(&mut self, timestep: Duration){
+
+        let number_of_substeps: u32 = 
+            (timestep.as_secs_f64() / MAX_TRANSIENT_SUBSTEP_SECONDS).
+            ceil().max(1.0) as u32;
+
+        let substep = timestep / number_of_substeps;
+
+        for _ in 0..number_of_substeps {
+
+            self.update_header_temperatures();
+
+            for (branch_index, branch) in self.branches.iter_mut().enumerate() {
+                branch.advance_temperatures(
+                    self.branch_mass_flowrates[branch_index], 
+                    self.top_header_temperature, 
+                    self.bottom_header_temperature, 
+                    self.ambient_temperature,
+                    substep);
+            }
+        }
+
+        self.update_header_temperatures();
+        self.update_temperature_range_violations();
+    }
+
+    // mixes the fluid flowing into each header, if nothing flows
+    // into a header, its temperature stays as it was
+    fn update_header_temperatures(&mut self){
+
+        let mut top_header_inflow_kg_per_s = 0.0;
+        let mut top_header_weighted_temperature_sum = 0.0;
+        let mut bottom_header_inflow_kg_per_s = 0.0;
+        let mut bottom_header_weighted_temperature_sum = 0.0;
+
+        for (branch_index, branch) in self.branches.iter().enumerate() {
+
+            let mass_flowrate_kg_per_s = 
+                self.branch_mass_flowrates[branch_index].value;
+
+            if mass_flowrate_kg_per_s > 0.0 {
+                // flows out of the bottom end into the bottom header
+                bottom_header_inflow_kg_per_s += mass_flowrate_kg_per_s;
+                bottom_header_weighted_temperature_sum += mass_flowrate_kg_per_s * 
+                    get_temperature_degrees_c(branch.get_bottom_end_temperature());
+            } else if mass_flowrate_kg_per_s < 0.0 {
+                // flows out of the top end into the top header
+                top_header_inflow_kg_per_s -= mass_flowrate_kg_per_s;
+                top_header_weighted_temperature_sum -= mass_flowrate_kg_per_s * 
+                    get_temperature_degrees_c(branch.get_top_end_temperature());
+            }
+        }
+
+        if top_header_inflow_kg_per_s > 0.0 {
+            self.top_header_temperature = 
+                ThermodynamicTemperature::new::<degree_celsius>(
+                    top_header_weighted_temperature_sum / top_header_inflow_kg_per_s);
+        }
+
+        if bottom_header_inflow_kg_per_s > 0.0 {
+            self.bottom_header_temperature = 
+                ThermodynamicTemperature::new::<degree_celsius>(
+                    bottom_header_weighted_temperature_sum / bottom_header_inflow_kg_per_s);
+        }
+    }
+
+    /// returns the time simulated by step_transient so far
+    pub fn get_simulation_time(&self) -> Duration {
+        return self.simulation_time;
+    }
+
+    pub fn reset_simulation_time(&mut self){
+        self.simulation_time = Duration::ZERO;
+    }
+
+    /// sets the branch flowrates directly, eg. to start a 
+    /// transient from rest
+    ///
+    /// the header pressure change is left as is until the next
+    /// solve or transient step
+    ///
+    /// returns a BranchCountMismatch error (and leaves the 
+    /// flowrates as they were) if there is not exactly one 
+    /// flowrate per branch
+    pub fn set_branch_mass_flowrates(
+        &mut self, 
+        branch_mass_flowrates: Vec<MassRate>) -> Result<(), CIETSolverError> {
+
+        if branch_mass_flowrates.len() != self.branches.len() {
+            return Err(CIETSolverError::BranchCountMismatch { 
+                expected_branches: self.branches.len(), 
+                given_branches: branch_mass_flowrates.len(),
+            });
+        }
+
+        self.branch_mass_flowrates = branch_mass_flowrates;
+        self.has_converged_solution = false;
+
+        return Ok(());
+    }
+
+    /// returns manometer readings at every junction between 
+    /// components in every branch (in branch order)
+    ///
+    /// the readings are gauge pressures relative to the
+    /// reference node chosen by the user
+    pub fn get_manometer_readings(
+        &self,
+        reference: ManometerReference) -> Vec<ManometerReading> {
+
+        let top_header_gauge_pressure = 
+            get_top_header_gauge_pressure(
+                reference, 
+                self.header_pressure_change);
+
+        let mut manometer_readings: Vec<ManometerReading> = vec![];
+
+        for (branch, branch_mass_flowrate) in 
+            self.branches.iter().zip(self.branch_mass_flowrates.iter()) {
+
+            manometer_readings.append(&mut branch.
+                get_manometer_readings(
+                    *branch_mass_flowrate, 
+                    top_header_gauge_pressure));
+        }
+
+        return manometer_readings;
+    }
+
+    /// returns the flow conditions and geometry of every 
+    /// component in every branch, branch by branch in the order 
+    /// the branches were given
+    pub fn get_component_readings(&self) -> Vec<ComponentReading> {
+
+        let mut component_readings: Vec<ComponentReading> = vec![];
+
+        for (branch, branch_mass_flowrate) in 
+            self.branches.iter().zip(self.branch_mass_flowrates.iter()) {
+
+            component_readings.append(&mut branch.
+                get_component_readings(*branch_mass_flowrate));
+        }
+
+        return component_readings;
+    }
+
+    /// returns the name and open (true) or closed (false) state
+    /// of every check valve in the network, labelled
+    /// "{branch_name}_{check_valve_name}"
+    pub fn get_check_valve_states(&self) -> Vec<(String, bool)> {
+
+        let mut check_valve_states: Vec<(String, bool)> = vec![];
+
+        for branch in self.branches.iter() {
+            for (check_valve_name, open) in branch.get_check_valve_states() {
+                check_valve_states.push(
+                    (format!("{}_{}", branch.get_name(), check_valve_name), open));
+            }
+        }
+
+        return check_valve_states;
+    }
+
+    /// returns labels for all the manometer readings in the
+    /// same order as get_manometer_readings
+    pub fn get_manometer_tap_labels(&self) -> Vec<String> {
+
+        let mut tap_labels: Vec<String> = vec![];
+
+        for branch in self.branches.iter() {
+            tap_labels.append(&mut branch.get_manometer_tap_labels());
+        }
+
+        return tap_labels;
+    }
+
+    /// finds the header pressure change where the net flow 
+    /// out of the headers is zero, and returns the flowrate 
+    /// in each branch (in the same order as the branches)
+    ///
+    /// for a cold start, the header pressure change is bracketed
+    /// within +/- 50 kPa of the hydrostatic head of the first 
+    /// branch, as in ciet digital twin v1. If warm starts are 
+    /// enabled and a previous solve converged, the brackets are 
+    /// centred on the previous header pressure change and branch 
+    /// flowrates. Either way, the brackets expand if needed
+    pub fn solve(&mut self) -> Result<Vec<MassRate>, CIETSolverError> {
+
+        if self.branches.is_empty() {
+            return Ok(vec![]);
+        }
+
+        self.check_fluid_temperatures()?;
+
+        let branches = &self.branches;
+
+        let warm_started = 
+            self.warm_start_enabled && self.has_converged_solution;
+
+        for branch in branches.iter() {
+            branch.reset_pressure_change_evaluations();
+        }
+
+        // for a cold start, the branch flowrates are bracketed 
+        // within -1 to +1 kg/s, for a warm start, they are 
+        // bracketed around the previous flowrate
+        let branch_brackets: Vec<(MassRate, MassRate)> = 
+            self.branch_mass_flowrates.iter().map(
+                |previous_flowrate| -> (MassRate, MassRate) {
+
+                    if !warm_started {
+                        return (MassRate::new::<kilogram_per_second>(-1.0),
+                        MassRate::new::<kilogram_per_second>(1.0));
+                    }
+
+                    let bracket_half_width = 
+                        MassRate::new::<kilogram_per_second>(
+                            WARM_START_BRANCH_FLOWRATE_BRACKET_KG_PER_S);
+
+                    return (*previous_flowrate - bracket_half_width,
+                    *previous_flowrate + bracket_half_width);
+                }).collect();
+
+        let get_branch_mass_flowrates = 
+            |test_pressure_change: Pressure| 
+            -> Result<Vec<MassRate>, CIETSolverError> {
+
+                let mut branch_mass_flowrates: Vec<MassRate> = vec![];
+
+                for (branch, branch_bracket) in 
+                    branches.iter().zip(branch_brackets.iter()) {
+
+                    branch_mass_flowrates.push(branch.
+                        try_get_mass_flowrate_from_pressure_change_with_bracket(
+                            test_pressure_change,
+                            branch_bracket.0,
+                            branch_bracket.1)?);
+                }
+
+                return Ok(branch_mass_flowrates);
+            };
+
+        // brent's method only works with f64, so if a branch 
+        // solver fails within the root find, i store its error 
+        // here and return NaN
+        let branch_solver_error: RefCell<Option<CIETSolverError>> = 
+            RefCell::new(None);
+
+        let header_residual_evaluations: Cell<usize> = Cell::new(0);
+
+        let pressure_change_root = 
+            |pressure_change_pascals: f64| -> f64 {
+
+                header_residual_evaluations.set(
+                    header_residual_evaluations.get() + 1);
+
+                let test_pressure_change = 
+                    Pressure::new::<pascal>(pressure_change_pascals);
+
+                match get_branch_mass_flowrates(test_pressure_change) {
+                    Ok(branch_mass_flowrates) => {
+                        let total_mass_flowrate: f64 = 
+                            branch_mass_flowrates.iter().
+                            map(|mass_flowrate| mass_flowrate.value).
+                            sum();
+
+                        return total_mass_flowrate;
+                    },
+                    Err(solver_error) => {
+                        branch_solver_error.borrow_mut().
+                            get_or_insert(solver_error);
+                        return f64::NAN;
+                    },
+                }
+            };
+
+        let (lower_bound, upper_bound) = if warm_started {
+
+            let bracket_half_width = Pressure::new::<pascal>(
+                WARM_START_HEADER_PRESSURE_BRACKET_PA);
+
+            (self.header_pressure_change - bracket_half_width,
+            self.header_pressure_change + bracket_half_width)
+
+        } else {
+
+            let zero_flowrate = MassRate::new::<kilogram_per_second>(0.0);
+
+            let first_branch_hydrostatic_pressure = branches[0].
+                get_pressure_change(zero_flowrate);
+
+            (first_branch_hydrostatic_pressure + 
+             Pressure::new::<pascal>(-50000_f64),
+             first_branch_hydrostatic_pressure + 
+             Pressure::new::<pascal>(50000_f64))
+        };
+
+        let pressure_change_result 
+            = find_root_brent_expanding(
+                "header_pressure_change_pa",
+                lower_bound.value,
+                upper_bound.value,
+                pressure_change_root);
+
+        // the branch error is more useful than the NaN it caused
+        if let Some(solver_error) = branch_solver_error.into_inner() {
+            return Err(solver_error);
+        }
+
+        let pressure_change = 
+            Pressure::new::<pascal>(pressure_change_result?);
+
+        let branch_mass_flowrates = 
+            get_branch_mass_flowrates(pressure_change)?;
+
+        let branch_residual_evaluations: usize = branches.iter().
+            map(|branch| branch.get_pressure_change_evaluations()).
+            sum();
+
+        let iteration_counts = CIETSolverIterationCounts {
+            header_residual_evaluations: header_residual_evaluations.get(),
+            branch_residual_evaluations: branch_residual_evaluations,
+            warm_started: warm_started,
+        };
+
+        // check valves only change state once the flow has 
+        // converged
+        for (branch, branch_mass_flowrate) in self.branches.iter_mut().
+            zip(branch_mass_flowrates.iter()) {
+            branch.update_check_valve_states(*branch_mass_flowrate);
+        }
+
+        self.branch_mass_flowrates = branch_mass_flowrates.clone();
+        self.header_pressure_change = pressure_change;
+
+        self.has_converged_solution = true;
+        self.last_iteration_counts = iteration_counts;
+
+        if !warm_started {
+            self.cold_start_iteration_counts = Some(iteration_counts);
+        }
+
+        return Ok(branch_mass_flowrates);
+    }
+
+    /// advances the branch flowrates over a timestep, starting 
+    /// from the current branch flowrates, and returns the new 
+    /// flowrates (in the same order as the branches)
+    ///
+    /// each branch obeys a momentum balance:
+    ///
+    /// inertance * d(mass_flowrate)/dt = 
+    /// branch_pressure_change(mass_flowrate) - header_pressure_change
+    ///
+    /// where inertance is the sum of L/A over the branch. At 
+    /// steady state, this reduces to what solve() finds.
+    ///
+    /// the branch pressure change is linearised about the current 
+    /// flowrate and treated implicitly, so branches with very 
+    /// steep curves (eg. closed check valves) don't blow up. The 
+    /// header pressure change is chosen so that mass is 
+    /// conserved at the end of every substep
+    ///
+    /// timesteps longer than MAX_TRANSIENT_SUBSTEP_SECONDS are 
+    /// split into equal substeps
+    pub fn step_transient(&mut self, timestep: Duration) 
+        -> Result<Vec<MassRate>, CIETSolverError> {
+
+        if self.branches.is_empty() {
+            self.simulation_time += timestep;
+            return Ok(vec![]);
+        }
+
+        self.check_fluid_temperatures()?;
+
+        let number_of_substeps: u32 = 
+            (timestep.as_secs_f64() / MAX_TRANSIENT_SUBSTEP_SECONDS).
+            ceil().max(1.0) as u32;
+
+        let substep = timestep / number_of_substeps;
+
+        for _ in 0..number_of_substeps {
+            self.advance_transient_substep(substep)?;
+        }
+
+        self.simulation_time += timestep;
+
+        return Ok(self.branch_mass_flowrates.clone());
+    }
+
+    fn advance_transient_substep(&mut self, substep: Duration) 
+        -> Result<(), CIETSolverError> {
+
+        let timestep_seconds = substep.as_secs_f64();
+
+        // pumps speed up or coast down first
+        for branch in self.branches.iter_mut() {
+            branch.advance_pump_speeds(substep);
+        }
+
+        // for each branch, 
+        // m_new = m_old + weight * (branch_pressure_change - header_pressure_change)
+        // where weight = dt / (inertance - dt * slope)
+        let mut branch_pressure_changes: Vec<f64> = vec![];
+        let mut branch_weights: Vec<f64> = vec![];
+
+        let perturbation = MassRate::new::<kilogram_per_second>(
+            BRANCH_SLOPE_PERTURBATION_KG_PER_S);
+
+        for (branch, branch_mass_flowrate) in 
+            self.branches.iter().zip(self.branch_mass_flowrates.iter()) {
+
+            branch.check_fluid_properties()?;
+
+            let branch_pressure_change = 
+                branch.get_pressure_change(*branch_mass_flowrate);
+
+            let slope_pascal_second_per_kg = 
+                (branch.get_pressure_change(*branch_mass_flowrate + perturbation)
+                 - branch.get_pressure_change(*branch_mass_flowrate - perturbation)).value
+                / (2.0 * perturbation.value);
+
+            let branch_weight = timestep_seconds / 
+                (branch.get_inertance_per_meter() 
+                 - timestep_seconds * slope_pascal_second_per_kg);
+
+            if !branch_pressure_change.value.is_finite() || 
+                !branch_weight.is_finite() {
+                return Err(CIETSolverError::NonFiniteResidual { 
+                    solved_variable: format!("{}_transient_mass_flowrate_kg_per_s",
+                                             branch.get_name()), 
+                    input_value: branch_mass_flowrate.value, 
+                    residual: branch_pressure_change.value });
+            }
+
+            branch_pressure_changes.push(branch_pressure_change.value);
+            branch_weights.push(branch_weight);
+        }
+
+        // sum of m_new = 0 gives the header pressure change
+        let total_mass_flowrate_kg_per_s: f64 = 
+            self.branch_mass_flowrates.iter().
+            map(|mass_flowrate| mass_flowrate.value).
+            sum();
+
+        let total_weight: f64 = branch_weights.iter().sum();
+
+        let weighted_pressure_change: f64 = branch_weights.iter().
+            zip(branch_pressure_changes.iter()).
+            map(|(weight, pressure_change)| weight * pressure_change).
+            sum();
+
+        let header_pressure_change_pascals = 
+            (total_mass_flowrate_kg_per_s + weighted_pressure_change) 
+            / total_weight;
+
+        if !header_pressure_change_pascals.is_finite() {
+            return Err(CIETSolverError::NonFiniteResidual { 
+                solved_variable: "transient_header_pressure_change_pa".to_string(), 
+                input_value: total_weight, 
+                residual: header_pressure_change_pascals });
+        }
+
+        for index in 0..self.branches.len() {
+
+            let new_mass_flowrate_kg_per_s = 
+                self.branch_mass_flowrates[index].value + 
+                branch_weights[index] * 
+                (branch_pressure_changes[index] - header_pressure_change_pascals);
+
+            self.branch_mass_flowrates[index] = 
+                MassRate::new::<kilogram_per_second>(new_mass_flowrate_kg_per_s);
+        }
+
+        self.header_pressure_change = 
+            Pressure::new::<pascal>(header_pressure_change_pascals);
+
+        for (branch, branch_mass_flowrate) in self.branches.iter_mut().
+            zip(self.branch_mass_flowrates.iter()) {
+            branch.update_check_valve_states(*branch_mass_flowrate);
+        }
+
+        return Ok(());
+    }
+}
@@ -0,0 +1,647 @@
+// This application was developed for use in my PhD thesis under supervision 
+// of Professor Per F. Peterson. It is part of a thermal hydraulics
+// library in Rust that is released under the GNU General Public License
+// v 3.0. This is partly due to the fact that some of the libraries 
+// inherit from GeN-Foam and OpenFOAM, both licensed under GNU General
+// Public License v3.0.
+//
+// As such, the entire library is released under GNU GPL v3.0. It is a strong 
+// copyleft license which means you cannot use it in proprietary software.
+//
+//
+// License
+//    This application is part ciet_digital_twin_rust which uses
+//    fluid_mechanics_rust, a partial library of the
+//    thermal hydraulics library written in rust meant to help with the
+//    fluid mechanics aspects of the calculations
+//     
+//    Copyright (C) 2022-2023  Theodore Kay Chen Ong, Singapore Nuclear
+//    Research and Safety Initiative, Per F. Peterson, University of 
+//    California, Berkeley Thermal Hydraulics Laboratory
+//
+//    ciet_digital_twin_rust is free software; you can redistribute it and/or modify it
+//    under the terms of the GNU General Public License as published by the
+//    Free Software Foundation; either version 2 of the License, or (at your
+//    option) any later version.
+//
+//    ciet_digital_twin_rust is distributed in the hope that it will be useful, but WITHOUT
+//    ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or
+//    FITNESS FOR A PARTICULAR PURPOSE.  See the GNU General Public License
+//    for more details.
+//
+//    ciet_digital_twin_rust relies on a thermal hydraulics library
+//    also written in rust. The isothermal digital twin in particular
+//    relies on fluid_mechanics_rust.
+//    This library is part of a thermal hydraulics library in rust
+//    and contains some code copied from GeN-Foam, and OpenFOAM derivative.
+//    This offering is not approved or endorsed by the OpenFOAM Foundation nor
+//    OpenCFD Limited, producer and distributor of the OpenFOAM(R)software via
+//    www.openfoam.com, and owner of the OPENFOAM(R) and OpenCFD(R) trademarks.
+//    Nor is it endorsed by the authors and owners of GeN-Foam.
+//
+//    You should have received a copy of the GNU General Public License
+//    along with this program.  If not, see <http://www.gnu.org/licenses/>.
+//
+// © All rights reserved. Theodore Kay Chen Ong,
+// Singapore Nuclear Research and Safety Initiative,
+// Per F. Peterson,
+// University of California, Berkeley Thermal Hydraulics Laboratory
+//
+// Main author of the code: Theodore Kay Chen Ong, supervised by
+// Professor Per F. Peterson
+extern crate fluid_mechanics_rust;
+use fluid_mechanics_rust::prelude::*;
+
+use std::f64::consts::PI;
+use std::time::Duration;
+
+use crate::energy_equation::get_temperature_degrees_c;
+use crate::insulation::InsulationParameters;
+use crate::fluid_properties::FluidProperties;
+
+/// Nusselt number for fully developed laminar flow in a tube 
+/// with constant heat flux
+pub const LAMINAR_NUSSELT_NUMBER: f64 = 4.36;
+
+/// Reynolds numbers bounding the laminar to turbulent 
+/// transition, the Nusselt number is interpolated between them
+pub const LAMINAR_REYNOLDS_NUMBER_LIMIT: f64 = 2300.0;
+pub const TURBULENT_REYNOLDS_NUMBER_LIMIT: f64 = 4000.0;
+
+/// material and thickness of a pipe (or heater) wall
+///
+/// with one radial node, the wall is lumped into a single 
+/// temperature. With more nodes, the wall is split into 
+/// shells of equal thickness, and heat is conducted radially 
+/// between them
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct WallParameters {
+    density_kg_per_cubic_meter: f64,
+    specific_heat_capacity_joule_per_kg_kelvin: f64,
+    thermal_conductivity_watts_per_meter_kelvin: f64,
+    wall_thickness: Length,
+    number_of_radial_nodes: usize,
+}
+
+impl WallParameters {
+
+    /// constructor, there is always at least one radial node 
+    /// and negative values are clamped to zero
+    pub fn new(density_kg_per_cubic_meter: f64,
+               specific_heat_capacity_joule_per_kg_kelvin: f64,
+               thermal_conductivity_watts_per_meter_kelvin: f64,
+               wall_thickness: Length,
+               number_of_radial_nodes: usize) -> Self {
+
+        let wall_thickness = if wall_thickness.value < 0.0 {
+            Length::new::<meter>(0.0)
+        } else {
+            wall_thickness
+        };
+
+        return Self { 
+            density_kg_per_cubic_meter: density_kg_per_cubic_meter.max(0.0), 
+            specific_heat_capacity_joule_per_kg_kelvin: 
+                specific_heat_capacity_joule_per_kg_kelvin.max(0.0), 
+            thermal_conductivity_watts_per_meter_kelvin: 
+                thermal_conductivity_watts_per_meter_kelvin.max(0.0), 
+            wall_thickness: wall_thickness, 
+            number_of_radial_nodes: number_of_radial_nodes.max(1),
+        };
+    }
+
+    /// returns a stainless steel 304 wall of the given 
+    /// thickness, properties are taken at room temperature
+    pub fn stainless_steel_304(wall_thickness: Length,
+                               number_of_radial_nodes: usize) -> Self {
+        return Self::new(8000.0, 500.0, 16.2, 
+                         wall_thickness, number_of_radial_nodes);
+    }
+
+    /// returns a lumped wall like that of the ciet pipes, 
+    /// 1 inch stainless steel tubing with 0.065 inch walls
+    pub fn ciet_pipe_wall() -> Self {
+        return Self::stainless_steel_304(Length::new::<millimeter>(1.65), 1);
+    }
+
+    /// returns a wall like that of the ciet heater, where the 
+    /// electrical heat is generated, split into three radial 
+    /// nodes
+    ///
+    /// the thickness is illustrative and has not been checked 
+    /// against the heater drawings
+    pub fn ciet_heater_wall() -> Self {
+        return Self::stainless_steel_304(Length::new::<millimeter>(1.65), 3);
+    }
+
+    pub fn get_density_kg_per_cubic_meter(&self) -> f64 {
+        return self.density_kg_per_cubic_meter;
+    }
+
+    pub fn get_specific_heat_capacity_joule_per_kg_kelvin(&self) -> f64 {
+        return self.specific_heat_capacity_joule_per_kg_kelvin;
+    }
+
+    pub fn get_thermal_conductivity_watts_per_meter_kelvin(&self) -> f64 {
+        return self.thermal_conductivity_watts_per_meter_kelvin;
+    }
+
+    pub fn get_wall_thickness(&self) -> Length {
+        return self.wall_thickness;
+    }
+
+    pub fn get_number_of_radial_nodes(&self) -> usize {
+        return self.number_of_radial_nodes;
+    }
+}
+
+/// the wall around a component, which stores heat and passes 
+/// it between the fluid and the insulation (or ambient)
+///
+/// the node temperatures go from the wetted surface outwards.
+/// The inner radius of the wall is taken as the radius of a 
+/// circle with the same area as the flow area, as for the 
+/// insulation
+#[derive(Debug, Clone, PartialEq)]
+pub struct PipeWall {
+    parameters: WallParameters,
+    node_temperatures: Vec<ThermodynamicTemperature>,
+
+    // heat generated within the wall (eg. by the electrical 
+    // heater), this is shared between the nodes by mass
+    heat_input_watts: f64,
+}
+
+impl PipeWall {
+
+    /// constructor, the whole wall starts at the given 
+    /// temperature
+    pub fn new(parameters: WallParameters,
+               initial_temperature: ThermodynamicTemperature) -> Self {
+
+        return Self { 
+            parameters: parameters, 
+            node_temperatures: vec![initial_temperature; 
+                parameters.get_number_of_radial_nodes()], 
+            heat_input_watts: 0.0,
+        };
+    }
+
+    pub fn get_parameters(&self) -> WallParameters {
+        return self.parameters;
+    }
+
+    /// returns the temperature of every radial node, from the 
+    /// wetted surface outwards
+    pub fn get_node_temperatures(&self) -> &Vec<ThermodynamicTemperature> {
+        return &self.node_temperatures;
+    }
+
+    pub fn set_uniform_temperature(&mut self, 
+                                   wall_temperature: ThermodynamicTemperature){
+        for node_temperature in self.node_temperatures.iter_mut() {
+            *node_temperature = wall_temperature;
+        }
+    }
+
+    /// returns the temperature of the node next to the fluid
+    pub fn get_inner_node_temperature(&self) -> ThermodynamicTemperature {
+        return self.node_temperatures[0];
+    }
+
+    /// returns the temperature of the node next to the 
+    /// insulation
+    pub fn get_outer_node_temperature(&self) -> ThermodynamicTemperature {
+        return self.node_temperatures[self.node_temperatures.len() - 1];
+    }
+
+    /// sets the heat generated within the wall (watts)
+    pub fn set_heat_input_watts(&mut self, heat_input_watts: f64){
+        self.heat_input_watts = heat_input_watts;
+    }
+
+    pub fn get_heat_input_watts(&self) -> f64 {
+        return self.heat_input_watts;
+    }
+
+    /// returns the radius of the wetted surface, the radius of
+    /// the outer surface, and the boundaries between nodes in 
+    /// between, from the inside outwards
+    pub fn get_node_boundary_radii(&self, cross_sectional_area: Area) -> Vec<f64> {
+
+        let inner_radius = (cross_sectional_area.value.abs() / PI).sqrt();
+        let number_of_radial_nodes = self.parameters.get_number_of_radial_nodes();
+        let node_thickness = self.parameters.get_wall_thickness().value / 
+            number_of_radial_nodes as f64;
+
+        return (0..=number_of_radial_nodes).
+            map(|boundary_index| inner_radius + 
+                node_thickness * boundary_index as f64).
+            collect();
+    }
+
+    // mass of each node (kg), from the inside outwards
+    fn get_node_masses_kg(&self, 
+                          cross_sectional_area: Area,
+                          component_length: Length) -> Vec<f64> {
+
+        let boundary_radii = self.get_node_boundary_radii(cross_sectional_area);
+        let length = component_length.value.abs();
+
+        return boundary_radii.windows(2).
+            map(|radii| self.parameters.get_density_kg_per_cubic_meter() * 
+                PI * (radii[1] * radii[1] - radii[0] * radii[0]) * length).
+            collect();
+    }
+
+    /// returns the heat capacity of the whole wall (J/K)
+    pub fn get_heat_capacity_joule_per_kelvin(
+        &self,
+        cross_sectional_area: Area,
+        component_length: Length) -> f64 {
+
+        let wall_mass_kg: f64 = self.get_node_masses_kg(
+            cross_sectional_area, component_length).iter().sum();
+
+        return wall_mass_kg * 
+            self.parameters.get_specific_heat_capacity_joule_per_kg_kelvin();
+    }
+
+    /// returns the conductance (W/K) between the fluid and the 
+    /// inner node, this is convection at the wetted surface in 
+    /// series with conduction to the middle of the inner node
+    pub fn get_fluid_conductance_watts_per_kelvin(
+        &self,
+        heat_transfer_coefficient_watts_per_square_meter_kelvin: f64,
+        cross_sectional_area: Area,
+        component_length: Length) -> f64 {
+
+        let length = component_length.value.abs();
+        let boundary_radii = self.get_node_boundary_radii(cross_sectional_area);
+        let inner_node_radius = 0.5 * (boundary_radii[0] + boundary_radii[1]);
+
+        let convection_area = 2.0 * PI * boundary_radii[0] * length;
+
+        if heat_transfer_coefficient_watts_per_square_meter_kelvin <= 0.0 || 
+            convection_area <= 0.0 {
+            return 0.0;
+        }
+
+        let convection_resistance = 1.0 / 
+            (heat_transfer_coefficient_watts_per_square_meter_kelvin * convection_area);
+
+        return 1.0 / (convection_resistance + 
+                      self.get_conduction_resistance(
+                          boundary_radii[0], inner_node_radius, length));
+    }
+
+    /// returns the conductance (W/K) between the outer node and
+    /// ambient, which is conduction to the outer surface in 
+    /// series with the insulation
+    pub fn get_ambient_conductance_watts_per_kelvin(
+        &self,
+        insulation: InsulationParameters,
+        cross_sectional_area: Area,
+        component_length: Length) -> f64 {
+
+        let length = component_length.value.abs();
+        let boundary_radii = self.get_node_boundary_radii(cross_sectional_area);
+        let number_of_radial_nodes = self.parameters.get_number_of_radial_nodes();
+        let outer_radius = boundary_radii[number_of_radial_nodes];
+        let outer_node_radius = 0.5 * (boundary_radii[number_of_radial_nodes - 1] 
+                                       + outer_radius);
+
+        let insulation_conductance = insulation.
+            get_conductance_watts_per_kelvin_from_inner_radius(
+                Length::new::<meter>(outer_radius), 
+                component_length);
+
+        if insulation_conductance <= 0.0 {
+            return 0.0;
+        }
+
+        return 1.0 / (1.0 / insulation_conductance + 
+                      self.get_conduction_resistance(
+                          outer_node_radius, outer_radius, length));
+    }
+
+    // resistance (K/W) to radial conduction through the wall 
+    // between two radii
+    fn get_conduction_resistance(&self, 
+                                 inner_radius: f64, 
+                                 outer_radius: f64, 
+                                 length: f64) -> f64 {
+
+        if outer_radius <= inner_radius || inner_radius <= 0.0 {
+            return 0.0;
+        }
+
+        let conductivity = 
+            self.parameters.get_thermal_conductivity_watts_per_meter_kelvin();
+
+        if conductivity <= 0.0 || length <= 0.0 {
+            return f64::INFINITY;
+        }
+
+        return (outer_radius / inner_radius).ln() / (2.0 * PI * conductivity * length);
+    }
+
+    /// advances the wall node temperatures over one timestep 
+    /// with backward euler, the fluid and ambient temperatures 
+    /// are held fixed over the timestep
+    ///
+    /// for each node:
+    ///
+    /// m cp (T - T_old)/dt = sum of G (T_neighbour - T) + q
+    ///
+    /// the inner node's neighbour is the fluid and the outer 
+    /// node's neighbour is ambient. The nodes form a tridiagonal
+    /// system which is solved directly
+    pub fn advance_temperatures(
+        &mut self,
+        fluid_temperature: ThermodynamicTemperature,
+        fluid_conductance_watts_per_kelvin: f64,
+        ambient_conductance_watts_per_kelvin: f64,
+        ambient_temperature: ThermodynamicTemperature,
+        cross_sectional_area: Area,
+        component_length: Length,
+        timestep: Duration){
+
+        let timestep_seconds = timestep.as_secs_f64();
+        let length = component_length.value.abs();
+
+        // a wall with no thickness holds no heat
+        if timestep_seconds <= 0.0 || length <= 0.0 || 
+            self.parameters.get_wall_thickness().value <= 0.0 {
+            return;
+        }
+
+        let number_of_radial_nodes = self.parameters.get_number_of_radial_nodes();
+        let boundary_radii = self.get_node_boundary_radii(cross_sectional_area);
+        let node_masses_kg = self.get_node_masses_kg(
+            cross_sectional_area, component_length);
+        let wall_mass_kg: f64 = node_masses_kg.iter().sum();
+
+        // conductance between each node and the next one out
+        let node_centre_radii: Vec<f64> = boundary_radii.windows(2).
+            map(|radii| 0.5 * (radii[0] + radii[1])).
+            collect();
+        let internode_conductances: Vec<f64> = node_centre_radii.windows(2).
+            map(|radii| 1.0 / self.get_conduction_resistance(
+                    radii[0], radii[1], length)).
+            collect();
+
+        // lower, main and upper diagonals and right hand side
+        let mut lower_diagonal = vec![0.0; number_of_radial_nodes];
+        let mut main_diagonal = vec![0.0; number_of_radial_nodes];
+        let mut upper_diagonal = vec![0.0; number_of_radial_nodes];
+        let mut right_hand_side = vec![0.0; number_of_radial_nodes];
+
+        for node_index in 0..number_of_radial_nodes {
+
+            let storage_coefficient = node_masses_kg[node_index] * 
+                self.parameters.get_specific_heat_capacity_joule_per_kg_kelvin() / 
+                timestep_seconds;
+
+            let node_heat_input_watts = if wall_mass_kg > 0.0 {
+                self.heat_input_watts * node_masses_kg[node_index] / wall_mass_kg
+            } else {
+                0.0
+            };
+
+            main_diagonal[node_index] = storage_coefficient;
+            right_hand_side[node_index] = storage_coefficient * 
+                get_temperature_degrees_c(self.node_temperatures[node_index]) + 
+                node_heat_input_watts;
+
+            if node_index == 0 {
+                main_diagonal[node_index] += fluid_conductance_watts_per_kelvin;
+                right_hand_side[node_index] += fluid_conductance_watts_per_kelvin * 
+                    get_temperature_degrees_c(fluid_temperature);
+            } else {
+                let conductance = internode_conductances[node_index - 1];
+                main_diagonal[node_index] += conductance;
+                lower_diagonal[node_index] = -conductance;
+            }
+
+            if node_index + 1 == number_of_radial_nodes {
+                main_diagonal[node_index] += ambient_conductance_watts_per_kelvin;
+                right_hand_side[node_index] += ambient_conductance_watts_per_kelvin * 
+                    get_temperature_degrees_c(ambient_temperature);
+            } else {
+                let conductance = internode_conductances[node_index];
+                main_diagonal[node_index] += conductance;
+                upper_diagonal[node_index] = -conductance;
+            }
+        }
+
+        // a wall with no mass and no connections can't change 
+        // temperature
+        if main_diagonal.iter().any(|coefficient| !(*coefficient > 0.0)) {
+            return;
+        }
+
+        // thomas algorithm, forward sweep then back substitution
+        for node_index in 1..number_of_radial_nodes {
+            let factor = lower_diagonal[node_index] / main_diagonal[node_index - 1];
+            main_diagonal[node_index] -= factor * upper_diagonal[node_index - 1];
+            right_hand_side[node_index] -= factor * right_hand_side[node_index - 1];
+        }
+
+        let mut new_temperatures_degrees_c = vec![0.0; number_of_radial_nodes];
+
+        for node_index in (0..number_of_radial_nodes).rev() {
+            let outer_contribution = if node_index + 1 < number_of_radial_nodes {
+                upper_diagonal[node_index] * new_temperatures_degrees_c[node_index + 1]
+            } else {
+                0.0
+            };
+
+            new_temperatures_degrees_c[node_index] = 
+                (right_hand_side[node_index] - outer_contribution) / 
+                main_diagonal[node_index];
+        }
+
+        for (node_temperature, new_temperature_degrees_c) in 
+            self.node_temperatures.iter_mut().zip(new_temperatures_degrees_c) {
+            *node_temperature = ThermodynamicTemperature::new::<degree_celsius>(
+                new_temperature_degrees_c);
+        }
+    }
+}
+
+/// returns the Nusselt number for flow inside a tube 
+///
+/// laminar flow uses the fully developed constant heat flux 
+/// value, turbulent flow uses the Gnielinski correlation, and 
+/// the transition region is interpolated between the two
+pub fn get_fluid_wall_nusselt_number(reynolds: f64, prandtl: f64) -> f64 {
+
+    let gnielinski_nusselt_number = |reynolds: f64| -> f64 {
+        let darcy_friction_factor = (0.790 * reynolds.ln() - 1.64).powi(-2);
+
+        return (darcy_friction_factor / 8.0) * (reynolds - 1000.0) * prandtl / 
+            (1.0 + 12.7 * (darcy_friction_factor / 8.0).sqrt() * 
+             (prandtl.powf(2.0/3.0) - 1.0));
+    };
+
+    let reynolds = reynolds.abs();
+
+    if reynolds <= LAMINAR_REYNOLDS_NUMBER_LIMIT {
+        return LAMINAR_NUSSELT_NUMBER;
+    }
+
+    if reynolds >= TURBULENT_REYNOLDS_NUMBER_LIMIT {
+        return gnielinski_nusselt_number(reynolds).max(LAMINAR_NUSSELT_NUMBER);
+    }
+
+    let turbulent_nusselt_number = 
+        gnielinski_nusselt_number(TURBULENT_REYNOLDS_NUMBER_LIMIT).
+        max(LAMINAR_NUSSELT_NUMBER);
+
+    let transition_fraction = (reynolds - LAMINAR_REYNOLDS_NUMBER_LIMIT) / 
+        (TURBULENT_REYNOLDS_NUMBER_LIMIT - LAMINAR_REYNOLDS_NUMBER_LIMIT);
+
+    return LAMINAR_NUSSELT_NUMBER + 
+        transition_fraction * (turbulent_nusselt_number - LAMINAR_NUSSELT_NUMBER);
+}
+
+/// returns the swirl number of flow through a tube with a 
+/// twisted-tape insert (Manglik and Bergles, 1993)
+///
+/// Sw = Re_s / sqrt(y), where Re_s is the Reynolds number based 
+/// on the swirl velocity, y is the twist ratio (length of a 
+/// 180 degree twist over the tube diameter) and the tape 
+/// thickness ratio is the tape thickness over the tube diameter
+pub fn get_twisted_tape_swirl_number(reynolds: f64, 
+                                     twist_ratio: f64,
+                                     tape_thickness_ratio: f64) -> f64 {
+
+    let swirl_reynolds = reynolds.abs() * 
+        (PI / (PI - 4.0 * tape_thickness_ratio)) * 
+        (1.0 + (PI / (2.0 * twist_ratio)).powi(2)).sqrt();
+
+    return swirl_reynolds / twist_ratio.sqrt();
+}
+
+/// returns the Nusselt number for flow inside a tube with a 
+/// twisted-tape insert (Manglik and Bergles, 1993)
+///
+/// laminar flow uses the swirl flow correlation without the 
+/// entry length and buoyancy terms (fully developed, forced 
+/// convection), turbulent flow uses the Dittus-Boelter form 
+/// with the twisted-tape enhancement factors, and the 
+/// transition region is interpolated between the two
+pub fn get_twisted_tape_nusselt_number(reynolds: f64, 
+                                       prandtl: f64,
+                                       twist_ratio: f64,
+                                       tape_thickness_ratio: f64) -> f64 {
+
+    let laminar_nusselt_number = |reynolds: f64| -> f64 {
+        let swirl_number = get_twisted_tape_swirl_number(
+            reynolds, twist_ratio, tape_thickness_ratio);
+
+        return 4.612 * (1.0 + 6.413e-9 * 
+                        (swirl_number * prandtl.powf(0.391)).powf(3.835)).powf(0.2);
+    };
+
+    let turbulent_nusselt_number = |reynolds: f64| -> f64 {
+        let blockage_factor = PI / (PI - 4.0 * tape_thickness_ratio);
+        let wetted_perimeter_factor = (PI + 2.0 - 2.0 * tape_thickness_ratio) / 
+            (PI - 4.0 * tape_thickness_ratio);
+
+        return 0.023 * reynolds.powf(0.8) * prandtl.powf(0.4) * 
+            (1.0 + 0.769 / twist_ratio) * 
+            blockage_factor.powf(0.8) * wetted_perimeter_factor.powf(0.2);
+    };
+
+    let reynolds = reynolds.abs();
+
+    if reynolds <= LAMINAR_REYNOLDS_NUMBER_LIMIT {
+        return laminar_nusselt_number(reynolds);
+    }
+
+    if reynolds >= TURBULENT_REYNOLDS_NUMBER_LIMIT {
+        return turbulent_nusselt_number(reynolds);
+    }
+
+    let laminar_limit_nusselt_number = 
+        laminar_nusselt_number(LAMINAR_REYNOLDS_NUMBER_LIMIT);
+    let turbulent_limit_nusselt_number = 
+        turbulent_nusselt_number(TURBULENT_REYNOLDS_NUMBER_LIMIT);
+
+    let transition_fraction = (reynolds - LAMINAR_REYNOLDS_NUMBER_LIMIT) / 
+        (TURBULENT_REYNOLDS_NUMBER_LIMIT - LAMINAR_REYNOLDS_NUMBER_LIMIT);
+
+    return laminar_limit_nusselt_number + transition_fraction * 
+        (turbulent_limit_nusselt_number - laminar_limit_nusselt_number);
+}
+
+/// the correlation used for the Nusselt number between the 
+/// working fluid and the wall of a component
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum HeatTransferCorrelation {
+    /// bare tube (see get_fluid_wall_nusselt_number)
+    Tube,
+    /// tube with a twisted-tape insert 
+    /// (see get_twisted_tape_nusselt_number)
+    TwistedTape {
+        twist_ratio: f64,
+        tape_thickness_ratio: f64,
+    },
+}
+
+impl Default for HeatTransferCorrelation {
+    fn default() -> Self {
+        return Self::Tube;
+    }
+}
+
+impl HeatTransferCorrelation {
+
+    /// returns the Nusselt number at the given Reynolds and 
+    /// Prandtl numbers
+    pub fn get_nusselt_number(&self, reynolds: f64, prandtl: f64) -> f64 {
+        match *self {
+            Self::Tube => 
+                get_fluid_wall_nusselt_number(reynolds, prandtl),
+            Self::TwistedTape { twist_ratio, tape_thickness_ratio } => 
+                get_twisted_tape_nusselt_number(
+                    reynolds, prandtl, twist_ratio, tape_thickness_ratio),
+        }
+    }
+}
+
+/// returns the heat transfer coefficient (W/(m^2 K)) between 
+/// the working fluid and the wall of a component
+///
+/// h = Nu k / D_h, with Re = |m| D_h / (A mu) and 
+/// Pr = mu cp / k
+pub fn calculate_fluid_wall_heat_transfer_coefficient(
+    mass_flowrate: MassRate,
+    cross_sectional_area: Area,
+    hydraulic_diameter: Length,
+    fluid_properties: &dyn FluidProperties,
+    fluid_temp: ThermodynamicTemperature,
+    heat_transfer_correlation: HeatTransferCorrelation) -> f64 {
+
+    let hydraulic_diameter = hydraulic_diameter.value.abs();
+    let cross_sectional_area = cross_sectional_area.value.abs();
+    let viscosity = fluid_properties.get_viscosity(fluid_temp).value;
+
+    if hydraulic_diameter <= 0.0 || cross_sectional_area <= 0.0 || viscosity <= 0.0 {
+        return 0.0;
+    }
+
+    let thermal_conductivity = fluid_properties.
+        get_thermal_conductivity_watts_per_meter_kelvin(fluid_temp);
+    let specific_heat_capacity = fluid_properties.
+        get_specific_heat_capacity_joule_per_kg_kelvin(fluid_temp);
+
+    let reynolds = mass_flowrate.value.abs() * hydraulic_diameter / 
+        (cross_sectional_area * viscosity);
+    let prandtl = viscosity * specific_heat_capacity / thermal_conductivity;
+
+    return heat_transfer_correlation.get_nusselt_number(reynolds, prandtl) * 
+        thermal_conductivity / hydraulic_diameter;
+}
@@ -0,0 +1,422 @@
+// This application was developed for use in my PhD thesis under supervision 
+// of Professor Per F. Peterson. It is part of a thermal hydraulics
+// library in Rust that is released under the GNU General Public License
+// v 3.0. This is partly due to the fact that some of the libraries 
+// inherit from GeN-Foam and OpenFOAM, both licensed under GNU General
+// Public License v3.0.
+//
+// As such, the entire library is released under GNU GPL v3.0. It is a strong 
+// copyleft license which means you cannot use it in proprietary software.
+//
+//
+// License
+//    This application is part ciet_digital_twin_rust which uses
+//    fluid_mechanics_rust, a partial library of the
+//    thermal hydraulics library written in rust meant to help with the
+//    fluid mechanics aspects of the calculations
+//     
+//    Copyright (C) 2022-2023  Theodore Kay Chen Ong, Singapore Nuclear
+//    Research and Safety Initiative, Per F. Peterson, University of 
+//    California, Berkeley Thermal Hydraulics Laboratory
+//
+//    ciet_digital_twin_rust is free software; you can redistribute it and/or modify it
+//    under the terms of the GNU General Public License as published by the
+//    Free Software Foundation; either version 2 of the License, or (at your
+//    option) any later version.
+//
+//    ciet_digital_twin_rust is distributed in the hope that it will be useful, but WITHOUT
+//    ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or
+//    FITNESS FOR A PARTICULAR PURPOSE.  See the GNU General Public License
+//    for more details.
+//
+//    ciet_digital_twin_rust relies on a thermal hydraulics library
+//    also written in rust. The isothermal digital twin in particular
+//    relies on fluid_mechanics_rust.
+//    This library is part of a thermal hydraulics library in rust
+//    and contains some code copied from GeN-Foam, and OpenFOAM derivative.
+//    This offering is not approved or endorsed by the OpenFOAM Foundation nor
+//    OpenCFD Limited, producer and distributor of the OpenFOAM(R)software via
+//    www.openfoam.com, and owner of the OPENFOAM(R) and OpenCFD(R) trademarks.
+//    Nor is it endorsed by the authors and owners of GeN-Foam.
+//
+//    You should have received a copy of the GNU General Public License
+//    along with this program.  If not, see <http://www.gnu.org/licenses/>.
+//
+// © All rights reserved. Theodore Kay Chen Ong,
+// Singapore Nuclear Research and Safety Initiative,
+// Per F. Peterson,
+// University of California, Berkeley Thermal Hydraulics Laboratory
+//
+// Main author of the code: Theodore Kay Chen Ong, supervised by
+// Professor Per F. Peterson
+extern crate fluid_mechanics_rust;
+use fluid_mechanics_rust::prelude::*;
+
+use std::time::Duration;
+
+use crate::energy_equation::get_temperature_degrees_c;
+
+/// number of delayed neutron precursor groups
+pub const NUMBER_OF_DELAYED_NEUTRON_GROUPS: usize = 6;
+
+/// number of decay heat groups
+pub const NUMBER_OF_DECAY_HEAT_GROUPS: usize = 3;
+
+/// kinetics data for the point kinetics model 
+///
+/// reactivities are in absolute units (delta k / k), divide by
+/// the total delayed neutron fraction to get dollars
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PointKineticsParameters {
+    pub delayed_neutron_fractions: [f64; NUMBER_OF_DELAYED_NEUTRON_GROUPS],
+    pub precursor_decay_constants_per_second: [f64; NUMBER_OF_DELAYED_NEUTRON_GROUPS],
+    pub prompt_neutron_generation_time_seconds: f64,
+
+    /// fraction of the fission power which is deposited later 
+    /// as decay heat, per group
+    pub decay_heat_fractions: [f64; NUMBER_OF_DECAY_HEAT_GROUPS],
+    pub decay_heat_decay_constants_per_second: [f64; NUMBER_OF_DECAY_HEAT_GROUPS],
+
+    /// reactivity per kelvin change in the heater fluid 
+    /// temperature from its reference
+    pub coolant_temperature_coefficient_per_kelvin: f64,
+    /// reactivity per kelvin change in the heater wall (which 
+    /// stands in for the fuel) temperature from its reference
+    pub fuel_temperature_coefficient_per_kelvin: f64,
+
+    /// reactivity inserted by the control rods on a scram,
+    /// this should be negative
+    pub scram_reactivity: f64,
+}
+
+impl PointKineticsParameters {
+
+    /// returns kinetics data for a thermal spectrum U-235 
+    /// fuelled salt cooled reactor, which ciet's heater stands
+    /// in for
+    ///
+    /// the delayed neutron data is Keepin's six group data for 
+    /// thermal fission of U-235, the decay heat groups roughly 
+    /// follow the ANS decay heat curve over the first few hours, 
+    /// and the feedback coefficients are typical of a graphite 
+    /// moderated pebble bed core. None of these are fitted to a
+    /// particular design
+    pub fn ciet_default() -> Self {
+        return Self { 
+            delayed_neutron_fractions: 
+                [0.000215, 0.001424, 0.001274, 0.002568, 0.000748, 0.000273], 
+            precursor_decay_constants_per_second: 
+                [0.0124, 0.0305, 0.111, 0.301, 1.14, 3.01], 
+            prompt_neutron_generation_time_seconds: 4.0e-4, 
+            decay_heat_fractions: [0.030, 0.020, 0.015], 
+            decay_heat_decay_constants_per_second: [5.0e-2, 2.0e-3, 5.0e-5], 
+            coolant_temperature_coefficient_per_kelvin: -1.0e-5, 
+            fuel_temperature_coefficient_per_kelvin: -3.0e-5, 
+            scram_reactivity: -0.05,
+        };
+    }
+
+    /// returns the total delayed neutron fraction (beta), one
+    /// dollar of reactivity
+    pub fn get_total_delayed_neutron_fraction(&self) -> f64 {
+        return self.delayed_neutron_fractions.iter().sum();
+    }
+
+    /// returns the fraction of the fission power which is 
+    /// deposited later as decay heat
+    pub fn get_total_decay_heat_fraction(&self) -> f64 {
+        return self.decay_heat_fractions.iter().sum();
+    }
+}
+
+/// point kinetics model of a reactor core, which supplies the 
+/// power of ciet's heater in place of the operator
+///
+/// dn/dt = (rho - beta)/Lambda n + sum(lambda_i C_i)
+///
+/// dC_i/dt = beta_i/Lambda n - lambda_i C_i
+///
+/// n is the neutron population relative to that at the 
+/// nominal power, and the fission power is n times the nominal
+/// power. A fraction of the fission power is held back in the 
+/// decay heat groups and deposited as they decay:
+///
+/// dD_j/dt = lambda_j (gamma_j P_fission - D_j)
+///
+/// P = (1 - sum(gamma_j)) P_fission + sum(D_j)
+///
+/// the reactivity is the external (operator) reactivity, the 
+/// feedback from the heater fluid and wall temperatures, and 
+/// the scram reactivity once scrammed
+///
+/// the equations are stiff (Lambda is under a millisecond), so
+/// they are advanced with backward euler, the temperatures are 
+/// held at their values at the start of the timestep
+#[derive(Debug, Clone, PartialEq)]
+pub struct PointKinetics {
+    parameters: PointKineticsParameters,
+    nominal_power_watts: f64,
+
+    relative_neutron_population: f64,
+    // precursor concentrations relative to the neutron 
+    // population at nominal power
+    precursor_concentrations: [f64; NUMBER_OF_DELAYED_NEUTRON_GROUPS],
+    decay_heat_watts: [f64; NUMBER_OF_DECAY_HEAT_GROUPS],
+
+    reference_coolant_temperature: ThermodynamicTemperature,
+    reference_fuel_temperature: ThermodynamicTemperature,
+
+    external_reactivity: f64,
+    feedback_reactivity: f64,
+    scrammed: bool,
+}
+
+impl PointKinetics {
+
+    /// constructor, the core starts critical at the nominal 
+    /// power, with the precursors and decay heat in equilibrium
+    ///
+    /// temperature feedback is zero at the reference 
+    /// temperatures given
+    pub fn new(parameters: PointKineticsParameters,
+               nominal_power_watts: f64,
+               reference_coolant_temperature: ThermodynamicTemperature,
+               reference_fuel_temperature: ThermodynamicTemperature) -> Self {
+
+        let mut point_kinetics = Self { 
+            parameters: parameters, 
+            nominal_power_watts: nominal_power_watts.max(0.0), 
+            relative_neutron_population: 1.0, 
+            precursor_concentrations: [0.0; NUMBER_OF_DELAYED_NEUTRON_GROUPS], 
+            decay_heat_watts: [0.0; NUMBER_OF_DECAY_HEAT_GROUPS], 
+            reference_coolant_temperature: reference_coolant_temperature, 
+            reference_fuel_temperature: reference_fuel_temperature, 
+            external_reactivity: 0.0, 
+            feedback_reactivity: 0.0, 
+            scrammed: false,
+        };
+
+        point_kinetics.reset_to_equilibrium();
+
+        return point_kinetics;
+    }
+
+    /// puts the precursors and decay heat in equilibrium with 
+    /// the current neutron population
+    pub fn reset_to_equilibrium(&mut self){
+
+        let prompt_neutron_generation_time_seconds = 
+            self.parameters.prompt_neutron_generation_time_seconds;
+
+        for group_index in 0..NUMBER_OF_DELAYED_NEUTRON_GROUPS {
+            self.precursor_concentrations[group_index] = 
+                self.parameters.delayed_neutron_fractions[group_index] * 
+                self.relative_neutron_population / 
+                (prompt_neutron_generation_time_seconds * 
+                 self.parameters.precursor_decay_constants_per_second[group_index]);
+        }
+
+        let fission_power_watts = self.get_fission_power_watts();
+
+        for group_index in 0..NUMBER_OF_DECAY_HEAT_GROUPS {
+            self.decay_heat_watts[group_index] = 
+                self.parameters.decay_heat_fractions[group_index] * 
+                fission_power_watts;
+        }
+    }
+
+    pub fn get_parameters(&self) -> PointKineticsParameters {
+        return self.parameters;
+    }
+
+    pub fn get_nominal_power_watts(&self) -> f64 {
+        return self.nominal_power_watts;
+    }
+
+    /// returns the neutron population relative to that at the
+    /// nominal power
+    pub fn get_relative_neutron_population(&self) -> f64 {
+        return self.relative_neutron_population;
+    }
+
+    /// returns the power from fission (watts), part of which 
+    /// is held back as decay heat
+    pub fn get_fission_power_watts(&self) -> f64 {
+        return self.relative_neutron_population * self.nominal_power_watts;
+    }
+
+    /// returns the power deposited by the decay heat groups 
+    /// (watts)
+    pub fn get_decay_heat_watts(&self) -> f64 {
+        return self.decay_heat_watts.iter().sum();
+    }
+
+    /// returns the total power deposited in the heater (watts), 
+    /// this is the prompt part of the fission power plus the 
+    /// decay heat
+    pub fn get_power_watts(&self) -> f64 {
+        return (1.0 - self.parameters.get_total_decay_heat_fraction()) * 
+            self.get_fission_power_watts() + 
+            self.get_decay_heat_watts();
+    }
+
+    /// sets the reactivity inserted by the operator, eg. by 
+    /// moving the control rods (absolute units)
+    pub fn set_external_reactivity(&mut self, external_reactivity: f64){
+        self.external_reactivity = external_reactivity;
+    }
+
+    pub fn get_external_reactivity(&self) -> f64 {
+        return self.external_reactivity;
+    }
+
+    /// sets the external reactivity in dollars
+    pub fn set_external_reactivity_dollars(&mut self, external_reactivity_dollars: f64){
+        self.external_reactivity = external_reactivity_dollars * 
+            self.parameters.get_total_delayed_neutron_fraction();
+    }
+
+    pub fn get_external_reactivity_dollars(&self) -> f64 {
+        return self.external_reactivity / 
+            self.parameters.get_total_delayed_neutron_fraction();
+    }
+
+    /// drops the control rods, inserting the scram reactivity
+    /// until the scram is reset
+    pub fn scram(&mut self){
+        self.scrammed = true;
+    }
+
+    /// withdraws the control rods after a scram
+    pub fn reset_scram(&mut self){
+        self.scrammed = false;
+    }
+
+    pub fn is_scrammed(&self) -> bool {
+        return self.scrammed;
+    }
+
+    /// returns the temperature feedback reactivity at the 
+    /// given heater fluid and wall temperatures
+    pub fn get_feedback_reactivity(
+        &self,
+        coolant_temperature: ThermodynamicTemperature,
+        fuel_temperature: ThermodynamicTemperature) -> f64 {
+
+        let coolant_temperature_change = 
+            get_temperature_degrees_c(coolant_temperature) - 
+            get_temperature_degrees_c(self.reference_coolant_temperature);
+
+        let fuel_temperature_change = 
+            get_temperature_degrees_c(fuel_temperature) - 
+            get_temperature_degrees_c(self.reference_fuel_temperature);
+
+        return self.parameters.coolant_temperature_coefficient_per_kelvin * 
+            coolant_temperature_change + 
+            self.parameters.fuel_temperature_coefficient_per_kelvin * 
+            fuel_temperature_change;
+    }
+
+    /// returns the total reactivity at the start of the last 
+    /// timestep (absolute units)
+    pub fn get_total_reactivity(&self) -> f64 {
+
+        let scram_reactivity = if self.scrammed {
+            self.parameters.scram_reactivity
+        } else {
+            0.0
+        };
+
+        return self.external_reactivity + self.feedback_reactivity + scram_reactivity;
+    }
+
+    pub fn get_total_reactivity_dollars(&self) -> f64 {
+        return self.get_total_reactivity() / 
+            self.parameters.get_total_delayed_neutron_fraction();
+    }
+
+    /// advances the neutron population, precursors and decay 
+    /// heat over a timestep, with feedback from the given heater
+    /// fluid and wall temperatures
+    pub fn advance(&mut self,
+                   coolant_temperature: ThermodynamicTemperature,
+                   fuel_temperature: ThermodynamicTemperature,
+                   timestep: Duration){
+
+        let timestep_seconds = timestep.as_secs_f64();
+
+        if timestep_seconds <= 0.0 {
+            return;
+        }
+
+        self.feedback_reactivity = self.get_feedback_reactivity(
+            coolant_temperature, fuel_temperature);
+
+        let reactivity = self.get_total_reactivity();
+        let total_delayed_neutron_fraction = 
+            self.parameters.get_total_delayed_neutron_fraction();
+        let prompt_neutron_generation_time_seconds = 
+            self.parameters.prompt_neutron_generation_time_seconds;
+
+        // backward euler gives 
+        // C_i_new = (C_i_old + dt beta_i/Lambda n_new)/(1 + dt lambda_i)
+        // which i substitute into the neutron population 
+        // equation to solve for n_new first
+        let mut neutron_population_coefficient = 1.0 - 
+            timestep_seconds * (reactivity - total_delayed_neutron_fraction) / 
+            prompt_neutron_generation_time_seconds;
+        let mut neutron_population_source = self.relative_neutron_population;
+
+        for group_index in 0..NUMBER_OF_DELAYED_NEUTRON_GROUPS {
+
+            let decay_constant = 
+                self.parameters.precursor_decay_constants_per_second[group_index];
+            let decay_denominator = 1.0 + timestep_seconds * decay_constant;
+
+            neutron_population_coefficient -= 
+                timestep_seconds * decay_constant * timestep_seconds * 
+                self.parameters.delayed_neutron_fractions[group_index] / 
+                (prompt_neutron_generation_time_seconds * decay_denominator);
+
+            neutron_population_source += 
+                timestep_seconds * decay_constant * 
+                self.precursor_concentrations[group_index] / decay_denominator;
+        }
+
+        // the population can't go negative, even for very large
+        // timesteps with large positive reactivity
+        let new_relative_neutron_population = 
+            (neutron_population_source / neutron_population_coefficient).max(0.0);
+
+        for group_index in 0..NUMBER_OF_DELAYED_NEUTRON_GROUPS {
+
+            let decay_constant = 
+                self.parameters.precursor_decay_constants_per_second[group_index];
+
+            self.precursor_concentrations[group_index] = 
+                (self.precursor_concentrations[group_index] + 
+                 timestep_seconds * 
+                 self.parameters.delayed_neutron_fractions[group_index] / 
+                 prompt_neutron_generation_time_seconds * 
+                 new_relative_neutron_population) / 
+                (1.0 + timestep_seconds * decay_constant);
+        }
+
+        self.relative_neutron_population = new_relative_neutron_population;
+
+        let fission_power_watts = self.get_fission_power_watts();
+
+        for group_index in 0..NUMBER_OF_DECAY_HEAT_GROUPS {
+
+            let decay_constant = 
+                self.parameters.decay_heat_decay_constants_per_second[group_index];
+
+            self.decay_heat_watts[group_index] = 
+                (self.decay_heat_watts[group_index] + 
+                 timestep_seconds * decay_constant * 
+                 self.parameters.decay_heat_fractions[group_index] * 
+                 fission_power_watts) / 
+                (1.0 + timestep_seconds * decay_constant);
+        }
+    }
+}
@@ -0,0 +1,295 @@
+// This application was developed for use in my PhD thesis under supervision 
+// of Professor Per F. Peterson. It is part of a thermal hydraulics
+// library in Rust that is released under the GNU General Public License
+// v 3.0. This is partly due to the fact that some of the libraries 
+// inherit from GeN-Foam and OpenFOAM, both licensed under GNU General
+// Public License v3.0.
+//
+// As such, the entire library is released under GNU GPL v3.0. It is a strong 
+// copyleft license which means you cannot use it in proprietary software.
+//
+//
+// License
+//    This application is part ciet_digital_twin_rust which uses
+//    fluid_mechanics_rust, a partial library of the
+//    thermal hydraulics library written in rust meant to help with the
+//    fluid mechanics aspects of the calculations
+//     
+//    Copyright (C) 2022-2023  Theodore Kay Chen Ong, Singapore Nuclear
+//    Research and Safety Initiative, Per F. Peterson, University of 
+//    California, Berkeley Thermal Hydraulics Laboratory
+//
+//    ciet_digital_twin_rust is free software; you can redistribute it and/or modify it
+//    under the terms of the GNU General Public License as published by the
+//    Free Software Foundation; either version 2 of the License, or (at your
+//    option) any later version.
+//
+//    ciet_digital_twin_rust is distributed in the hope that it will be useful, but WITHOUT
+//    ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or
+//    FITNESS FOR A PARTICULAR PURPOSE.  See the GNU General Public License
+//    for more details.
+//
+//    ciet_digital_twin_rust relies on a thermal hydraulics library
+//    also written in rust. The isothermal digital twin in particular
+//    relies on fluid_mechanics_rust.
+//    This library is part of a thermal hydraulics library in rust
+//    and contains some code copied from GeN-Foam, and OpenFOAM derivative.
+//    This offering is not approved or endorsed by the OpenFOAM Foundation nor
+//    OpenCFD Limited, producer and distributor of the OpenFOAM(R)software via
+//    www.openfoam.com, and owner of the OPENFOAM(R) and OpenCFD(R) trademarks.
+//    Nor is it endorsed by the authors and owners of GeN-Foam.
+//
+//    You should have received a copy of the GNU General Public License
+//    along with this program.  If not, see <http://www.gnu.org/licenses/>.
+//
+// © All rights reserved. Theodore Kay Chen Ong,
+// Singapore Nuclear Research and Safety Initiative,
+// Per F. Peterson,
+// University of California, Berkeley Thermal Hydraulics Laboratory
+//
+// Main author of the code: Theodore Kay Chen Ong, supervised by
+// Professor Per F. Peterson
+extern crate fluid_mechanics_rust;
+use fluid_mechanics_rust::prelude::*;
+
+use std::time::Duration;
+
+use crate::therminol_component::TherminolCustomComponent;
+
+/// a quadratic head versus flow curve for a centrifugal pump 
+/// at its rated speed
+///
+/// pressure_rise = shutoff_pressure - k * m |m|
+///
+/// where k is chosen so that the curve passes through the 
+/// rated point. At other speeds, the affinity laws are used:
+///
+/// m_2 / m_1 = N_2 / N_1 
+/// pressure_rise_2 / pressure_rise_1 = (N_2 / N_1)^2
+///
+/// which for this curve shape means only the shutoff pressure 
+/// scales with speed squared. At zero speed, the pump behaves 
+/// like a flow resistance. The m |m| term keeps that 
+/// resistance opposing the flow when flow is reversed
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PumpCurve {
+    rated_speed_rpm: f64,
+    shutoff_pressure: Pressure,
+    rated_mass_flowrate: MassRate,
+    rated_pressure: Pressure,
+}
+
+impl PumpCurve {
+
+    /// constructor, the curve passes through the shutoff pressure
+    /// at zero flow and the rated point at rated flow, 
+    /// both at the rated speed
+    pub fn new(rated_speed_rpm: f64,
+               shutoff_pressure: Pressure,
+               rated_mass_flowrate: MassRate,
+               rated_pressure: Pressure) -> Self {
+
+        return Self { 
+            rated_speed_rpm: rated_speed_rpm, 
+            shutoff_pressure: shutoff_pressure, 
+            rated_mass_flowrate: rated_mass_flowrate, 
+            rated_pressure: rated_pressure,
+        };
+    }
+
+    /// returns a curve for the ctah pump
+    ///
+    /// the shutoff pressure is matched to the 45 kPa limit used
+    /// by the opcua server, the rated point is illustrative and 
+    /// has not been fitted to pump data yet
+    pub fn ctah_pump_curve() -> Self {
+        return Self::new(
+            2900.0,
+            Pressure::new::<pascal>(45000.0),
+            MassRate::new::<kilogram_per_second>(0.5),
+            Pressure::new::<pascal>(35000.0));
+    }
+
+    pub fn get_rated_speed_rpm(&self) -> f64 {
+        return self.rated_speed_rpm;
+    }
+
+    /// returns the pressure rise across the pump at a given 
+    /// mass flowrate and speed
+    pub fn get_pressure_rise(&self, 
+                             mass_flowrate: MassRate,
+                             speed_rpm: f64) -> Pressure {
+
+        let speed_ratio = speed_rpm / self.rated_speed_rpm;
+
+        // k = (shutoff - rated) / m_rated^2
+        let rated_flowrate_kg_per_s = self.rated_mass_flowrate.value;
+        let resistance_coefficient = 
+            (self.shutoff_pressure.value - self.rated_pressure.value)
+            / (rated_flowrate_kg_per_s * rated_flowrate_kg_per_s);
+
+        let mass_flowrate_kg_per_s = mass_flowrate.value;
+
+        let pressure_rise_pascals = 
+            speed_ratio * speed_ratio * self.shutoff_pressure.value
+            - resistance_coefficient * mass_flowrate_kg_per_s 
+            * mass_flowrate_kg_per_s.abs();
+
+        return Pressure::new::<pascal>(pressure_rise_pascals);
+    }
+}
+
+/// how the pump pressure rise is decided
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PumpControl {
+    /// the pump supplies a fixed pressure regardless of flow,
+    /// this is how the ctah pump has been modelled so far
+    PressureSource(Pressure),
+    /// the pump runs at a set speed (rpm), and the pressure rise 
+    /// comes from the pump curve
+    Speed(f64),
+}
+
+/// default time constant for the pump speed to approach its
+/// target speed in transients (start-up and coast-down)
+pub const DEFAULT_PUMP_SPEED_TIME_CONSTANT_SECONDS: f64 = 2.0;
+
+/// a pump within a branch
+///
+/// the hydrostatic pressure change and geometry come from the 
+/// underlying component (eg. CTAHPump, which has no friction 
+/// losses), the pressure rise comes from the pump control
+///
+/// for transients, the pump speed approaches a target speed 
+/// with a first order lag, so a pump trip (target speed zero) 
+/// gives an exponential coast-down
+pub struct CentrifugalPump {
+    component: TherminolCustomComponent<'static>,
+    pump_curve: PumpCurve,
+    control: PumpControl,
+    target_speed_rpm: f64,
+    speed_time_constant: Duration,
+}
+
+impl CentrifugalPump {
+
+    /// constructor, the pump starts as a pressure source 
+    /// supplying zero pressure
+    ///
+    /// the component's own internal pressure source is not used,
+    /// so it should be left at zero
+    pub fn new(component: TherminolCustomComponent<'static>,
+               pump_curve: PumpCurve) -> Self {
+
+        return Self { 
+            component: component, 
+            pump_curve: pump_curve, 
+            control: PumpControl::PressureSource(
+                Pressure::new::<pascal>(0.0)),
+            target_speed_rpm: 0.0,
+            speed_time_constant: Duration::from_secs_f64(
+                DEFAULT_PUMP_SPEED_TIME_CONSTANT_SECONDS),
+        };
+    }
+
+    pub fn get_name(&self) -> &str {
+        return self.component.get_name();
+    }
+
+    pub fn get_component(&self) -> &TherminolCustomComponent<'static> {
+        return &self.component;
+    }
+
+    pub fn get_component_mut(&mut self) -> &mut TherminolCustomComponent<'static> {
+        return &mut self.component;
+    }
+
+    pub fn get_pump_curve(&self) -> &PumpCurve {
+        return &self.pump_curve;
+    }
+
+    pub fn get_control(&self) -> PumpControl {
+        return self.control;
+    }
+
+    /// supplies a fixed pressure regardless of flow
+    pub fn set_pressure_source(&mut self, pump_pressure: Pressure){
+        self.control = PumpControl::PressureSource(pump_pressure);
+    }
+
+    /// runs the pump at a set speed along its curve
+    ///
+    /// the speed changes immediately, for transients use 
+    /// set_target_speed_rpm instead
+    pub fn set_speed_rpm(&mut self, speed_rpm: f64){
+        self.control = PumpControl::Speed(speed_rpm);
+        self.target_speed_rpm = speed_rpm;
+    }
+
+    /// sets the speed the pump approaches during transients,
+    /// a target of zero is a pump trip
+    ///
+    /// if the pump was a pressure source, it switches to speed 
+    /// control starting from rest
+    pub fn set_target_speed_rpm(&mut self, target_speed_rpm: f64){
+
+        if let PumpControl::PressureSource(_) = self.control {
+            self.control = PumpControl::Speed(0.0);
+        }
+
+        self.target_speed_rpm = target_speed_rpm;
+    }
+
+    pub fn get_target_speed_rpm(&self) -> f64 {
+        return self.target_speed_rpm;
+    }
+
+    pub fn set_speed_time_constant(&mut self, speed_time_constant: Duration){
+        self.speed_time_constant = speed_time_constant;
+    }
+
+    /// moves the pump speed towards the target speed over 
+    /// a timestep, this does nothing for pressure sources
+    pub fn advance_speed(&mut self, timestep: Duration){
+
+        if let PumpControl::Speed(speed_rpm) = self.control {
+
+            let time_constant_seconds = 
+                self.speed_time_constant.as_secs_f64();
+
+            // a zero time constant means the speed changes 
+            // immediately
+            let relaxation_factor = if time_constant_seconds > 0.0 {
+                1.0 - (-timestep.as_secs_f64() / time_constant_seconds).exp()
+            } else {
+                1.0
+            };
+
+            let new_speed_rpm = speed_rpm + 
+                relaxation_factor * (self.target_speed_rpm - speed_rpm);
+
+            self.control = PumpControl::Speed(new_speed_rpm);
+        }
+    }
+
+    /// returns the pressure rise supplied by the pump at a given
+    /// mass flowrate
+    pub fn get_pressure_rise(&self, mass_flowrate: MassRate) -> Pressure {
+        match self.control {
+            PumpControl::PressureSource(pump_pressure) => {
+                return pump_pressure;
+            },
+            PumpControl::Speed(speed_rpm) => {
+                return self.pump_curve.get_pressure_rise(
+                    mass_flowrate, speed_rpm);
+            },
+        }
+    }
+
+    /// returns the pressure change across the pump, including the
+    /// hydrostatic pressure change of the component
+    pub fn get_pressure_change(&self, mass_flowrate: MassRate) -> Pressure {
+        return self.component.get_pressure_change_immutable(mass_flowrate)
+            + self.get_pressure_rise(mass_flowrate);
+    }
+}
@@ -52,6 +52,14 @@
 extern crate fluid_mechanics_rust;
 use fluid_mechanics_rust::prelude::*;
 
+use crate::insulation::InsulationParameters;
+use crate::pipe_wall::{PipeWall, WallParameters, HeatTransferCorrelation};
+use crate::ciet_solver::{CIETSolverError, 
+    check_component_fluid_temperature_in_range};
+use crate::fluid_properties::{FluidProperties, TherminolVP1};
+
+use std::sync::Arc;
+
 
 
 
@@ -70,7 +78,8 @@ pub trait TherminolCustomComponentTraits<'trait_lifetime> :
 // and start implementing it
 pub struct TherminolCustomComponent<'pipe_lifetime> {
 
-    therminol_properties: TherminolVP1Properties,
+    // therminol VP-1 unless set otherwise
+    fluid_properties: Arc<dyn FluidProperties>,
     fluid_temp: ThermodynamicTemperature,
     fluid_mass_flowrate: MassRate,
 
@@ -83,11 +92,22 @@ pub struct TherminolCustomComponent<'pipe_lifetime> {
     pressure_loss: Pressure,
     absolute_roughness: Length,
     name: String,
+
+    // no insulation means no heat is lost to ambient
+    insulation: Option<InsulationParameters>,
+
+    // no wall means the fluid exchanges heat with the 
+    // insulation directly and the wall stores no heat
+    wall: Option<PipeWall>,
+
+    // bare tube unless the component has an insert
+    heat_transfer_correlation: HeatTransferCorrelation,
     
-    // these are trait object references which cannot be brought into
-    // the scope
-    custom_k: &'pipe_lifetime dyn Fn(f64) -> f64,
-    custom_darcy: &'pipe_lifetime dyn Fn(f64,f64) ->f64,
+    // these are shared trait objects so that the component can be 
+    // cloned and moved to (and shared with) the opcua server's 
+    // threads
+    custom_k: Arc<dyn Fn(f64) -> f64 + Send + Sync + 'pipe_lifetime>,
+    custom_darcy: Arc<dyn Fn(f64,f64) -> f64 + Send + Sync + 'pipe_lifetime>,
 
 }
 
@@ -119,7 +139,7 @@ for TherminolCustomComponent<'pipe_lifetime> {
     fn get_custom_darcy(&mut self) 
         -> &dyn Fn(f64, f64) -> f64 {
 
-            return self.custom_darcy.clone();
+            return &*self.custom_darcy;
 
         }
 
@@ -127,37 +147,50 @@ for TherminolCustomComponent<'pipe_lifetime> {
     fn get_custom_darcy_immutable(&self) 
         -> &dyn Fn(f64, f64) -> f64 {
 
-            return self.custom_darcy.clone();
+            return &*self.custom_darcy;
 
         }
 
     fn get_custom_k(&mut self) 
         -> &dyn Fn(f64) -> f64 {
 
-            return self.custom_k.clone();
+            return &*self.custom_k;
 
         }
 
     fn get_custom_k_immutable(&self) 
         -> &dyn Fn(f64) -> f64 {
 
-            return self.custom_k.clone();
+            return &*self.custom_k;
 
         }
 
+    /// # Panics
+    ///
+    /// always, the trait hands over a borrowed closure which is 
+    /// not Sync, so it cannot be kept in a component which the 
+    /// opcua server shares between threads, give the correlation
+    /// to TherminolCustomComponent::new instead
     fn set_custom_k(
         &mut self,
-        custom_k: &'pipe_lifetime dyn Fn(f64) -> f64){
-
-        self.custom_k = custom_k;
+        _custom_k: &'pipe_lifetime dyn Fn(f64) -> f64){
 
+        panic!("{}: custom_k must be Send + Sync, \
+               give it to TherminolCustomComponent::new", self.name);
     }
 
+    /// # Panics
+    ///
+    /// always, the trait hands over a borrowed closure which is 
+    /// not Sync, so it cannot be kept in a component which the 
+    /// opcua server shares between threads, give the correlation
+    /// to TherminolCustomComponent::new instead
     fn set_custom_darcy(
         &mut self,
-        custom_darcy: &'pipe_lifetime dyn Fn(f64,f64) -> f64){
+        _custom_darcy: &'pipe_lifetime dyn Fn(f64,f64) -> f64){
 
-        self.custom_darcy = custom_darcy;
+        panic!("{}: custom_darcy must be Send + Sync, \
+               give it to TherminolCustomComponent::new", self.name);
     }
 
 
@@ -192,10 +225,10 @@ FluidComponent for TherminolCustomComponent<'pipe_lifetime>{
 
         // i need to make some immutable borrows here...
         let custom_darcy: &dyn Fn(f64, f64) -> f64 = 
-            self.custom_darcy;
+            &*self.custom_darcy;
 
         let custom_k : &dyn Fn(f64) -> f64 =
-            self.custom_k;
+            &*self.custom_k;
 
         let pressure_loss =
             Self::
@@ -243,10 +276,10 @@ FluidComponent for TherminolCustomComponent<'pipe_lifetime>{
 
         // i need to make some immutable borrows here...
         let custom_darcy: &dyn Fn(f64, f64) -> f64 = 
-            self.custom_darcy;
+            &*self.custom_darcy;
 
         let custom_k : &dyn Fn(f64) -> f64 =
-            self.custom_k;
+            &*self.custom_k;
 
         let pressure_loss =
             Self:: fluid_custom_component_calc_pressure_loss(
@@ -307,10 +340,10 @@ FluidComponent for TherminolCustomComponent<'pipe_lifetime>{
             + internal_pressure_source;
 
         let custom_darcy : &dyn Fn(f64, f64) -> f64 = 
-            self.custom_darcy;
+            &*self.custom_darcy;
 
         let custom_k : &dyn Fn(f64) -> f64 =
-            self.custom_k;
+            &*self.custom_k;
 
 
         let cross_sectional_area = 
@@ -389,10 +422,10 @@ FluidComponent for TherminolCustomComponent<'pipe_lifetime>{
             + internal_pressure_source;
 
         let custom_darcy : &dyn Fn(f64, f64) -> f64 = 
-            self.custom_darcy;
+            &*self.custom_darcy;
 
         let custom_k : &dyn Fn(f64) -> f64 =
-            self.custom_k;
+            &*self.custom_k;
 
 
         let cross_sectional_area = 
@@ -460,7 +493,7 @@ FluidComponent for TherminolCustomComponent<'pipe_lifetime>{
 
         // then the fluid properties
 
-        let fluid_viscosity = self.therminol_properties.viscosity(fluid_temp);
+        let fluid_viscosity = self.fluid_properties.get_viscosity(fluid_temp);
         return fluid_viscosity;
 
 
@@ -474,7 +507,7 @@ FluidComponent for TherminolCustomComponent<'pipe_lifetime>{
 
         // then the fluid properties
 
-        let fluid_viscosity = self.therminol_properties.viscosity(fluid_temp);
+        let fluid_viscosity = self.fluid_properties.get_viscosity(fluid_temp);
 
         return fluid_viscosity;
 
@@ -489,7 +522,7 @@ FluidComponent for TherminolCustomComponent<'pipe_lifetime>{
 
         // then the fluid properties
 
-        let fluid_density = self.therminol_properties.density(fluid_temp);
+        let fluid_density = self.fluid_properties.get_density(fluid_temp);
 
         return fluid_density;
 
@@ -503,7 +536,7 @@ FluidComponent for TherminolCustomComponent<'pipe_lifetime>{
         let fluid_temp = self.fluid_temp;
 
         // then the fluid properties
-        let fluid_density = self.therminol_properties.density(fluid_temp);
+        let fluid_density = self.fluid_properties.get_density(fluid_temp);
 
         return fluid_density;
 
@@ -562,12 +595,12 @@ impl<'pipe_lifetime> TherminolCustomComponent<'pipe_lifetime>{
                cross_sectional_area: Area,
                hydraulic_diameter: Length,
                absolute_roughness: Length,
-               custom_k: &'pipe_lifetime dyn Fn(f64)-> f64 ,
-               custom_darcy: &'pipe_lifetime dyn Fn(f64,f64) -> f64 ) -> Self {
+               custom_k: impl Fn(f64)-> f64 + Send + Sync + 'pipe_lifetime,
+               custom_darcy: impl Fn(f64,f64) -> f64 + Send + Sync + 'pipe_lifetime) -> Self {
 
         return Self { 
             name: name.to_string(),
-            therminol_properties: TherminolVP1Properties::new(),
+            fluid_properties: Arc::new(TherminolVP1),
             fluid_temp: fluid_temp, 
             fluid_mass_flowrate: MassRate::new::<kilogram_per_second>(0.0), 
             internal_pressure: Pressure::new::<pascal>(0.0), 
@@ -577,8 +610,11 @@ impl<'pipe_lifetime> TherminolCustomComponent<'pipe_lifetime>{
             cross_sectional_area: cross_sectional_area,
             pressure_loss: Pressure::new::<pascal>(0.0),
             absolute_roughness: absolute_roughness,
-            custom_k: custom_k,
-            custom_darcy: custom_darcy,
+            insulation: None,
+            wall: None,
+            heat_transfer_correlation: HeatTransferCorrelation::Tube,
+            custom_k: Arc::new(custom_k),
+            custom_darcy: Arc::new(custom_darcy),
         };
 
     }
@@ -592,5 +628,119 @@ impl<'pipe_lifetime> TherminolCustomComponent<'pipe_lifetime>{
         self.name = name.to_string();
     }
 
+    /// gets the fluid temperature used for density and viscosity
+    pub fn get_fluid_temperature(&self) -> ThermodynamicTemperature {
+        return self.fluid_temp;
+    }
+
+    /// sets the fluid temperature, density and viscosity 
+    /// (and therefore pressure losses and hydrostatic pressure 
+    /// changes) then follow this temperature
+    pub fn set_fluid_temperature(&mut self, 
+                                 fluid_temp: ThermodynamicTemperature) {
+        self.fluid_temp = fluid_temp;
+    }
+
+    /// checks that the fluid temperature is within the range 
+    /// the fluid property correlations are validated for, 
+    /// outside it the density and viscosity are extrapolated
+    pub fn check_fluid_temperature_range(&self) -> Result<(), CIETSolverError> {
+
+        let (min_temperature_degrees_c, max_temperature_degrees_c) = 
+            self.fluid_properties.get_validated_temperature_range_degrees_c();
+
+        return check_component_fluid_temperature_in_range(
+            &self.name, 
+            self.fluid_temp, 
+            min_temperature_degrees_c, 
+            max_temperature_degrees_c);
+    }
+
+    /// sets the working fluid, by default this is therminol 
+    /// VP-1
+    pub fn set_fluid_properties(&mut self, 
+                                fluid_properties: Arc<dyn FluidProperties>) {
+        self.fluid_properties = fluid_properties;
+    }
+
+    pub fn get_fluid_properties(&self) -> &dyn FluidProperties {
+        return self.fluid_properties.as_ref();
+    }
+
+    /// returns the shared working fluid, so that other 
+    /// components can be filled with the same fluid
+    pub fn get_fluid_properties_arc(&self) -> Arc<dyn FluidProperties> {
+        return self.fluid_properties.clone();
+    }
+
+    /// wraps the component in insulation, so that it loses 
+    /// heat to ambient
+    pub fn set_insulation(&mut self, insulation: InsulationParameters) {
+        self.insulation = Some(insulation);
+    }
+
+    /// removes the insulation, the component then loses no 
+    /// heat
+    pub fn clear_insulation(&mut self) {
+        self.insulation = None;
+    }
+
+    pub fn get_insulation(&self) -> Option<InsulationParameters> {
+        return self.insulation;
+    }
+
+    /// returns the conductance UA (W/K) from the fluid to 
+    /// ambient through the insulation, zero if there is none
+    ///
+    /// if the component has a wall, this is from the outermost 
+    /// wall node instead
+    pub fn get_ambient_conductance_watts_per_kelvin(&self) -> f64 {
+        match (self.insulation, &self.wall) {
+            (Some(insulation), Some(wall)) => 
+                wall.get_ambient_conductance_watts_per_kelvin(
+                    insulation, 
+                    self.cross_sectional_area, 
+                    self.component_length),
+            (Some(insulation), None) => 
+                insulation.get_conductance_watts_per_kelvin(
+                    self.cross_sectional_area, 
+                    self.component_length),
+            (None, _) => 0.0,
+        }
+    }
+
+    /// gives the component a wall which stores heat, the wall 
+    /// starts at the fluid temperature
+    pub fn set_wall(&mut self, wall_parameters: WallParameters) {
+        self.wall = Some(PipeWall::new(wall_parameters, self.fluid_temp));
+    }
+
+    /// removes the wall, the fluid then exchanges heat with the
+    /// insulation directly
+    pub fn clear_wall(&mut self) {
+        self.wall = None;
+    }
+
+    pub fn get_wall(&self) -> Option<&PipeWall> {
+        return self.wall.as_ref();
+    }
+
+    pub fn get_wall_mut(&mut self) -> Option<&mut PipeWall> {
+        return self.wall.as_mut();
+    }
+
+    /// sets the correlation for heat transfer between the fluid
+    /// and the wall, by default the component behaves like a 
+    /// bare tube
+    pub fn set_heat_transfer_correlation(
+        &mut self, 
+        heat_transfer_correlation: HeatTransferCorrelation) {
+        self.heat_transfer_correlation = heat_transfer_correlation;
+    }
+
+    pub fn get_heat_transfer_correlation(&self) -> HeatTransferCorrelation {
+        return self.heat_transfer_correlation;
+    }
+
 }
 
@@ -52,6 +52,14 @@
 extern crate fluid_mechanics_rust;
 use fluid_mechanics_rust::prelude::*;
 
+use crate::insulation::InsulationParameters;
+use crate::pipe_wall::{PipeWall, WallParameters};
+use crate::ciet_solver::{CIETSolverError, 
+    check_component_fluid_temperature_in_range};
+use crate::fluid_properties::{FluidProperties, TherminolVP1};
+
+use std::sync::Arc;
+
 // we will implement a few properties here for our therminol pipe
 // for clarity we will list them in a
 // supertrait
@@ -72,7 +80,8 @@ pub trait TherminolPipeTraits<'trait_lifetime> :
 /// which can represent therminol pipes
 pub struct TherminolPipe {
 
-    therminol_properties: TherminolVP1Properties,
+    // therminol VP-1 unless set otherwise
+    fluid_properties: Arc<dyn FluidProperties>,
     fluid_temp: ThermodynamicTemperature,
     fluid_mass_flowrate: MassRate,
 
@@ -86,6 +95,13 @@ pub struct TherminolPipe {
     absolute_roughness: Length,
     name: String,
 
+    // no insulation means no heat is lost to ambient
+    insulation: Option<InsulationParameters>,
+
+    // no wall means the fluid exchanges heat with the 
+    // insulation directly and the wall stores no heat
+    wall: Option<PipeWall>,
+
 }
 
 impl<'pipe_lifetime> 
@@ -325,7 +341,7 @@ FluidComponent for TherminolPipe{
 
         // then the fluid properties
 
-        let fluid_viscosity = self.therminol_properties.viscosity(fluid_temp);
+        let fluid_viscosity = self.fluid_properties.get_viscosity(fluid_temp);
         return fluid_viscosity;
 
 
@@ -339,7 +355,7 @@ FluidComponent for TherminolPipe{
 
         // then the fluid properties
 
-        let fluid_viscosity = self.therminol_properties.viscosity(fluid_temp);
+        let fluid_viscosity = self.fluid_properties.get_viscosity(fluid_temp);
 
         return fluid_viscosity;
 
@@ -354,7 +370,7 @@ FluidComponent for TherminolPipe{
 
         // then the fluid properties
 
-        let fluid_density = self.therminol_properties.density(fluid_temp);
+        let fluid_density = self.fluid_properties.get_density(fluid_temp);
 
         return fluid_density;
 
@@ -368,7 +384,7 @@ FluidComponent for TherminolPipe{
         let fluid_temp = self.fluid_temp;
 
         // then the fluid properties
-        let fluid_density = self.therminol_properties.density(fluid_temp);
+        let fluid_density = self.fluid_properties.get_density(fluid_temp);
 
         return fluid_density;
 
@@ -431,7 +447,7 @@ impl<'pipe_lifetime> TherminolPipe{
 
         return Self { 
             name: name.to_string(),
-            therminol_properties: TherminolVP1Properties::new(),
+            fluid_properties: Arc::new(TherminolVP1),
             fluid_temp: fluid_temp, 
             fluid_mass_flowrate: MassRate::new::<kilogram_per_second>(0.0), 
             internal_pressure: Pressure::new::<pascal>(0.0), 
@@ -441,6 +457,8 @@ impl<'pipe_lifetime> TherminolPipe{
             pressure_loss: Pressure::new::<pascal>(0.0),
             form_loss_k: form_loss_k ,
             absolute_roughness: absolute_roughness,
+            insulation: None,
+            wall: None,
         };
 
 
@@ -455,5 +473,106 @@ impl<'pipe_lifetime> TherminolPipe{
 
         self.name = name.to_string();
     }
+
+    /// gets the fluid temperature used for density and viscosity
+    pub fn get_fluid_temperature(&self) -> ThermodynamicTemperature {
+        return self.fluid_temp;
+    }
+
+    /// sets the fluid temperature, density and viscosity 
+    /// (and therefore pressure losses and hydrostatic pressure 
+    /// changes) then follow this temperature
+    pub fn set_fluid_temperature(&mut self, 
+                                 fluid_temp: ThermodynamicTemperature) {
+        self.fluid_temp = fluid_temp;
+    }
+
+    /// checks that the fluid temperature is within the range 
+    /// the fluid property correlations are validated for, 
+    /// outside it the density and viscosity are extrapolated
+    pub fn check_fluid_temperature_range(&self) -> Result<(), CIETSolverError> {
+
+        let (min_temperature_degrees_c, max_temperature_degrees_c) = 
+            self.fluid_properties.get_validated_temperature_range_degrees_c();
+
+        return check_component_fluid_temperature_in_range(
+            &self.name, 
+            self.fluid_temp, 
+            min_temperature_degrees_c, 
+            max_temperature_degrees_c);
+    }
+
+    /// sets the working fluid, by default this is therminol 
+    /// VP-1
+    pub fn set_fluid_properties(&mut self, 
+                                fluid_properties: Arc<dyn FluidProperties>) {
+        self.fluid_properties = fluid_properties;
+    }
+
+    pub fn get_fluid_properties(&self) -> &dyn FluidProperties {
+        return self.fluid_properties.as_ref();
+    }
+
+    /// returns the shared working fluid, so that other 
+    /// components can be filled with the same fluid
+    pub fn get_fluid_properties_arc(&self) -> Arc<dyn FluidProperties> {
+        return self.fluid_properties.clone();
+    }
+
+    /// wraps the component in insulation, so that it loses 
+    /// heat to ambient
+    pub fn set_insulation(&mut self, insulation: InsulationParameters) {
+        self.insulation = Some(insulation);
+    }
+
+    /// removes the insulation, the component then loses no 
+    /// heat
+    pub fn clear_insulation(&mut self) {
+        self.insulation = None;
+    }
+
+    pub fn get_insulation(&self) -> Option<InsulationParameters> {
+        return self.insulation;
+    }
+
+    /// returns the conductance UA (W/K) from the fluid to 
+    /// ambient through the insulation, zero if there is none
+    ///
+    /// if the component has a wall, this is from the outermost 
+    /// wall node instead
+    pub fn get_ambient_conductance_watts_per_kelvin(&self) -> f64 {
+        match (self.insulation, &self.wall) {
+            (Some(insulation), Some(wall)) => 
+                wall.get_ambient_conductance_watts_per_kelvin(
+                    insulation, 
+                    self.get_cross_sectional_area_immutable(), 
+                    self.component_length),
+            (Some(insulation), None) => 
+                insulation.get_conductance_watts_per_kelvin(
+                    self.get_cross_sectional_area_immutable(), 
+                    self.component_length),
+            (None, _) => 0.0,
+        }
+    }
+
+    /// gives the component a wall which stores heat, the wall 
+    /// starts at the fluid temperature
+    pub fn set_wall(&mut self, wall_parameters: WallParameters) {
+        self.wall = Some(PipeWall::new(wall_parameters, self.fluid_temp));
+    }
+
+    /// removes the wall, the fluid then exchanges heat with the
+    /// insulation directly
+    pub fn clear_wall(&mut self) {
+        self.wall = None;
+    }
+
+    pub fn get_wall(&self) -> Option<&PipeWall> {
+        return self.wall.as_ref();
+    }
+
+    pub fn get_wall_mut(&mut self) -> Option<&mut PipeWall> {
+        return self.wall.as_mut();
+    }
 }
 
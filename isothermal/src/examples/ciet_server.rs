@@ -56,32 +56,71 @@
 // which are for bringing mutable objects and references into multithread
 // operations safely
 //
-// probably use in future
-//use std::sync::Arc;
-//use std::sync::Mutex;
 //use std::sync::mpsc;
 //use std::thread;
 //use std::time;
-use std::time::Instant;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
 
 #[warn(missing_docs)]
 use opcua::server::prelude::*;
-use local_ip_address::local_ip;
-use opcua::server::{config};
 
 use fluid_mechanics_rust::prelude::*;
 
-use crate::CIETIsothermalFacility;
-use crate::CTAHBranch;
-use crate::DHXBranch;
-use crate::HeaterBranch;
+use crate::OwnedCIETFacility;
+use crate::OwnedDRACSFacility;
+use crate::ManometerReference;
+use crate::CIETSolverError;
+use crate::get_temperature_degrees_c;
+use crate::InsulationParameters;
+use crate::PointKineticsParameters;
+use crate::ComponentReading;
+
+use super::simulation_control::{SimulationControl, ScenarioControllerNodes};
+use super::simulation_control::{add_simulation_control_methods, get_paused_calculation_result};
+use super::simulation_control::{add_set_controller_value_method, ControllerVariable};
+use super::server_security::{ServerSecurity, SERVER_USERS_ENVIRONMENT_VARIABLE};
+use super::control_server::{add_controller_variables_to_control_server, ControllerMirror};
+use super::server_config::CIETServerConfig;
 
 /// in example 8,     
 /// we want to check if the server runs polling actions synchronously 
 /// or asynchronously
 pub fn construct_and_run_ciet_server(run_server: bool){
 
-    let mut server = build_standard_server();
+    let server_config = CIETServerConfig {
+        security: ServerSecurity::from_environment(),
+        ..CIETServerConfig::default()
+    };
+
+    construct_and_run_ciet_server_with_config(run_server, server_config);
+}
+
+/// constructs the ciet server with the host, port, polling 
+/// interval and so on from the server configuration, and runs 
+/// it if run_server is true
+pub fn construct_and_run_ciet_server_with_config(
+    run_server: bool,
+    server_config: CIETServerConfig){
+
+    // anonymous users may only read, operators write the 
+    // controller values on the control server or through 
+    // SetControllerValue
+    let server_security = Arc::new(server_config.security.clone());
+
+    let mut server = build_standard_server(&server_config);
+
+    let control_server = build_control_server(&server_config);
+
+    if control_server.is_none() {
+        println!("no operators given in {} (or no Basic256Sha256 endpoints), \
+                 the controller is read only", 
+                 SERVER_USERS_ENVIRONMENT_VARIABLE);
+    }
+
+    let polling_interval = server_config.get_polling_interval();
+    let max_ctah_pump_pressure_pa = server_config.max_ctah_pump_pressure_pa;
 
     let ns = {
         let address_space = server.address_space();
@@ -91,18 +130,106 @@ pub fn construct_and_run_ciet_server(run_server: bool){
             .unwrap()
     };
 
-    // I'll have 4 variables here
+    // the fixed variables are set up here, the component 
+    // readings get their node ids from their branch and 
+    // component names
     // note that each variable needs a separate node ID
-    // this is how the user will interact with ciet: through these
-    // flowrates and the pump pressure
+    // this is how the user will interact with ciet: through the
+    // controller variables (pump, heater, fan and so on) and 
+    // the flowrates and temperatures published alongside them
 
     let ctah_branch_mass_flowrate_node = NodeId::new(ns, "ctah_branch_mass_flowrate");
     let heater_branch_mass_flowrate_node = NodeId::new(ns, "heater_branch_flowrate");
     let dhx_branch_mass_flowrate_node = NodeId::new(ns, "dhx_branch_flowrate");
     let ctah_pump_pressure_node = NodeId::new(ns, "ctah_pump_pressure");
+    let ctah_pump_speed_node = NodeId::new(ns, "ctah_pump_speed");
+    let ctah_pump_speed_control_node = NodeId::new(ns, "ctah_pump_speed_control");
+    let ctah_pump_pressure_rise_node = NodeId::new(ns, "ctah_pump_pressure_rise");
+    let transient_mode_node = NodeId::new(ns, "transient_mode");
+    let loop_temperature_node = NodeId::new(ns, "loop_temperature");
+    let thermal_hydraulics_mode_node = NodeId::new(ns, "thermal_hydraulics_mode");
+    let heater_power_node = NodeId::new(ns, "heater_power");
+    let ctah_fan_speed_node = NodeId::new(ns, "ctah_fan_speed");
+    let ctah_heat_removal_node = NodeId::new(ns, "ctah_heat_removal");
+    let ambient_temperature_node = NodeId::new(ns, "ambient_temperature");
+    let heat_loss_node = NodeId::new(ns, "heat_loss");
+    let heater_inlet_temperature_node = NodeId::new(ns, "heater_inlet_temperature");
+    let heater_outlet_temperature_node = NodeId::new(ns, "heater_outlet_temperature");
+    let ctah_inlet_temperature_node = NodeId::new(ns, "ctah_inlet_temperature");
+    let ctah_outlet_temperature_node = NodeId::new(ns, "ctah_outlet_temperature");
+    let natural_circulation_driving_head_node = 
+        NodeId::new(ns, "natural_circulation_driving_head");
+    let simulation_time_node = NodeId::new(ns, "simulation_time");
     let calculation_time_node = NodeId::new(ns, "calculation_time");
     let initiation_time_node = NodeId::new(ns, "ciet_obj_construction_time");
     let total_calc_time_node = NodeId::new(ns, "construction_time_plus_calc_time");
+    let solver_status_node = NodeId::new(ns, "solver_status");
+    let header_residual_evaluations_node = 
+        NodeId::new(ns, "header_residual_evaluations");
+    let branch_residual_evaluations_node = 
+        NodeId::new(ns, "branch_residual_evaluations");
+    let warm_start_evaluations_saved_node = 
+        NodeId::new(ns, "warm_start_evaluations_saved");
+    let out_of_range_temperature_events_node = 
+        NodeId::new(ns, "out_of_range_temperature_events");
+    let components_out_of_temperature_range_node = 
+        NodeId::new(ns, "components_out_of_temperature_range");
+    let point_kinetics_mode_node = NodeId::new(ns, "point_kinetics_mode");
+    let external_reactivity_node = NodeId::new(ns, "external_reactivity");
+    let scram_node = NodeId::new(ns, "scram");
+    let reactor_power_node = NodeId::new(ns, "reactor_power");
+    let decay_heat_node = NodeId::new(ns, "decay_heat");
+    let total_reactivity_node = NodeId::new(ns, "total_reactivity");
+
+    // construct CIET once, this facility owns all its components
+    // and lives as long as the server does
+    let start_of_object_init = Instant::now();
+
+    let ciet_isothermal_facility = construct_ciet_facility();
+
+    let initiation_duration = start_of_object_init.elapsed();
+    // f64 rather than u16, which would overflow (and panic) past
+    // about 65 s
+    let initiation_time_taken_millseconds: f64 =
+        initiation_duration.as_millis() as f64;
+
+    let manometer_tap_labels = 
+        ciet_isothermal_facility.get_manometer_tap_labels();
+
+    let check_valve_labels: Vec<String> = ciet_isothermal_facility.
+        get_check_valve_states().into_iter().
+        map(|(check_valve_label, _open)| check_valve_label).
+        collect();
+
+    let component_readings = ciet_isothermal_facility.get_component_readings();
+
+    let component_mass_flowrate_nodes: Vec<NodeId> = component_readings.iter().
+        map(|component_reading| get_component_reading_node_id(
+                ns, component_reading, COMPONENT_MASS_FLOWRATE_READING_NAME)).
+        collect();
+
+    let ciet_isothermal_facility_ptr = 
+        Arc::new(Mutex::new(ciet_isothermal_facility));
+
+    // the simulation control methods share the facility with the
+    // polling action, and queue controller values and pausing 
+    // for it here
+    let simulation_control_ptr = 
+        Arc::new(Mutex::new(SimulationControl::new()));
+
+    let scenario_controller_nodes = ScenarioControllerNodes {
+        ctah_pump_pressure: ctah_pump_pressure_node.clone(),
+        ctah_pump_speed_control: ctah_pump_speed_control_node.clone(),
+        transient_mode: transient_mode_node.clone(),
+        loop_temperature: loop_temperature_node.clone(),
+        thermal_hydraulics_mode: thermal_hydraulics_mode_node.clone(),
+        heater_power: heater_power_node.clone(),
+        ctah_fan_speed: ctah_fan_speed_node.clone(),
+        ambient_temperature: ambient_temperature_node.clone(),
+        point_kinetics_mode: point_kinetics_mode_node.clone(),
+        external_reactivity: external_reactivity_node.clone(),
+        scram: scram_node.clone(),
+    };
 
     let address_space = server.address_space();
 
@@ -136,15 +263,139 @@ pub fn construct_and_run_ciet_server(run_server: bool){
                 Variable::new(&total_calc_time_node, 
                               "construction_time_plus_calc_time_ms", 
                               "construction_time_plus_calc_time_ms", 0 as f64),
+                Variable::new(&ctah_pump_pressure_rise_node, 
+                              "ctah_pump_pressure_rise_pa", 
+                              "ctah_pump_pressure_rise_pa", 0 as f64),
+                Variable::new(&simulation_time_node, 
+                              "simulation_time_s", 
+                              "simulation_time_s", 0 as f64),
+                Variable::new(&solver_status_node, 
+                              "solver_status", 
+                              "solver_status", "ok"),
+                Variable::new(&header_residual_evaluations_node, 
+                              "header_residual_evaluations", 
+                              "header_residual_evaluations", 0 as f64),
+                Variable::new(&branch_residual_evaluations_node, 
+                              "branch_residual_evaluations", 
+                              "branch_residual_evaluations", 0 as f64),
+                Variable::new(&warm_start_evaluations_saved_node, 
+                              "warm_start_evaluations_saved", 
+                              "warm_start_evaluations_saved", 0 as f64),
+                Variable::new(&out_of_range_temperature_events_node, 
+                              "out_of_range_temperature_events", 
+                              "out_of_range_temperature_events", 0 as f64),
+                Variable::new(&components_out_of_temperature_range_node, 
+                              "components_out_of_temperature_range", 
+                              "components_out_of_temperature_range", 0 as f64),
+                Variable::new(&heater_inlet_temperature_node, 
+                              "heater_inlet_temperature_degc", 
+                              "heater_inlet_temperature_degc", 21 as f64),
+                Variable::new(&heater_outlet_temperature_node, 
+                              "heater_outlet_temperature_degc", 
+                              "heater_outlet_temperature_degc", 21 as f64),
+                Variable::new(&ctah_inlet_temperature_node, 
+                              "ctah_inlet_temperature_degc", 
+                              "ctah_inlet_temperature_degc", 21 as f64),
+                Variable::new(&ctah_outlet_temperature_node, 
+                              "ctah_outlet_temperature_degc", 
+                              "ctah_outlet_temperature_degc", 21 as f64),
+                Variable::new(&natural_circulation_driving_head_node, 
+                              "natural_circulation_driving_head_pa", 
+                              "natural_circulation_driving_head_pa", 0 as f64),
+                Variable::new(&ctah_heat_removal_node, 
+                              "ctah_heat_removal_watts", 
+                              "ctah_heat_removal_watts", 0 as f64),
+                Variable::new(&heat_loss_node, 
+                              "heat_loss_to_ambient_watts", 
+                              "heat_loss_to_ambient_watts", 0 as f64),
+                Variable::new(&reactor_power_node, 
+                              "reactor_power_watts", 
+                              "reactor_power_watts", 0 as f64),
+                Variable::new(&decay_heat_node, 
+                              "decay_heat_watts", 
+                              "decay_heat_watts", 0 as f64),
+                Variable::new(&total_reactivity_node, 
+                              "total_reactivity_dollars", 
+                              "total_reactivity_dollars", 0 as f64),
             ],
             &sample_folder_id,
         );
     }
 
-    // this is the piece of code for the writeonly variable
-    // we can use booleans or floats
+    // this part is responsible for manometer readings,
+    // one variable per junction between components in each branch
+    //
+    // these are gauge pressures relative to the top header
+    // shared by the heater, dhx and ctah branches
+    {
+        let mut address_space = address_space.write();
+
+        let manometer_folder_id = address_space
+            .add_folder("manometer readings", "manometer readings", 
+                        &NodeId::objects_folder_id())
+            .unwrap();
+
+        let mut manometer_variables: Vec<Variable> = vec![];
+
+        for tap_label in manometer_tap_labels.iter() {
+
+            let tap_node = NodeId::new(ns, tap_label.clone());
+            let tap_browse_name = format!("{}_pa", tap_label);
+
+            manometer_variables.push(
+                Variable::new(&tap_node, 
+                              tap_browse_name.as_str(),
+                              tap_browse_name.as_str(), 0 as f64));
+        }
+
+        let _ = address_space.add_variables(
+            manometer_variables,
+            &manometer_folder_id);
+    }
+
+    // every component in every branch, under 
+    // Objects/CIET/<branch>/<component>
+    {
+        let mut address_space = address_space.write();
+
+        add_component_nodes(&mut address_space, ns, &component_readings);
+    }
+
+    // check valve states, true if open
     {
         let mut address_space = address_space.write();
+
+        let check_valve_folder_id = address_space
+            .add_folder("check valves", "check valves", 
+                        &NodeId::objects_folder_id())
+            .unwrap();
+
+        let mut check_valve_variables: Vec<Variable> = vec![];
+
+        for check_valve_label in check_valve_labels.iter() {
+
+            let check_valve_node = NodeId::new(ns, check_valve_label.clone());
+            let check_valve_browse_name = format!("{}_open", check_valve_label);
+
+            check_valve_variables.push(
+                Variable::new(&check_valve_node, 
+                              check_valve_browse_name.as_str(), 
+                              check_valve_browse_name.as_str(), false));
+        }
+
+        let _ = address_space.add_variables(
+            check_valve_variables,
+            &check_valve_folder_id);
+    }
+
+    // this is the piece of code for the controller variables
+    // we can use booleans or floats
+    //
+    // these are read only here, operators write them on the 
+    // control server (see ControllerMirror) or through the 
+    // SetControllerValue method
+    let controller_folder_id = {
+        let mut address_space = address_space.write();
         let folder_id = address_space
             .add_folder("Controller", "Controller", &NodeId::objects_folder_id())
             .unwrap();
@@ -154,9 +405,140 @@ pub fn construct_and_run_ciet_server(run_server: bool){
                              "ctah_branch_pressure_pa", "ctah_branch_pressure_pa")
             .data_type(DataTypeId::Float)
             .value(0 as f64)
-            .writable()
             .organized_by(&folder_id)
             .insert(&mut address_space);
+
+        // if ctah_pump_speed_control is true, the pump runs along
+        // its pump curve at ctah_pump_speed_rpm, and 
+        // ctah_branch_pressure_pa is ignored
+        VariableBuilder::new(&ctah_pump_speed_node, 
+                             "ctah_pump_speed_rpm", "ctah_pump_speed_rpm")
+            .data_type(DataTypeId::Float)
+            .value(0 as f64)
+            .organized_by(&folder_id)
+            .insert(&mut address_space);
+
+        VariableBuilder::new(&ctah_pump_speed_control_node, 
+                             "ctah_pump_speed_control", "ctah_pump_speed_control")
+            .data_type(DataTypeId::Boolean)
+            .value(false)
+            .organized_by(&folder_id)
+            .insert(&mut address_space);
+
+        // if transient_mode is true, every polling tick advances 
+        // the flowrates by one polling interval instead of 
+        // solving for steady state. The pump speed is then a 
+        // target the pump spins up or coasts down towards
+        VariableBuilder::new(&transient_mode_node, 
+                             "transient_mode", "transient_mode")
+            .data_type(DataTypeId::Boolean)
+            .value(false)
+            .organized_by(&folder_id)
+            .insert(&mut address_space);
+
+        // uniform temperature of the therminol in the loop,
+        // density and viscosity follow this temperature
+        VariableBuilder::new(&loop_temperature_node, 
+                             "loop_temperature_degc", "loop_temperature_degc")
+            .data_type(DataTypeId::Float)
+            .value(21 as f64)
+            .organized_by(&folder_id)
+            .insert(&mut address_space);
+
+        // if thermal_hydraulics_mode is true, the heater power 
+        // goes into the fluid and the temperatures are carried 
+        // around the loop every polling tick, the loop 
+        // temperature is then only the starting temperature
+        VariableBuilder::new(&thermal_hydraulics_mode_node, 
+                             "thermal_hydraulics_mode", "thermal_hydraulics_mode")
+            .data_type(DataTypeId::Boolean)
+            .value(false)
+            .organized_by(&folder_id)
+            .insert(&mut address_space);
+
+        VariableBuilder::new(&heater_power_node, 
+                             "heater_power_watts", "heater_power_watts")
+            .data_type(DataTypeId::Float)
+            .value(0 as f64)
+            .organized_by(&folder_id)
+            .insert(&mut address_space);
+
+        // operators control the ctah outlet temperature with 
+        // the ctah fan speed
+        VariableBuilder::new(&ctah_fan_speed_node, 
+                             "ctah_fan_speed_rpm", "ctah_fan_speed_rpm")
+            .data_type(DataTypeId::Float)
+            .value(0 as f64)
+            .organized_by(&folder_id)
+            .insert(&mut address_space);
+
+        // temperature of the room the insulated pipes lose heat
+        // to
+        VariableBuilder::new(&ambient_temperature_node, 
+                             "ambient_temperature_degc", "ambient_temperature_degc")
+            .data_type(DataTypeId::Float)
+            .value(21 as f64)
+            .organized_by(&folder_id)
+            .insert(&mut address_space);
+
+        // if point_kinetics_mode is true, the heater stands in for
+        // a reactor core, its power comes from point kinetics 
+        // starting critical at heater_power_watts, and operators 
+        // insert reactivity or scram instead of setting the power
+        //
+        // the kinetics only advance in thermal hydraulics mode
+        VariableBuilder::new(&point_kinetics_mode_node, 
+                             "point_kinetics_mode", "point_kinetics_mode")
+            .data_type(DataTypeId::Boolean)
+            .value(false)
+            .organized_by(&folder_id)
+            .insert(&mut address_space);
+
+        VariableBuilder::new(&external_reactivity_node, 
+                             "external_reactivity_dollars", "external_reactivity_dollars")
+            .data_type(DataTypeId::Float)
+            .value(0 as f64)
+            .organized_by(&folder_id)
+            .insert(&mut address_space);
+
+        // the control rods stay in while scram is true
+        VariableBuilder::new(&scram_node, 
+                             "scram", "scram")
+            .data_type(DataTypeId::Boolean)
+            .value(false)
+            .organized_by(&folder_id)
+            .insert(&mut address_space);
+
+        folder_id
+    };
+
+    let mut controller_variables = vec![
+        ControllerVariable::new_float("ctah_branch_pressure_pa", &ctah_pump_pressure_node),
+        ControllerVariable::new_float("ctah_pump_speed_rpm", &ctah_pump_speed_node),
+        ControllerVariable::new_boolean("ctah_pump_speed_control", &ctah_pump_speed_control_node),
+        ControllerVariable::new_boolean("transient_mode", &transient_mode_node),
+        ControllerVariable::new_float("loop_temperature_degc", &loop_temperature_node),
+        ControllerVariable::new_boolean("thermal_hydraulics_mode", &thermal_hydraulics_mode_node),
+        ControllerVariable::new_float("heater_power_watts", &heater_power_node),
+        ControllerVariable::new_float("ctah_fan_speed_rpm", &ctah_fan_speed_node),
+        ControllerVariable::new_float("ambient_temperature_degc", &ambient_temperature_node),
+        ControllerVariable::new_boolean("point_kinetics_mode", &point_kinetics_mode_node),
+        ControllerVariable::new_float("external_reactivity_dollars", &external_reactivity_node),
+        ControllerVariable::new_boolean("scram", &scram_node),
+    ];
+
+    // SolveSteadyState, Reset, Pause, Resume, StepTransient and 
+    // LoadScenario, each returns once the solver is done
+    {
+        let mut address_space = address_space.write();
+
+        add_simulation_control_methods(
+            &mut address_space, 
+            ns, 
+            &ciet_isothermal_facility_ptr, 
+            &simulation_control_ptr, 
+            &scenario_controller_nodes,
+            &server_security);
     }
 
 
@@ -164,276 +546,371 @@ pub fn construct_and_run_ciet_server(run_server: bool){
 
     // adding functions to ciet's server now...
     //
-    // this one prints the endpoint every 5s so the user knows
-    // how to connect to ciet
+    // this one prints the endpoint every 5s (by default) so the 
+    // user knows how to connect to ciet
 
-    let print_endpoint_simple = || {
-        let ip_add = get_ip_as_str();
+    let endpoint_print_config = server_config.clone();
 
-        println!("\n opc.tcp://{}:{}{} \n",ip_add,4840,CUSTOM_ENDPOINT_PATH);
+    let print_endpoint_simple = move || {
+        println!("\n {} \n", endpoint_print_config.get_endpoint_url());
     };
 
 
     //server.add_polling_action(5000, print_endpoint);
-    server.add_polling_action(5000, print_endpoint_simple);
+    server.add_polling_action(
+        server_config.endpoint_print_interval_milliseconds, 
+        print_endpoint_simple);
 
 
-    // we need to prepare transmitters and receivers for the
-    // ciet isothermal facility
+    // the ciet facility is constructed once here and kept alive
+    // across polling ticks, it is shared with the polling closure
+    // through an Arc<Mutex<>>
+    //
+    // (previously i was instantiating CIET at EVERY timestep 
+    // in addition to calculation, which was REALLY inefficient)
 
-    //let (tx, rx) = mpsc::channel();
+    let polling_simulation_control_ptr = simulation_control_ptr.clone();
 
+    // set once the control server is built, below
+    let controller_mirror_ptr: Arc<Mutex<Option<ControllerMirror>>> = 
+        Arc::new(Mutex::new(None));
+    let polling_controller_mirror_ptr = controller_mirror_ptr.clone();
 
-    // now this algorithm is REALLY inefficient, i am instantiating CIET at
-    // EVERY timestep in addition to calculation
-    //
-    // but if it works, it works
     let calculate_flowrate_and_pressure_loss = move || {
 
-        // construct CIET
-        let start_of_object_init = Instant::now();
-
-        // here i will start storing all of ciet's component objects
-        // and constructing them into vectors
-        //
-        // firstly, the ctah branch
-
-        let ctah_branch_factory = CTAHBranch::new();
-        let pipe6a = ctah_branch_factory.get_pipe6a();
-        let static_mixer_41 = ctah_branch_factory.get_static_mixer_41();
-        let ctah_vertical = ctah_branch_factory.get_ctah_vertical();
-        let ctah_horizontal = ctah_branch_factory.get_ctah_horizontal();
-        let pipe_8a = ctah_branch_factory.get_pipe_8a();
-        let static_mixer_40 = ctah_branch_factory.get_static_mixer_40();
-        let pipe_9 = ctah_branch_factory.get_pipe_9();
-        let pipe_10 = ctah_branch_factory.get_pipe_10();
-        let pipe_11 = ctah_branch_factory.get_pipe_11();
-        let pipe_12 = ctah_branch_factory.get_pipe_12();
-        let pipe_13 = ctah_branch_factory.get_pipe_13();
-        let pipe_14 = ctah_branch_factory.get_pipe_14();
-        let flowmeter_40_14a = ctah_branch_factory.get_flowmeter_40_14a();
-        let pipe_15 = ctah_branch_factory.get_pipe_15();
-        let pipe_16 = ctah_branch_factory.get_pipe_16();
-        let branch_17 = ctah_branch_factory.get_branch_17();
-
-        // now push them into a vector
-        // step 2, find the variable using this node object
+        // step 1, find the correct node object
         // first let's get the address space
         // i want to first set my ciet ctah branch pressure to the user specified
         // value
         let mut address_space = address_space.write();
-        
-        // step 1, find the correct node object
-        let ctah_pump_node = ctah_pump_pressure_node.clone();
-        let pump_pressure_value = address_space.
-            get_variable_value(ctah_pump_node).unwrap();
 
-        // let's try setting ctah branch pressure to the user specified value
+        // values operators wrote on the control server come in 
+        // first
+        if let Some(controller_mirror) = 
+            polling_controller_mirror_ptr.lock().unwrap().as_mut() {
+            controller_mirror.synchronise(&mut address_space);
+        }
+
+        // controller values queued by SetControllerValue, Reset 
+        // or LoadScenario go in first, so this tick runs with them
         //
-        // This is basically how you set ctah pump pressure... kind of cumbersome
-        // but oh well
+        // the simulation control lock is let go before the 
+        // facility is locked, the methods lock them the other 
+        // way round
+        let paused: bool = {
+            let mut simulation_control = polling_simulation_control_ptr.lock().unwrap();
+            simulation_control.write_pending_controller_values(&mut address_space);
+            simulation_control.is_paused()
+        };
+        
+        // step 2, convert variable value into f64 and limit pump
+        // pressure to 45,000 Pa (by default), anything which isn't
+        // a finite number counts as no pump pressure
+        let pump_pressure_value: f64 = get_clamped_float_variable_value(
+            &mut address_space, 
+            &ctah_pump_pressure_node, 
+            -max_ctah_pump_pressure_pa, 
+            max_ctah_pump_pressure_pa, 
+            0.0);
+
+        // step 3 convert f64 to Pressure
+        let user_specified_pump_pressure = 
+            Pressure::new::<pascal>(pump_pressure_value);
+
+        // step 4, set the pump pressure (or speed) in place 
+        // and calculate everything
+        let mut ciet_isothermal_facility = 
+            ciet_isothermal_facility_ptr.lock().unwrap();
+
+        let speed_control: bool = get_boolean_variable_value(
+            &address_space, &ctah_pump_speed_control_node);
+
+        let transient_mode: bool = get_boolean_variable_value(
+            &address_space, &transient_mode_node);
+
+        // the property correlations of the working fluid are 
+        // only good over their validated range, so i clamp the 
+        // loop temperature to that range
+        let (min_loop_temperature_degc, max_loop_temperature_degc) = 
+            ciet_isothermal_facility.get_fluid_properties().
+            get_validated_temperature_range_degrees_c();
+
+        let loop_temperature_degc = get_clamped_float_variable_value(
+            &mut address_space, 
+            &loop_temperature_node, 
+            min_loop_temperature_degc, 
+            max_loop_temperature_degc, 
+            get_default_loop_temperature_degc(
+                min_loop_temperature_degc, max_loop_temperature_degc));
+
+        let loop_temperature = ThermodynamicTemperature::new::<degree_celsius>(
+            loop_temperature_degc);
+
+        if ciet_isothermal_facility.get_loop_temperature() != loop_temperature {
+            ciet_isothermal_facility.set_loop_temperature(loop_temperature);
+        }
+
+        let thermal_hydraulics_mode: bool = get_boolean_variable_value(
+            &address_space, &thermal_hydraulics_mode_node);
+
+        ciet_isothermal_facility.set_thermal_hydraulics_enabled(
+            thermal_hydraulics_mode);
 
-        // step 3, convert variable value into f64
-        let mut pump_pressure_value: f64 = pump_pressure_value.
-            value.unwrap().as_f64().unwrap();
+        // the heater can't cool the fluid or go beyond its 
+        // rated power
+        let mut heater_power_watts: f64 = address_space.
+            get_variable_value(heater_power_node.clone()).
+            ok().
+            and_then(|data_value| data_value.value).
+            and_then(|variant| variant.as_f64()).
+            unwrap_or(0.0);
 
-        // limit pump pressure to 45,000 Pa
-        if pump_pressure_value > 45_000_f64 {
-            pump_pressure_value = 45_000_f64;
+        if heater_power_watts < 0.0 || heater_power_watts > MAX_HEATER_POWER_WATTS ||
+            !heater_power_watts.is_finite() {
+
+            heater_power_watts = if heater_power_watts.is_finite() {
+                heater_power_watts.max(0.0).min(MAX_HEATER_POWER_WATTS)
+            } else {
+                0.0
+            };
 
             let now = DateTime::now();
             let _ = address_space.set_variable_value(
-                ctah_pump_pressure_node.clone(), 
-                pump_pressure_value as f64,
+                heater_power_node.clone(), 
+                heater_power_watts,
                 &now, 
                 &now);
         }
 
-        if pump_pressure_value < -45_000_f64 {
+        let point_kinetics_mode: bool = get_boolean_variable_value(
+            &address_space, &point_kinetics_mode_node);
+
+        if point_kinetics_mode {
+
+            if ciet_isothermal_facility.get_point_kinetics().is_none() {
+                ciet_isothermal_facility.enable_point_kinetics(
+                    PointKineticsParameters::ciet_default(), 
+                    heater_power_watts);
+            }
+
+            let external_reactivity_dollars = get_clamped_float_variable_value(
+                &mut address_space, 
+                &external_reactivity_node, 
+                MIN_EXTERNAL_REACTIVITY_DOLLARS, 
+                MAX_EXTERNAL_REACTIVITY_DOLLARS, 
+                0.0);
+
+            let scram: bool = get_boolean_variable_value(
+                &address_space, &scram_node);
+
+            if let Some(point_kinetics) = 
+                ciet_isothermal_facility.get_point_kinetics_mut() {
+
+                point_kinetics.set_external_reactivity_dollars(
+                    external_reactivity_dollars);
+
+                if scram {
+                    point_kinetics.scram();
+                } else {
+                    point_kinetics.reset_scram();
+                }
+            }
+
+        } else {
+
+            if ciet_isothermal_facility.get_point_kinetics().is_some() {
+                ciet_isothermal_facility.disable_point_kinetics();
+            }
+
+            ciet_isothermal_facility.set_heater_power_watts(heater_power_watts);
+        }
 
-            pump_pressure_value = -45_000_f64;
+        let mut ctah_fan_speed_rpm: f64 = address_space.
+            get_variable_value(ctah_fan_speed_node.clone()).
+            ok().
+            and_then(|data_value| data_value.value).
+            and_then(|variant| variant.as_f64()).
+            unwrap_or(0.0);
+
+        if ctah_fan_speed_rpm < 0.0 || ctah_fan_speed_rpm > MAX_CTAH_FAN_SPEED_RPM ||
+            !ctah_fan_speed_rpm.is_finite() {
+
+            ctah_fan_speed_rpm = if ctah_fan_speed_rpm.is_finite() {
+                ctah_fan_speed_rpm.max(0.0).min(MAX_CTAH_FAN_SPEED_RPM)
+            } else {
+                0.0
+            };
 
             let now = DateTime::now();
             let _ = address_space.set_variable_value(
-                ctah_pump_pressure_node.clone(), 
-                pump_pressure_value as f64,
+                ctah_fan_speed_node.clone(), 
+                ctah_fan_speed_rpm,
                 &now, 
                 &now);
         }
 
-        // step 4 convert f64 to Pressure
-        let user_specified_pump_pressure = 
-            Pressure::new::<pascal>(pump_pressure_value);
+        ciet_isothermal_facility.set_ctah_fan_speed_rpm(ctah_fan_speed_rpm);
 
-        let mut mutable_ctah_pump = ctah_branch_factory.get_ctah_pump();
-        mutable_ctah_pump.set_internal_pressure_source(user_specified_pump_pressure);
-
-        let mut ctah_branch_vector :Vec<&dyn FluidComponent> = vec![];
-
-        // element number: 0 
-        ctah_branch_vector.push(&pipe6a); 
-        // 1
-        ctah_branch_vector.push(&static_mixer_41);
-        // 2
-        ctah_branch_vector.push(&ctah_vertical);
-        // 3
-        ctah_branch_vector.push(&ctah_horizontal);
-        // 4
-        ctah_branch_vector.push(&pipe_8a);
-        // 5
-        ctah_branch_vector.push(&static_mixer_40);
-        // 6
-        ctah_branch_vector.push(&pipe_9);
-        // 7
-        ctah_branch_vector.push(&pipe_10);
-        // 8
-        ctah_branch_vector.push(&pipe_11);
-        // 9
-        ctah_branch_vector.push(&pipe_12);
-        // 10
-        ctah_branch_vector.push(&mutable_ctah_pump);
-        // 11
-        ctah_branch_vector.push(&pipe_13);
-        // 12
-        ctah_branch_vector.push(&pipe_14);
-        //13
-        ctah_branch_vector.push(&flowmeter_40_14a);
-        //14
-        ctah_branch_vector.push(&pipe_15);
-        //15
-        ctah_branch_vector.push(&pipe_16);
-        //16
-        ctah_branch_vector.push(&branch_17);
-
-        // now set the vector in the CTAHBranch Object
-
-        let mut ctah_branch = CTAHBranch::new();
-        ctah_branch.set_fluid_component_vector(ctah_branch_vector);
-
-        // now for the heater branch
-
-        let heater_branch_factory = HeaterBranch::new();
-
-        let branch5 = heater_branch_factory.get_branch5();
-        let pipe4 = heater_branch_factory.get_pipe4();
-        let pipe3 = heater_branch_factory.get_pipe3();
-        let mixer10 = heater_branch_factory.get_mixer10();
-        let pipe2a = heater_branch_factory.get_pipe2a();
-        let heater_top_head_1a = heater_branch_factory.get_heater_top_head_1a();
-        let ciet_heater = heater_branch_factory.get_ciet_heater();
-        let heater_bottom_head_1b = heater_branch_factory.get_heater_bottom_head_1b();
-        let pipe18 = heater_branch_factory.get_pipe18();
-
-        let mut heater_branch_vector :Vec<&dyn FluidComponent> = vec![];
-
-        heater_branch_vector.push(&branch5);
-        heater_branch_vector.push(&pipe4);
-        heater_branch_vector.push(&pipe3);
-        heater_branch_vector.push(&mixer10);
-        heater_branch_vector.push(&pipe2a);
-        heater_branch_vector.push(&heater_top_head_1a);
-        heater_branch_vector.push(&ciet_heater);
-        heater_branch_vector.push(&heater_bottom_head_1b);
-        heater_branch_vector.push(&pipe18);
-
-        let mut heater_branch = HeaterBranch::new();
-        heater_branch.set_fluid_component_vector(heater_branch_vector);
-
-        // last but not least the dhx branch
-        let dhx_branch_factory = DHXBranch::new();
-
-        let pipe26 = dhx_branch_factory.get_pipe26();
-        // item 25
-        let static_mixer_21 = dhx_branch_factory.get_static_mixer_21();
-        let pipe25a = dhx_branch_factory.get_pipe25a();
-        // item 24
-        let dhx_shell_side_heat_exchanger = dhx_branch_factory.get_dhx_shell_side_heat_exchanger();
-        // item 23
-        let static_mixer_20 = dhx_branch_factory.get_static_mixer_20();
-        let pipe23a = dhx_branch_factory.get_pipe23a();
-        let pipe22 = dhx_branch_factory.get_pipe22();
-        // item 21a
-        let flowmeter20 = dhx_branch_factory.get_flowmeter20();
-        let pipe21 = dhx_branch_factory.get_pipe21();
-        let pipe20 = dhx_branch_factory.get_pipe20();
-        let pipe19 = dhx_branch_factory.get_pipe19();
-
-        let mut dhx_branch_vector :Vec<&dyn FluidComponent> = vec![];
-
-        dhx_branch_vector.push(&pipe26);
-        dhx_branch_vector.push(&static_mixer_21);
-        dhx_branch_vector.push(&pipe25a);
-        dhx_branch_vector.push(&dhx_shell_side_heat_exchanger);
-        dhx_branch_vector.push(&static_mixer_20);
-        dhx_branch_vector.push(&pipe23a);
-        dhx_branch_vector.push(&pipe22);
-        dhx_branch_vector.push(&flowmeter20);
-        dhx_branch_vector.push(&pipe21);
-        dhx_branch_vector.push(&pipe20);
-        dhx_branch_vector.push(&pipe19);
-
-        let mut dhx_branch = DHXBranch::new();
-        dhx_branch.set_fluid_component_vector(dhx_branch_vector);
-
-        // now we are ready for ciet
+        let ambient_temperature_degc = get_clamped_float_variable_value(
+            &mut address_space, 
+            &ambient_temperature_node, 
+            MIN_AMBIENT_TEMPERATURE_DEGC, 
+            MAX_AMBIENT_TEMPERATURE_DEGC, 
+            21.0);
 
-        let mut ciet_isothermal_facility = 
-        CIETIsothermalFacility::new(ctah_branch, heater_branch, dhx_branch);
+        ciet_isothermal_facility.set_ambient_temperature(
+            ThermodynamicTemperature::new::<degree_celsius>(ambient_temperature_degc));
 
-        let initiation_duration = start_of_object_init.elapsed();
+        if speed_control {
 
+            // the pump can't run backwards or far beyond its 
+            // rated speed
+            let pump_speed_rpm = get_clamped_float_variable_value(
+                &mut address_space, 
+                &ctah_pump_speed_node, 
+                0.0, 
+                MAX_CTAH_PUMP_SPEED_RPM, 
+                0.0);
 
+            if transient_mode {
+                ciet_isothermal_facility.set_ctah_pump_target_speed(pump_speed_rpm);
+            } else {
+                ciet_isothermal_facility.set_ctah_pump_speed(pump_speed_rpm);
+            }
 
-        // step 5, set the pump pressure to the correct value
-        // and calculate everything
-        //
+        } else {
+
+            ciet_isothermal_facility.set_ctah_pump_pressure(
+                user_specified_pump_pressure);
+        }
+
+        // while paused, the facility stays as it is (apart from 
+        // the simulation control methods) and the variables show 
+        // it as it is
+        let calculation_result = if paused {
+            get_paused_calculation_result(&ciet_isothermal_facility)
+        } else {
+            calculate_or_step_transient(
+                &mut ciet_isothermal_facility, 
+                transient_mode,
+                polling_interval)
+        };
+
+        // the fluid properties are extrapolated outside their 
+        // validated range, so clients are told how often (and 
+        // where) that happened, whether or not the solve worked
+        let now = DateTime::now();
+        let _ = address_space.set_variable_value(
+            out_of_range_temperature_events_node.clone(), 
+            ciet_isothermal_facility.get_out_of_range_temperature_events() as f64,
+            &now, 
+            &now);
+
+        let now = DateTime::now();
+        let _ = address_space.set_variable_value(
+            components_out_of_temperature_range_node.clone(), 
+            ciet_isothermal_facility.get_temperature_range_violations().len() as f64,
+            &now, 
+            &now);
 
-        
-        // need to mutably borrow ciet and return the mutable borrow
         let (calc_time,
              ctah_branch_flowrate,
              heater_branch_flowrate,
              dhx_branch_flowrate)
-             = ciet_isothermal_facility.calculate();
+             = match calculation_result {
+                 Ok(calculation_result) => calculation_result,
+                 Err(solver_error) => {
+
+                     // instead of panicking within the polling action,
+                     // i mark the flowrates and manometer readings as bad
+                     // and tell the client why
+                     let status_code = 
+                         get_solver_error_status_code(&solver_error);
+
+                     let mut output_nodes: Vec<NodeId> = vec![
+                         ctah_branch_mass_flowrate_node.clone(),
+                         heater_branch_mass_flowrate_node.clone(),
+                         dhx_branch_mass_flowrate_node.clone(),
+                     ];
+
+                     for tap_label in manometer_tap_labels.iter() {
+                         output_nodes.push(NodeId::new(ns, tap_label.clone()));
+                     }
+
+                     output_nodes.extend(component_mass_flowrate_nodes.iter().cloned());
+
+                     for output_node in output_nodes.iter() {
+                         set_variable_bad_status(
+                             &mut address_space, 
+                             output_node, 
+                             status_code);
+                     }
+
+                     let now = DateTime::now();
+                     let _ = address_space.set_variable_value(
+                         solver_status_node.clone(), 
+                         UAString::from(solver_error.to_string()),
+                         &now, 
+                         &now);
+
+                     return;
+                 },
+             };
 
-        // this mutable ctah pump cannot be used safely
-        // as it cannot be shared between threads
+        let now = DateTime::now();
+        let _ = address_space.set_variable_value(
+            solver_status_node.clone(), 
+            "ok",
+            &now, 
+            &now);
 
+        let now = DateTime::now();
+        let _ = address_space.set_variable_value(
+            ctah_pump_pressure_rise_node.clone(), 
+            ciet_isothermal_facility.get_ctah_pump_pressure().value,
+            &now, 
+            &now);
 
+        let now = DateTime::now();
+        let _ = address_space.set_variable_value(
+            simulation_time_node.clone(), 
+            ciet_isothermal_facility.get_simulation_time().as_secs_f64(),
+            &now, 
+            &now);
 
-        // step 6 set the time variable
+        // step 5 set the time variables
+        //
+        // the construction time is now a one off cost
+        // which happened when the server was started
 
-        let calc_time_taken_milleseconds: u16 = 
-            calc_time.as_millis().try_into().unwrap();
+        let calc_time_taken_milleseconds: f64 = 
+            calc_time.as_millis() as f64;
 
         let now = DateTime::now();
         let _ = address_space.set_variable_value(
             calculation_time_node.clone(), 
-            calc_time_taken_milleseconds as f64,
+            calc_time_taken_milleseconds,
             &now, 
             &now);
 
-        let initiation_time_taken_millseconds: u16 =
-            initiation_duration.as_millis().try_into().unwrap();
-
         let now = DateTime::now();
         let _ = address_space.set_variable_value(
             initiation_time_node.clone(), 
-            initiation_time_taken_millseconds as f64,
+            initiation_time_taken_millseconds,
             &now, 
             &now);
 
-        let total_time_taken: u16 =
+        let total_time_taken: f64 =
             calc_time_taken_milleseconds + initiation_time_taken_millseconds;
 
         let now = DateTime::now();
         let _ = address_space.set_variable_value(
             total_calc_time_node.clone(), 
-            total_time_taken as f64,
+            total_time_taken,
             &now, 
             &now);
 
-        // step 7 let's put in our flowrate values
+        // step 6 let's put in our flowrate values
 
         
         let now = DateTime::now();
@@ -457,15 +934,193 @@ pub fn construct_and_run_ciet_server(run_server: bool){
             &now, 
             &now);
 
+        // step 7 solver iteration counts, the number of residual 
+        // evaluations saved is compared against the last cold start
 
-        // i think we are done!
+        let iteration_counts = 
+            ciet_isothermal_facility.get_last_iteration_counts();
+
+        let evaluations_saved: f64 = 
+            match ciet_isothermal_facility.get_cold_start_iteration_counts() {
+                Some(cold_start_iteration_counts) => {
+                    cold_start_iteration_counts.get_total_residual_evaluations() as f64
+                    - iteration_counts.get_total_residual_evaluations() as f64
+                },
+                None => 0.0,
+            };
 
+        let now = DateTime::now();
+        let _ = address_space.set_variable_value(
+            header_residual_evaluations_node.clone(), 
+            iteration_counts.header_residual_evaluations as f64,
+            &now, 
+            &now);
 
+        let now = DateTime::now();
+        let _ = address_space.set_variable_value(
+            branch_residual_evaluations_node.clone(), 
+            iteration_counts.branch_residual_evaluations as f64,
+            &now, 
+            &now);
 
+        let now = DateTime::now();
+        let _ = address_space.set_variable_value(
+            warm_start_evaluations_saved_node.clone(), 
+            evaluations_saved,
+            &now, 
+            &now);
+
+        // step 8 temperatures, these only change in thermal 
+        // hydraulics mode
+
+        let temperature_readings = vec![
+            (heater_inlet_temperature_node.clone(), 
+             ciet_isothermal_facility.get_heater_inlet_temperature()),
+            (heater_outlet_temperature_node.clone(), 
+             ciet_isothermal_facility.get_heater_outlet_temperature()),
+            (ctah_inlet_temperature_node.clone(), 
+             ciet_isothermal_facility.get_ctah_inlet_temperature()),
+            (ctah_outlet_temperature_node.clone(), 
+             ciet_isothermal_facility.get_ctah_outlet_temperature()),
+        ];
+
+        for (temperature_node, temperature) in temperature_readings {
+
+            let now = DateTime::now();
+            let _ = address_space.set_variable_value(
+                temperature_node, 
+                get_temperature_degrees_c(temperature),
+                &now, 
+                &now);
+        }
+
+        let now = DateTime::now();
+        let _ = address_space.set_variable_value(
+            ctah_heat_removal_node.clone(), 
+            ciet_isothermal_facility.get_ctah_heat_removal_watts(),
+            &now, 
+            &now);
+
+        let now = DateTime::now();
+        let _ = address_space.set_variable_value(
+            heat_loss_node.clone(), 
+            ciet_isothermal_facility.get_heat_loss_watts(),
+            &now, 
+            &now);
+
+        // reactor power is the heater power when point kinetics
+        // is off
+        let (reactor_power_watts, decay_heat_watts, total_reactivity_dollars) = 
+            match ciet_isothermal_facility.get_point_kinetics() {
+                Some(point_kinetics) => (
+                    point_kinetics.get_power_watts(),
+                    point_kinetics.get_decay_heat_watts(),
+                    point_kinetics.get_total_reactivity_dollars()),
+                None => (
+                    ciet_isothermal_facility.get_heater_power_watts(), 
+                    0.0, 
+                    0.0),
+            };
+
+        let point_kinetics_readings = vec![
+            (reactor_power_node.clone(), reactor_power_watts),
+            (decay_heat_node.clone(), decay_heat_watts),
+            (total_reactivity_node.clone(), total_reactivity_dollars),
+        ];
+
+        for (point_kinetics_node, point_kinetics_reading) in point_kinetics_readings {
+
+            let now = DateTime::now();
+            let _ = address_space.set_variable_value(
+                point_kinetics_node, 
+                point_kinetics_reading,
+                &now, 
+                &now);
+        }
+
+        // the buoyancy head which drives flow up the heater 
+        // branch and down the dhx branch when the pump is off
+        let now = DateTime::now();
+        let _ = address_space.set_variable_value(
+            natural_circulation_driving_head_node.clone(), 
+            ciet_isothermal_facility.get_natural_circulation_driving_head().value,
+            &now, 
+            &now);
+
+        // step 9 check valve states
+
+        for (check_valve_label, open) in 
+            ciet_isothermal_facility.get_check_valve_states() {
+
+            let now = DateTime::now();
+            let _ = address_space.set_variable_value(
+                NodeId::new(ns, check_valve_label), 
+                open,
+                &now, 
+                &now);
+        }
+
+        // step 10 manometer readings relative to the top header
+
+        let manometer_readings = ciet_isothermal_facility.
+            get_manometer_readings(ManometerReference::TopHeader);
+
+        for manometer_reading in manometer_readings.iter() {
+
+            let tap_node = NodeId::new(ns, manometer_reading.get_label());
+
+            let now = DateTime::now();
+            let _ = address_space.set_variable_value(
+                tap_node, 
+                manometer_reading.gauge_pressure.value as f64,
+                &now, 
+                &now);
+        }
+
+        // step 11 flow conditions in every component
+
+        set_component_node_values(
+            &mut address_space, 
+            ns, 
+            &ciet_isothermal_facility.get_component_readings());
+
+
+        // i think we are done!
 
     };
 
-    server.add_polling_action(500, calculate_flowrate_and_pressure_loss);
+    server.add_polling_action(server_config.polling_interval_milliseconds, 
+                              calculate_flowrate_and_pressure_loss);
+
+    // the DRACS loop is solved on its own, in its own polling 
+    // action
+    let mut dracs_controller_variables = add_dracs_loop_to_server(
+        &mut server, ns, server_config.polling_interval_milliseconds);
+    controller_variables.append(&mut dracs_controller_variables);
+
+    // the queued values are written by the primary loop's 
+    // polling action, including those of the DRACS loop, and so 
+    // are the values written on the control server
+    {
+        let address_space = server.address_space();
+        let mut address_space = address_space.write();
+
+        if let Some(control_server) = control_server.as_ref() {
+            *controller_mirror_ptr.lock().unwrap() = Some(
+                add_controller_variables_to_control_server(
+                    control_server, 
+                    &address_space, 
+                    &controller_variables));
+        }
+
+        add_set_controller_value_method(
+            &mut address_space, 
+            ns, 
+            &controller_folder_id, 
+            controller_variables, 
+            &simulation_control_ptr, 
+            &server_security);
+    }
 
     // to check if polling server adds the polling time to
     // the execution time
@@ -476,62 +1131,511 @@ pub fn construct_and_run_ciet_server(run_server: bool){
     // the endpoint prints (every 5s)
     // otherwise it will print twice as often
 
-    if run_server { server.run(); }
+    if run_server { 
 
+        if let Some(control_server) = control_server {
+            println!("operators write the controller on {}", 
+                     server_config.get_control_endpoint_url());
+            std::thread::spawn(move || control_server.run());
+        }
 
-}
+        server.run(); 
+    }
 
-const CUSTOM_ENDPOINT_PATH: &str = "/rust_ciet_opcua_server";
-fn build_standard_server() -> Server {
 
-    let server_builder = ServerBuilder::new();
+}
 
-    let server_builder = 
-        server_builder.application_name("test server_builder");
+/// adds the DRACS loop to the server, with its own controller 
+/// and sensor data folders and its own polling action
+///
+/// the DRACS loop flow is driven by the driving head (like a 
+/// pump in the cold leg) and by the difference between the hot 
+/// and cold leg temperatures
+///
+/// returns the DRACS controller variables, for SetControllerValue
+fn add_dracs_loop_to_server(
+    server: &mut Server, 
+    ns: u16,
+    polling_interval_milliseconds: u64) -> Vec<ControllerVariable> {
+
+    let dracs_driving_head_node = NodeId::new(ns, "dracs_driving_head");
+    let dracs_hot_leg_temperature_node = NodeId::new(ns, "dracs_hot_leg_temperature");
+    let dracs_cold_leg_temperature_node = NodeId::new(ns, "dracs_cold_leg_temperature");
+    let dracs_loop_mass_flowrate_node = NodeId::new(ns, "dracs_loop_mass_flowrate");
+    let dracs_natural_circulation_driving_head_node = 
+        NodeId::new(ns, "dracs_natural_circulation_driving_head");
+    let dracs_solver_status_node = NodeId::new(ns, "dracs_solver_status");
+
+    // the node ids above move into the polling action
+    let dracs_driving_head_node_id = dracs_driving_head_node.clone();
+    let dracs_hot_leg_temperature_node_id = dracs_hot_leg_temperature_node.clone();
+    let dracs_cold_leg_temperature_node_id = dracs_cold_leg_temperature_node.clone();
+
+    let dracs_facility = OwnedDRACSFacility::new();
+
+    let dracs_component_readings = dracs_facility.get_component_readings();
+
+    let dracs_facility_ptr = Arc::new(Mutex::new(dracs_facility));
 
-    let server_builder =
-        server_builder.application_uri("urn:OPC UA Sample Server");
+    let address_space = server.address_space();
 
+    {
+        let mut address_space = address_space.write();
 
+        let dracs_folder_id = address_space
+            .add_folder("DRACS sensor data", "DRACS sensor data", 
+                        &NodeId::objects_folder_id())
+            .unwrap();
 
+        let _ = address_space.add_variables(
+            vec![
+                Variable::new(&dracs_loop_mass_flowrate_node, 
+                              "dracs_loop_mass_flowrate_kg_per_s", 
+                              "dracs_loop_mass_flowrate_kg_per_s", 0 as f64),
+                Variable::new(&dracs_natural_circulation_driving_head_node, 
+                              "dracs_natural_circulation_driving_head_pa", 
+                              "dracs_natural_circulation_driving_head_pa", 0 as f64),
+                Variable::new(&dracs_solver_status_node, 
+                              "dracs_solver_status", 
+                              "dracs_solver_status", "ok"),
+            ],
+            &dracs_folder_id);
 
-    let ip_address = get_ip_as_str();
+        let dracs_controller_folder_id = address_space
+            .add_folder("DRACS Controller", "DRACS Controller", 
+                        &NodeId::objects_folder_id())
+            .unwrap();
 
-    let server_builder = 
-        server_builder.host_and_port(&ip_address, 4840);
+        // positive driving head pushes fluid down the cold leg
+        VariableBuilder::new(&dracs_driving_head_node, 
+                             "dracs_driving_head_pa", "dracs_driving_head_pa")
+            .data_type(DataTypeId::Float)
+            .value(0 as f64)
+            .organized_by(&dracs_controller_folder_id)
+            .insert(&mut address_space);
 
+        VariableBuilder::new(&dracs_hot_leg_temperature_node, 
+                             "dracs_hot_leg_temperature_degc", 
+                             "dracs_hot_leg_temperature_degc")
+            .data_type(DataTypeId::Float)
+            .value(21 as f64)
+            .organized_by(&dracs_controller_folder_id)
+            .insert(&mut address_space);
 
-    let server_builder =
-        server_builder.discovery_urls(
-            vec![
-            CUSTOM_ENDPOINT_PATH.into(),
-            ]);
+        VariableBuilder::new(&dracs_cold_leg_temperature_node, 
+                             "dracs_cold_leg_temperature_degc", 
+                             "dracs_cold_leg_temperature_degc")
+            .data_type(DataTypeId::Float)
+            .value(21 as f64)
+            .organized_by(&dracs_controller_folder_id)
+            .insert(&mut address_space);
 
+        // the DRACS branches go in the same CIET folder as the 
+        // primary loop branches
+        add_component_nodes(&mut address_space, ns, &dracs_component_readings);
+    }
 
-    // username and password is just anonymous
+    let calculate_dracs_flowrate = move || {
 
-    let user_id_anonymous = config::ANONYMOUS_USER_TOKEN_ID;
+        let mut address_space = address_space.write();
+        let mut dracs_facility = dracs_facility_ptr.lock().unwrap();
+
+        let driving_head_pa = get_clamped_float_variable_value(
+            &mut address_space, 
+            &dracs_driving_head_node, 
+            -MAX_DRACS_DRIVING_HEAD_PA, 
+            MAX_DRACS_DRIVING_HEAD_PA, 
+            0.0);
+
+        // the DRACS fluid may differ from the primary loop fluid,
+        // so its leg temperatures are clamped to its own range
+        let (min_leg_temperature_degc, max_leg_temperature_degc) = 
+            dracs_facility.get_fluid_properties().
+            get_validated_temperature_range_degrees_c();
+
+        let default_leg_temperature_degc = get_default_loop_temperature_degc(
+            min_leg_temperature_degc, max_leg_temperature_degc);
+
+        let hot_leg_temperature_degc = get_clamped_float_variable_value(
+            &mut address_space, 
+            &dracs_hot_leg_temperature_node, 
+            min_leg_temperature_degc, 
+            max_leg_temperature_degc, 
+            default_leg_temperature_degc);
+
+        let cold_leg_temperature_degc = get_clamped_float_variable_value(
+            &mut address_space, 
+            &dracs_cold_leg_temperature_node, 
+            min_leg_temperature_degc, 
+            max_leg_temperature_degc, 
+            default_leg_temperature_degc);
+
+        dracs_facility.set_driving_head(Pressure::new::<pascal>(driving_head_pa));
+        dracs_facility.set_hot_branch_temperature(
+            ThermodynamicTemperature::new::<degree_celsius>(hot_leg_temperature_degc));
+        dracs_facility.set_cold_branch_temperature(
+            ThermodynamicTemperature::new::<degree_celsius>(cold_leg_temperature_degc));
 
+        let now = DateTime::now();
+        let _ = address_space.set_variable_value(
+            dracs_natural_circulation_driving_head_node.clone(), 
+            dracs_facility.get_natural_circulation_driving_head().value,
+            &now, 
+            &now);
 
-    let user_id_vector = 
-        vec![user_id_anonymous]
-        .iter()
-        .map(|u| u.to_string())
-        .collect::<Vec<String>>();
+        match dracs_facility.calculate() {
+            Ok((_calc_time, dracs_loop_mass_flowrate)) => {
+
+                let now = DateTime::now();
+                let _ = address_space.set_variable_value(
+                    dracs_loop_mass_flowrate_node.clone(), 
+                    dracs_loop_mass_flowrate.value,
+                    &now, 
+                    &now);
+
+                let now = DateTime::now();
+                let _ = address_space.set_variable_value(
+                    dracs_solver_status_node.clone(), 
+                    "ok",
+                    &now, 
+                    &now);
+
+                set_component_node_values(
+                    &mut address_space, 
+                    ns, 
+                    &dracs_facility.get_component_readings());
+            },
+            Err(solver_error) => {
+
+                set_variable_bad_status(
+                    &mut address_space, 
+                    &dracs_loop_mass_flowrate_node, 
+                    get_solver_error_status_code(&solver_error));
+
+                let now = DateTime::now();
+                let _ = address_space.set_variable_value(
+                    dracs_solver_status_node.clone(), 
+                    UAString::from(solver_error.to_string()),
+                    &now, 
+                    &now);
+            },
+        }
+    };
 
+    server.add_polling_action(polling_interval_milliseconds, 
+                              calculate_dracs_flowrate);
 
+    return vec![
+        ControllerVariable::new_float("dracs_driving_head_pa", 
+                                      &dracs_driving_head_node_id),
+        ControllerVariable::new_float("dracs_hot_leg_temperature_degc", 
+                                      &dracs_hot_leg_temperature_node_id),
+        ControllerVariable::new_float("dracs_cold_leg_temperature_degc", 
+                                      &dracs_cold_leg_temperature_node_id),
+    ];
+}
 
+/// browse name of the component mass flowrate variables, these 
+/// are marked bad along with the branch flowrates if the solver
+/// fails
+const COMPONENT_MASS_FLOWRATE_READING_NAME: &str = "mass_flowrate_kg_per_s";
 
-    let path = CUSTOM_ENDPOINT_PATH;
+/// returns the node id of the CIET folder, which holds a folder 
+/// for every branch, each holding a folder for every component
+fn get_ciet_folder_node_id(ns: u16) -> NodeId {
+    return NodeId::new(ns, "CIET");
+}
 
+/// returns the node id of one quantity of a component, the id 
+/// is its path within the CIET folder, 
+/// eg. "CIET/ctah_branch/pipe_6a/reynolds_number"
+fn get_component_reading_node_id(
+    ns: u16, 
+    component_reading: &ComponentReading,
+    reading_name: &str) -> NodeId {
+
+    return NodeId::new(ns, format!("CIET/{}/{}/{}", 
+                                   component_reading.branch_name,
+                                   component_reading.component_name,
+                                   reading_name));
+}
 
-    let my_endpoints = vec![
-        ("custom_path", ServerEndpoint::new_none(path,&user_id_vector)),
+/// returns the browse name and value of every quantity 
+/// published for a component
+fn get_component_reading_values(
+    component_reading: &ComponentReading) -> Vec<(&'static str, f64)> {
+
+    return vec![
+        (COMPONENT_MASS_FLOWRATE_READING_NAME, 
+         component_reading.mass_flowrate.value),
+        ("pressure_loss_pa", 
+         component_reading.pressure_loss.value),
+        ("hydrostatic_pressure_change_pa", 
+         component_reading.hydrostatic_pressure_change.value),
+        ("reynolds_number", 
+         component_reading.reynolds_number),
+        ("velocity_m_per_s", 
+         component_reading.velocity_meters_per_second),
+        ("length_m", 
+         component_reading.component_length.value),
+        ("hydraulic_diameter_m", 
+         component_reading.hydraulic_diameter.value),
+        ("incline_angle_degrees", 
+         component_reading.incline_angle.get::<degree>()),
+        ("fluid_temperature_degc", 
+         get_temperature_degrees_c(component_reading.fluid_temperature)),
     ];
+}
 
+/// adds a folder for every branch and component in the readings
+/// to the CIET folder (which is added if it isn't there yet), 
+/// with a variable for every quantity of the component
+fn add_component_nodes(
+    address_space: &mut AddressSpace,
+    ns: u16,
+    component_readings: &Vec<ComponentReading>) {
 
-    let server_builder = 
-        server_builder.endpoints(my_endpoints);
+    let ciet_folder_id = get_ciet_folder_node_id(ns);
+
+    if address_space.find_node(&ciet_folder_id).is_none() {
+        let _ = address_space.add_folder_with_id(
+            &ciet_folder_id, "CIET", "CIET", &NodeId::objects_folder_id());
+    }
+
+    for component_reading in component_readings.iter() {
+
+        let branch_name = component_reading.branch_name.as_str();
+        let component_name = component_reading.component_name.as_str();
+
+        let branch_folder_id = NodeId::new(ns, format!("CIET/{}", branch_name));
+
+        if address_space.find_node(&branch_folder_id).is_none() {
+            let _ = address_space.add_folder_with_id(
+                &branch_folder_id, branch_name, branch_name, &ciet_folder_id);
+        }
+
+        let component_folder_id = NodeId::new(
+            ns, format!("CIET/{}/{}", branch_name, component_name));
+
+        let _ = address_space.add_folder_with_id(
+            &component_folder_id, component_name, component_name, 
+            &branch_folder_id);
+
+        let component_variables: Vec<Variable> = 
+            get_component_reading_values(component_reading).into_iter().
+            map(|(reading_name, reading_value)| Variable::new(
+                    &get_component_reading_node_id(
+                        ns, component_reading, reading_name), 
+                    reading_name, 
+                    reading_name, 
+                    reading_value)).
+            collect();
+
+        let _ = address_space.add_variables(
+            component_variables, 
+            &component_folder_id);
+    }
+}
+
+/// writes the latest readings into the component variables
+/// added by add_component_nodes
+fn set_component_node_values(
+    address_space: &mut AddressSpace,
+    ns: u16,
+    component_readings: &Vec<ComponentReading>) {
+
+    for component_reading in component_readings.iter() {
+
+        for (reading_name, reading_value) in 
+            get_component_reading_values(component_reading) {
+
+            let now = DateTime::now();
+            let _ = address_space.set_variable_value(
+                get_component_reading_node_id(ns, component_reading, reading_name), 
+                reading_value,
+                &now, 
+                &now);
+        }
+    }
+}
+
+/// reads a float variable and clamps it to [min_value, max_value],
+/// if the value had to be clamped, the clamped value is written
+/// back so the client sees what is actually used
+///
+/// anything which is not a finite number is replaced by the
+/// default value
+fn get_clamped_float_variable_value(
+    address_space: &mut AddressSpace,
+    node_id: &NodeId,
+    min_value: f64,
+    max_value: f64,
+    default_value: f64) -> f64 {
+
+    let value: f64 = address_space.
+        get_variable_value(node_id.clone()).
+        ok().
+        and_then(|data_value| data_value.value).
+        and_then(|variant| variant.as_f64()).
+        unwrap_or(default_value);
+
+    let clamped_value = if value.is_finite() {
+        value.max(min_value).min(max_value)
+    } else {
+        default_value
+    };
+
+    if clamped_value != value {
+        let now = DateTime::now();
+        let _ = address_space.set_variable_value(
+            node_id.clone(), 
+            clamped_value,
+            &now, 
+            &now);
+    }
+
+    return clamped_value;
+}
+
+/// maps each kind of solver error onto an OPC UA status code
+/// so clients can tell why the flowrates are not available
+pub(crate) fn get_solver_error_status_code(solver_error: &CIETSolverError) -> StatusCode {
+
+    match solver_error {
+        CIETSolverError::RootNotBracketed { .. } => StatusCode::BadOutOfRange,
+        CIETSolverError::MaxIterationsReached { .. } => StatusCode::BadTimeout,
+        CIETSolverError::NonFiniteResidual { .. } => StatusCode::BadInvalidArgument,
+        CIETSolverError::PropertyOutOfRange { .. } => StatusCode::BadOutOfRange,
+        CIETSolverError::TemperatureOutOfRange { .. } => StatusCode::BadOutOfRange,
+        CIETSolverError::BranchCountMismatch { .. } => StatusCode::BadInvalidArgument,
+    }
+}
+
+/// constructs CIET as the server runs it, at startup and 
+/// whenever a client resets the facility or loads a scenario
+pub(crate) fn construct_ciet_facility() -> OwnedCIETFacility {
+
+    let mut ciet_isothermal_facility = OwnedCIETFacility::new();
+
+    // the pipes and components in ciet are insulated, so they 
+    // lose some heat to the room in thermal hydraulics mode
+    ciet_isothermal_facility.set_insulation(
+        InsulationParameters::ciet_fiberglass());
+
+    return ciet_isothermal_facility;
+}
+
+/// sets a variable to NaN with a bad status code, the client 
+/// then sees the status code instead of a stale value
+fn set_variable_bad_status(
+    address_space: &mut AddressSpace,
+    node_id: &NodeId,
+    status_code: StatusCode){
+
+    let now = DateTime::now();
+
+    if let Some(variable) = address_space.find_variable_mut(node_id.clone()) {
+        let _ = variable.set_value_direct(
+            f64::NAN, 
+            status_code, 
+            &now, 
+            &now);
+    }
+}
+
+/// either solves for steady state, or advances the transient by
+/// one polling interval
+///
+/// in transient mode, the polling interval is also the simulated
+/// timestep so that simulated time keeps pace with the server 
+/// clock
+///
+/// in thermal hydraulics mode, the temperatures are advanced by 
+/// one polling interval either way
+fn calculate_or_step_transient(
+    ciet_isothermal_facility: &mut OwnedCIETFacility,
+    transient_mode: bool,
+    polling_interval: Duration) -> 
+    Result<(Duration, MassRate, MassRate, MassRate), CIETSolverError> {
+
+    if transient_mode {
+        return ciet_isothermal_facility.step_transient(polling_interval);
+    }
+
+    let calculation_result = ciet_isothermal_facility.calculate()?;
+
+    if ciet_isothermal_facility.get_thermal_hydraulics_enabled() {
+        ciet_isothermal_facility.advance_temperatures(polling_interval);
+    }
+
+    return Ok(calculation_result);
+}
+
+/// reads a boolean variable, anything else (or a missing 
+/// variable) is read as false
+fn get_boolean_variable_value(
+    address_space: &AddressSpace,
+    node_id: &NodeId) -> bool {
+
+    return address_space.
+        get_variable_value(node_id.clone()).
+        ok().
+        and_then(|data_value| data_value.value).
+        and_then(|variant| match variant {
+            Variant::Boolean(value) => Some(value),
+            _ => None,
+        }).
+        unwrap_or(false);
+}
+
+/// highest speed the ctah pump may be set to over opcua
+const MAX_CTAH_PUMP_SPEED_RPM: f64 = 3500.0;
+
+/// loop temperature used when a client writes something which
+/// is not a number, this is room temperature
+const DEFAULT_LOOP_TEMPERATURE_DEGC: f64 = 21.0;
+
+/// returns room temperature, or the nearest temperature to it 
+/// within the validated range of the working fluid (eg. FLiBe 
+/// is only validated well above room temperature)
+fn get_default_loop_temperature_degc(
+    min_loop_temperature_degc: f64,
+    max_loop_temperature_degc: f64) -> f64 {
+    return DEFAULT_LOOP_TEMPERATURE_DEGC.
+        max(min_loop_temperature_degc).
+        min(max_loop_temperature_degc);
+}
+
+/// range of ambient (room) temperatures which may be set over 
+/// opcua
+const MIN_AMBIENT_TEMPERATURE_DEGC: f64 = 0.0;
+const MAX_AMBIENT_TEMPERATURE_DEGC: f64 = 50.0;
+
+/// highest electrical power the heater may be set to over opcua
+const MAX_HEATER_POWER_WATTS: f64 = 10000.0;
+
+/// range of external reactivity (dollars) which may be inserted
+/// over opcua, kept below prompt critical
+const MIN_EXTERNAL_REACTIVITY_DOLLARS: f64 = -10.0;
+const MAX_EXTERNAL_REACTIVITY_DOLLARS: f64 = 0.9;
+
+/// largest DRACS driving head (either way) which may be set 
+/// over opcua
+const MAX_DRACS_DRIVING_HEAD_PA: f64 = 5000.0;
+
+/// highest speed the ctah fan may be set to over opcua, this is
+/// the rated speed of CTAHHeatExchanger::ciet_ctah
+const MAX_CTAH_FAN_SPEED_RPM: f64 = 1750.0;
+
+fn build_standard_server(server_config: &CIETServerConfig) -> Server {
+
+    let server_builder = get_server_builder(server_config, server_config.port);
+
+    // the certificate, users and endpoints (none, Basic256Sha256 
+    // Sign and SignAndEncrypt) all come from the server security
+    let server_builder = server_config.security.configure_server_builder(
+        server_builder, 
+        &server_config.endpoint_path);
 
 
     // then we build the server
@@ -541,14 +1645,48 @@ fn build_standard_server() -> Server {
 
 }
 
-fn get_ip_as_str() -> String {
+// the control server has only the controller variables, on the 
+// control port, and only lets in operators (see 
+// ServerSecurity::configure_control_server_builder)
+fn build_control_server(server_config: &CIETServerConfig) -> Option<Server> {
+
+    let server_builder = get_server_builder(server_config, server_config.control_port);
+
+    let server_builder = server_config.security.configure_control_server_builder(
+        server_builder, 
+        &server_config.endpoint_path)?;
+
+    return Some(server_builder.server().unwrap());
+}
+
+// application name and uri, host and port, the same for the 
+// main and control servers apart from the port
+fn get_server_builder(server_config: &CIETServerConfig, port: u16) -> ServerBuilder {
+
+    let server_builder = ServerBuilder::new();
+
+    let server_builder = 
+        server_builder.application_name(server_config.application_name.as_str());
+
+    let server_builder =
+        server_builder.application_uri(server_config.application_uri.as_str());
 
-    let my_local_ip = local_ip().unwrap();
 
-    // i can convert it to a string
 
-    let ip_add_string : String = my_local_ip.to_string();
 
-    return ip_add_string;
+    // 127.0.0.1 or 0.0.0.0 work where there is no routable 
+    // interface, eg. in sandboxes
+    let ip_address = server_config.get_bind_host();
+
+    let server_builder = 
+        server_builder.host_and_port(&ip_address, port);
+
+
+    let server_builder =
+        server_builder.discovery_urls(
+            vec![
+            server_config.endpoint_path.clone(),
+            ]);
 
+    return server_builder;
 }
@@ -0,0 +1,188 @@
+// This application was developed for use in my PhD thesis under supervision 
+// of Professor Per F. Peterson. It is part of a thermal hydraulics
+// library in Rust that is released under the GNU General Public License
+// v 3.0. This is partly due to the fact that some of the libraries 
+// inherit from GeN-Foam and OpenFOAM, both licensed under GNU General
+// Public License v3.0.
+//
+// As such, the entire library is released under GNU GPL v3.0. It is a strong 
+// copyleft license which means you cannot use it in proprietary software.
+//
+//
+// License
+//    This application is part ciet_digital_twin_rust which uses
+//    fluid_mechanics_rust, a partial library of the
+//    thermal hydraulics library written in rust meant to help with the
+//    fluid mechanics aspects of the calculations
+//     
+//    Copyright (C) 2022-2023  Theodore Kay Chen Ong, Singapore Nuclear
+//    Research and Safety Initiative, Per F. Peterson, University of 
+//    California, Berkeley Thermal Hydraulics Laboratory
+//
+//    ciet_digital_twin_rust is free software; you can redistribute it and/or modify it
+//    under the terms of the GNU General Public License as published by the
+//    Free Software Foundation; either version 2 of the License, or (at your
+//    option) any later version.
+//
+//    ciet_digital_twin_rust is distributed in the hope that it will be useful, but WITHOUT
+//    ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or
+//    FITNESS FOR A PARTICULAR PURPOSE.  See the GNU General Public License
+//    for more details.
+//
+//    ciet_digital_twin_rust relies on a thermal hydraulics library
+//    also written in rust. The isothermal digital twin in particular
+//    relies on fluid_mechanics_rust.
+//    This library is part of a thermal hydraulics library in rust
+//    and contains some code copied from GeN-Foam, and OpenFOAM derivative.
+//    This offering is not approved or endorsed by the OpenFOAM Foundation nor
+//    OpenCFD Limited, producer and distributor of the OpenFOAM(R)software via
+//    www.openfoam.com, and owner of the OPENFOAM(R) and OpenCFD(R) trademarks.
+//    Nor is it endorsed by the authors and owners of GeN-Foam.
+//
+//    You should have received a copy of the GNU General Public License
+//    along with this program.  If not, see <http://www.gnu.org/licenses/>.
+//
+// © All rights reserved. Theodore Kay Chen Ong,
+// Singapore Nuclear Research and Safety Initiative,
+// Per F. Peterson,
+// University of California, Berkeley Thermal Hydraulics Laboratory
+//
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use opcua::server::prelude::*;
+use opcua::sync::RwLock;
+
+use super::simulation_control::ControllerVariable;
+
+/// adds the controller variables to the "Controller" folder of 
+/// the control server, writable and starting at their values on
+/// the main server
+///
+/// only users with CONTROLLER_WRITE_ROLE can connect to the 
+/// control server, so only they can write these
+///
+/// returns the mirror which keeps the two servers in step
+pub fn add_controller_variables_to_control_server(
+    control_server: &Server,
+    address_space: &AddressSpace,
+    controller_variables: &Vec<ControllerVariable>) -> ControllerMirror {
+
+    let control_address_space = control_server.address_space();
+
+    let mut controller_nodes: Vec<(NodeId, NodeId)> = vec![];
+    let mut last_control_values: HashMap<NodeId, Variant> = HashMap::new();
+
+    {
+        let mut control_address_space = control_address_space.write();
+
+        let control_ns = control_address_space
+            .register_namespace("urn:simple-server")
+            .unwrap();
+
+        let folder_id = control_address_space
+            .add_folder("Controller", "Controller", &NodeId::objects_folder_id())
+            .unwrap();
+
+        for controller_variable in controller_variables.iter() {
+
+            // same identifier as on the main server, so clients 
+            // can use the same node ids on both
+            let control_node = NodeId::new(
+                control_ns, controller_variable.node_id.identifier.clone());
+
+            let (data_type, default_value) = if controller_variable.is_boolean {
+                (DataTypeId::Boolean, Variant::from(false))
+            } else {
+                (DataTypeId::Float, Variant::from(0 as f64))
+            };
+
+            let value = get_variable_variant(
+                address_space, &controller_variable.node_id).
+                unwrap_or(default_value);
+
+            VariableBuilder::new(&control_node, 
+                                 controller_variable.browse_name.as_str(), 
+                                 controller_variable.browse_name.as_str())
+                .data_type(data_type)
+                .value(value.clone())
+                .writable()
+                .organized_by(&folder_id)
+                .insert(&mut control_address_space);
+
+            last_control_values.insert(control_node.clone(), value);
+            controller_nodes.push(
+                (controller_variable.node_id.clone(), control_node));
+        }
+    }
+
+    return ControllerMirror { 
+        control_address_space: control_address_space, 
+        controller_nodes: controller_nodes, 
+        last_control_values: last_control_values,
+    };
+}
+
+/// keeps the controller variables of the main server and the 
+/// control server in step
+///
+/// the main server's variables are the ones the polling actions
+/// read, SetControllerValue, Reset and LoadScenario write and 
+/// the clamps correct
+pub struct ControllerMirror {
+    control_address_space: Arc<RwLock<AddressSpace>>,
+
+    // (main server node, control server node)
+    controller_nodes: Vec<(NodeId, NodeId)>,
+
+    // what the control server showed after the last 
+    // synchronise, anything else was written by an operator
+    last_control_values: HashMap<NodeId, Variant>,
+}
+
+impl ControllerMirror {
+
+    /// copies the values operators wrote on the control server 
+    /// since the last call to the main server, then copies every
+    /// value on the main server back, so the control server 
+    /// shows the values in use
+    pub fn synchronise(&mut self, address_space: &mut AddressSpace){
+
+        let mut control_address_space = self.control_address_space.write();
+
+        for (node, control_node) in self.controller_nodes.iter() {
+
+            let control_value = get_variable_variant(
+                &control_address_space, control_node);
+
+            if let Some(control_value) = control_value {
+                if self.last_control_values.get(control_node) != Some(&control_value) {
+                    let now = DateTime::now();
+                    let _ = address_space.set_variable_value(
+                        node.clone(), 
+                        control_value,
+                        &now, 
+                        &now);
+                }
+            }
+
+            if let Some(value) = get_variable_variant(address_space, node) {
+                let now = DateTime::now();
+                let _ = control_address_space.set_variable_value(
+                    control_node.clone(), 
+                    value.clone(),
+                    &now, 
+                    &now);
+
+                self.last_control_values.insert(control_node.clone(), value);
+            }
+        }
+    }
+}
+
+fn get_variable_variant(address_space: &AddressSpace, 
+                        node_id: &NodeId) -> Option<Variant> {
+    return address_space.get_variable_value(node_id.clone()).
+        ok().
+        and_then(|data_value| data_value.value);
+}
@@ -54,3 +54,21 @@
 /// ciet server
 pub mod ciet_server;
 pub use ciet_server::*;
+
+/// simulation control methods for the ciet server
+pub mod simulation_control;
+pub use simulation_control::*;
+
+/// endpoints, certificates and users for the ciet server
+pub mod server_security;
+pub use server_security::*;
+
+/// control server where operators write the controller 
+/// variables
+pub mod control_server;
+pub use control_server::*;
+
+/// configuration file and command line options for the ciet 
+/// server
+pub mod server_config;
+pub use server_config::*;
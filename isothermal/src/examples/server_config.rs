@@ -0,0 +1,364 @@
+// This application was developed for use in my PhD thesis under supervision 
+// of Professor Per F. Peterson. It is part of a thermal hydraulics
+// library in Rust that is released under the GNU General Public License
+// v 3.0. This is partly due to the fact that some of the libraries 
+// inherit from GeN-Foam and OpenFOAM, both licensed under GNU General
+// Public License v3.0.
+//
+// As such, the entire library is released under GNU GPL v3.0. It is a strong 
+// copyleft license which means you cannot use it in proprietary software.
+//
+//
+// License
+//    This application is part ciet_digital_twin_rust which uses
+//    fluid_mechanics_rust, a partial library of the
+//    thermal hydraulics library written in rust meant to help with the
+//    fluid mechanics aspects of the calculations
+//     
+//    Copyright (C) 2022-2023  Theodore Kay Chen Ong, Singapore Nuclear
+//    Research and Safety Initiative, Per F. Peterson, University of 
+//    California, Berkeley Thermal Hydraulics Laboratory
+//
+//    ciet_digital_twin_rust is free software; you can redistribute it and/or modify it
+//    under the terms of the GNU General Public License as published by the
+//    Free Software Foundation; either version 2 of the License, or (at your
+//    option) any later version.
+//
+//    ciet_digital_twin_rust is distributed in the hope that it will be useful, but WITHOUT
+//    ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or
+//    FITNESS FOR A PARTICULAR PURPOSE.  See the GNU General Public License
+//    for more details.
+//
+//    ciet_digital_twin_rust relies on a thermal hydraulics library
+//    also written in rust. The isothermal digital twin in particular
+//    relies on fluid_mechanics_rust.
+//    This library is part of a thermal hydraulics library in rust
+//    and contains some code copied from GeN-Foam, and OpenFOAM derivative.
+//    This offering is not approved or endorsed by the OpenFOAM Foundation nor
+//    OpenCFD Limited, producer and distributor of the OpenFOAM(R)software via
+//    www.openfoam.com, and owner of the OPENFOAM(R) and OpenCFD(R) trademarks.
+//    Nor is it endorsed by the authors and owners of GeN-Foam.
+//
+//    You should have received a copy of the GNU General Public License
+//    along with this program.  If not, see <http://www.gnu.org/licenses/>.
+//
+// © All rights reserved. Theodore Kay Chen Ong,
+// Singapore Nuclear Research and Safety Initiative,
+// Per F. Peterson,
+// University of California, Berkeley Thermal Hydraulics Laboratory
+//
+// Main author of the code: Theodore Kay Chen Ong, supervised by
+// Professor Per F. Peterson
+use std::path::Path;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use local_ip_address::local_ip;
+use serde::Deserialize;
+
+use super::server_security::ServerSecurity;
+
+/// the server reads this file from the working directory if no 
+/// --config is given, and runs on the defaults if it isn't there
+pub const DEFAULT_CONFIG_PATH: &str = "ciet_server.toml";
+
+/// binds to the address of the local network interface
+pub const AUTO_HOST: &str = "auto";
+
+/// used when AUTO_HOST is asked for but there is no local 
+/// network interface, eg. in a sandbox
+pub const LOOPBACK_HOST: &str = "127.0.0.1";
+
+/// binds to every interface
+pub const UNSPECIFIED_HOST: &str = "0.0.0.0";
+
+pub const DEFAULT_PORT: u16 = 4840;
+
+/// port of the control server, where users with the operator 
+/// role write the controller variables
+pub const DEFAULT_CONTROL_PORT: u16 = 4850;
+
+pub const DEFAULT_ENDPOINT_PATH: &str = "/rust_ciet_opcua_server";
+
+/// how often the polling actions run, in transient mode, this 
+/// is also the simulated timestep so that simulated time keeps 
+/// pace with the server clock
+pub const DEFAULT_POLLING_INTERVAL_MILLISECONDS: u64 = 500;
+
+pub const DEFAULT_ENDPOINT_PRINT_INTERVAL_MILLISECONDS: u64 = 5000;
+
+pub const DEFAULT_APPLICATION_NAME: &str = "test server_builder";
+
+pub const DEFAULT_APPLICATION_URI: &str = "urn:OPC UA Sample Server";
+
+/// the ctah pump pressure is clamped to plus or minus this
+pub const DEFAULT_MAX_CTAH_PUMP_PRESSURE_PA: f64 = 45_000.0;
+
+/// settings for the ciet server, read from a TOML file with 
+/// command line overrides
+///
+/// every key is optional, missing keys take the defaults above,
+/// for example:
+///
+/// ```toml
+/// host = "0.0.0.0"
+/// port = 4840
+/// control_port = 4850
+/// polling_interval_milliseconds = 500
+///
+/// [security]
+/// sign_endpoint = true
+///
+/// [[security.users]]
+/// user_name = "alice"
+/// password = "secret"
+/// role = "operator"
+/// ```
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct CIETServerConfig {
+    /// "auto", an ip address (eg. 127.0.0.1 or 0.0.0.0) or a 
+    /// host name
+    pub host: String,
+    pub port: u16,
+    pub control_port: u16,
+    pub endpoint_path: String,
+    pub polling_interval_milliseconds: u64,
+    pub endpoint_print_interval_milliseconds: u64,
+    pub application_name: String,
+    pub application_uri: String,
+    pub max_ctah_pump_pressure_pa: f64,
+    pub security: ServerSecurity,
+}
+
+impl Default for CIETServerConfig {
+    fn default() -> Self {
+        return Self { 
+            host: AUTO_HOST.to_string(), 
+            port: DEFAULT_PORT, 
+            control_port: DEFAULT_CONTROL_PORT, 
+            endpoint_path: DEFAULT_ENDPOINT_PATH.to_string(), 
+            polling_interval_milliseconds: DEFAULT_POLLING_INTERVAL_MILLISECONDS, 
+            endpoint_print_interval_milliseconds: 
+                DEFAULT_ENDPOINT_PRINT_INTERVAL_MILLISECONDS, 
+            application_name: DEFAULT_APPLICATION_NAME.to_string(), 
+            application_uri: DEFAULT_APPLICATION_URI.to_string(), 
+            max_ctah_pump_pressure_pa: DEFAULT_MAX_CTAH_PUMP_PRESSURE_PA, 
+            security: ServerSecurity::default(),
+        };
+    }
+}
+
+/// what the command line options are, printed when they can't 
+/// be parsed
+pub const COMMAND_LINE_USAGE: &str = "\
+options (each overrides the configuration file):
+    --config <path>                          TOML configuration file
+    --host <auto|ip address|host name>
+    --port <port>
+    --control-port <port>
+    --endpoint-path <path>
+    --polling-interval-ms <milliseconds>
+    --endpoint-print-interval-ms <milliseconds>
+    --application-name <name>
+    --application-uri <uri>
+    --max-ctah-pump-pressure-pa <pascals>";
+
+impl CIETServerConfig {
+
+    /// parses a TOML configuration, and checks it
+    pub fn from_toml_str(config_string: &str) -> Result<Self, String> {
+
+        let config: Self = toml::from_str(config_string).
+            map_err(|error| error.to_string())?;
+
+        config.validate()?;
+
+        return Ok(config);
+    }
+
+    /// reads a TOML configuration file
+    pub fn from_file(config_path: &Path) -> Result<Self, String> {
+
+        let config_string = std::fs::read_to_string(config_path).
+            map_err(|error| format!("{}: {}", config_path.display(), error))?;
+
+        return Self::from_toml_str(&config_string).
+            map_err(|error_message| 
+                    format!("{}: {}", config_path.display(), error_message));
+    }
+
+    /// reads the configuration file given by --config (or 
+    /// DEFAULT_CONFIG_PATH if it exists), then applies the other
+    /// command line options over it
+    ///
+    /// the arguments should not include the program name
+    ///
+    /// users given in the users environment variable replace 
+    /// those in the file, so passwords can be kept out of it
+    pub fn from_args<I>(args: I) -> Result<Self, String> 
+        where I: IntoIterator<Item = String> {
+
+        let args: Vec<String> = args.into_iter().collect();
+
+        // options come in pairs, the configuration file is read 
+        // first whichever position --config is in
+        if args.len() % 2 != 0 {
+            return Err(format!("{} has no value", args[args.len() - 1]));
+        }
+
+        let options: Vec<(&str, &str)> = args.chunks(2).
+            map(|option| (option[0].as_str(), option[1].as_str())).
+            collect();
+
+        let config_path = options.iter().
+            find(|(option_name, _)| *option_name == "--config").
+            map(|(_, config_path)| PathBuf::from(config_path));
+
+        let mut config = match config_path {
+            Some(config_path) => Self::from_file(&config_path)?,
+            None => {
+                let default_config_path = PathBuf::from(DEFAULT_CONFIG_PATH);
+
+                if default_config_path.exists() {
+                    Self::from_file(&default_config_path)?
+                } else {
+                    Self::default()
+                }
+            },
+        };
+
+        for (option_name, option_value) in options.into_iter() {
+            config.apply_command_line_option(option_name, option_value)?;
+        }
+
+        config.security.apply_environment();
+
+        config.validate()?;
+
+        return Ok(config);
+    }
+
+    fn apply_command_line_option(&mut self, 
+                                 option_name: &str, 
+                                 option_value: &str) -> Result<(), String> {
+
+        fn parse<T: std::str::FromStr>(option_name: &str, 
+                                       option_value: &str) -> Result<T, String> {
+            return option_value.parse::<T>().
+                map_err(|_| format!("{} {} is not valid", option_name, option_value));
+        }
+
+        match option_name {
+            "--config" => (),
+            "--host" => self.host = option_value.to_string(),
+            "--port" => self.port = parse(option_name, option_value)?,
+            "--control-port" => self.control_port = parse(option_name, option_value)?,
+            "--endpoint-path" => self.endpoint_path = option_value.to_string(),
+            "--polling-interval-ms" => self.polling_interval_milliseconds = 
+                parse(option_name, option_value)?,
+            "--endpoint-print-interval-ms" => 
+                self.endpoint_print_interval_milliseconds = 
+                parse(option_name, option_value)?,
+            "--application-name" => self.application_name = option_value.to_string(),
+            "--application-uri" => self.application_uri = option_value.to_string(),
+            "--max-ctah-pump-pressure-pa" => self.max_ctah_pump_pressure_pa = 
+                parse(option_name, option_value)?,
+            _ => return Err(format!("unknown option {}", option_name)),
+        }
+
+        return Ok(());
+    }
+
+    /// checks that the server can run with these settings
+    pub fn validate(&self) -> Result<(), String> {
+
+        if self.host.trim().is_empty() {
+            return Err("host is empty".to_string());
+        }
+
+        if self.port == 0 {
+            return Err("port must not be 0".to_string());
+        }
+
+        if self.control_port == 0 {
+            return Err("control port must not be 0".to_string());
+        }
+
+        if self.control_port == self.port {
+            return Err(format!("control port {} is the same as the port", 
+                               self.control_port));
+        }
+
+        if !self.endpoint_path.starts_with('/') {
+            return Err(format!("endpoint path {} must start with /", 
+                               self.endpoint_path));
+        }
+
+        if self.polling_interval_milliseconds == 0 {
+            return Err("polling interval must be more than 0 ms".to_string());
+        }
+
+        if self.endpoint_print_interval_milliseconds == 0 {
+            return Err("endpoint print interval must be more than 0 ms".to_string());
+        }
+
+        if !self.max_ctah_pump_pressure_pa.is_finite() || 
+            self.max_ctah_pump_pressure_pa <= 0.0 {
+            return Err(format!("max ctah pump pressure {} Pa must be positive", 
+                               self.max_ctah_pump_pressure_pa));
+        }
+
+        return Ok(());
+    }
+
+    pub fn get_polling_interval(&self) -> Duration {
+        return Duration::from_millis(self.polling_interval_milliseconds);
+    }
+
+    /// the address the server binds to, "auto" is the local 
+    /// network interface, or the loopback address if there 
+    /// isn't one
+    pub fn get_bind_host(&self) -> String {
+
+        if self.host != AUTO_HOST {
+            return self.host.clone();
+        }
+
+        return get_local_ip_or_loopback();
+    }
+
+    /// the url clients connect to, a server bound to every 
+    /// interface is reached on the local network interface
+    pub fn get_endpoint_url(&self) -> String {
+        return self.get_url_on_port(self.port);
+    }
+
+    /// the url operators connect to in order to write the 
+    /// controller variables
+    pub fn get_control_endpoint_url(&self) -> String {
+        return self.get_url_on_port(self.control_port);
+    }
+
+    fn get_url_on_port(&self, port: u16) -> String {
+
+        let mut endpoint_host = self.get_bind_host();
+
+        if endpoint_host == UNSPECIFIED_HOST {
+            endpoint_host = get_local_ip_or_loopback();
+        }
+
+        return format!("opc.tcp://{}:{}{}", 
+                       endpoint_host, port, self.endpoint_path);
+    }
+}
+
+/// the address of the local network interface, or the loopback
+/// address if there isn't one
+pub fn get_local_ip_or_loopback() -> String {
+
+    match local_ip() {
+        Ok(my_local_ip) => return my_local_ip.to_string(),
+        Err(_) => return LOOPBACK_HOST.to_string(),
+    }
+}
@@ -0,0 +1,391 @@
+// This application was developed for use in my PhD thesis under supervision 
+// of Professor Per F. Peterson. It is part of a thermal hydraulics
+// library in Rust that is released under the GNU General Public License
+// v 3.0. This is partly due to the fact that some of the libraries 
+// inherit from GeN-Foam and OpenFOAM, both licensed under GNU General
+// Public License v3.0.
+//
+// As such, the entire library is released under GNU GPL v3.0. It is a strong 
+// copyleft license which means you cannot use it in proprietary software.
+//
+//
+// License
+//    This application is part ciet_digital_twin_rust which uses
+//    fluid_mechanics_rust, a partial library of the
+//    thermal hydraulics library written in rust meant to help with the
+//    fluid mechanics aspects of the calculations
+//     
+//    Copyright (C) 2022-2023  Theodore Kay Chen Ong, Singapore Nuclear
+//    Research and Safety Initiative, Per F. Peterson, University of 
+//    California, Berkeley Thermal Hydraulics Laboratory
+//
+//    ciet_digital_twin_rust is free software; you can redistribute it and/or modify it
+//    under the terms of the GNU General Public License as published by the
+//    Free Software Foundation; either version 2 of the License, or (at your
+//    option) any later version.
+//
+//    ciet_digital_twin_rust is distributed in the hope that it will be useful, but WITHOUT
+//    ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or
+//    FITNESS FOR A PARTICULAR PURPOSE.  See the GNU General Public License
+//    for more details.
+//
+//    ciet_digital_twin_rust relies on a thermal hydraulics library
+//    also written in rust. The isothermal digital twin in particular
+//    relies on fluid_mechanics_rust.
+//    This library is part of a thermal hydraulics library in rust
+//    and contains some code copied from GeN-Foam, and OpenFOAM derivative.
+//    This offering is not approved or endorsed by the OpenFOAM Foundation nor
+//    OpenCFD Limited, producer and distributor of the OpenFOAM(R)software via
+//    www.openfoam.com, and owner of the OPENFOAM(R) and OpenCFD(R) trademarks.
+//    Nor is it endorsed by the authors and owners of GeN-Foam.
+//
+//    You should have received a copy of the GNU General Public License
+//    along with this program.  If not, see <http://www.gnu.org/licenses/>.
+//
+// © All rights reserved. Theodore Kay Chen Ong,
+// Singapore Nuclear Research and Safety Initiative,
+// Per F. Peterson,
+// University of California, Berkeley Thermal Hydraulics Laboratory
+//
+// Main author of the code: Theodore Kay Chen Ong, supervised by
+// Professor Per F. Peterson
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use opcua::server::prelude::*;
+use opcua::server::config;
+use opcua::server::identity_token::IdentityToken;
+use opcua::server::session::SessionManager;
+use opcua::sync::RwLock;
+
+use serde::Deserialize;
+
+/// where the server looks for its certificate and private key
+pub const DEFAULT_PKI_DIR: &str = "./pki";
+
+/// the bundled certificate, relative to the pki directory
+pub const DEFAULT_CERTIFICATE_PATH: &str = "own/cert.der";
+
+/// the bundled private key, relative to the pki directory
+pub const DEFAULT_PRIVATE_KEY_PATH: &str = "private/private.pem";
+
+/// users are read from this environment variable as 
+/// comma separated user_name:password:role entries, eg.
+///
+/// CIET_SERVER_USERS="alice:secret:operator,bob:secret2:supervisor"
+pub const SERVER_USERS_ENVIRONMENT_VARIABLE: &str = "CIET_SERVER_USERS";
+
+/// users need this role to log in to the control server, which
+/// is where the controller variables are written
+pub const CONTROLLER_WRITE_ROLE: CIETServerRole = CIETServerRole::Operator;
+
+/// what a logged in user may do, anonymous users have no role 
+/// and may only read
+///
+/// roles are ordered, a supervisor may do anything an operator 
+/// may do
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CIETServerRole {
+    /// writes controller values, pauses, resumes, steps and 
+    /// solves the facility
+    Operator,
+    /// additionally resets the facility and loads scenarios
+    Supervisor,
+}
+
+impl CIETServerRole {
+
+    pub fn from_name(role_name: &str) -> Option<Self> {
+        match role_name.trim().to_lowercase().as_str() {
+            "operator" => Some(Self::Operator),
+            "supervisor" => Some(Self::Supervisor),
+            _ => None,
+        }
+    }
+
+    /// true if this role may do what the required role may do
+    pub fn includes(&self, required_role: CIETServerRole) -> bool {
+        return *self >= required_role;
+    }
+}
+
+/// a username and password user of the server
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct CIETServerUser {
+    pub user_name: String,
+    pub password: String,
+    pub role: CIETServerRole,
+}
+
+impl CIETServerUser {
+
+    /// id of this user's token in the server configuration
+    pub fn get_user_token_id(&self) -> String {
+        return format!("user_{}", self.user_name);
+    }
+}
+
+/// which endpoints the server offers, the certificate it signs 
+/// and encrypts with, and who may log in
+///
+/// anonymous users may connect on any endpoint, username and 
+/// password users only on the Basic256Sha256 endpoints so that
+/// passwords never go out in the clear
+///
+/// opcua 0.11 checks variable writes against the access level
+/// of the variable alone, not against the user who wrote it, so
+/// the controller variables on the main server are read only. 
+/// They are writable on the control server, whose endpoints 
+/// only let in users with CONTROLLER_WRITE_ROLE
+///
+/// this is the [security] table of the server configuration 
+/// file, missing keys take the defaults
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ServerSecurity {
+    pub none_endpoint: bool,
+    pub sign_endpoint: bool,
+    pub sign_and_encrypt_endpoint: bool,
+    pub pki_dir: PathBuf,
+    pub certificate_path: PathBuf,
+    pub private_key_path: PathBuf,
+    pub users: Vec<CIETServerUser>,
+}
+
+impl Default for ServerSecurity {
+    fn default() -> Self {
+        return Self { 
+            none_endpoint: true, 
+            sign_endpoint: true, 
+            sign_and_encrypt_endpoint: true, 
+            pki_dir: PathBuf::from(DEFAULT_PKI_DIR), 
+            certificate_path: PathBuf::from(DEFAULT_CERTIFICATE_PATH), 
+            private_key_path: PathBuf::from(DEFAULT_PRIVATE_KEY_PATH), 
+            users: vec![],
+        };
+    }
+}
+
+impl ServerSecurity {
+
+    /// default endpoints with the users from 
+    /// SERVER_USERS_ENVIRONMENT_VARIABLE
+    pub fn from_environment() -> Self {
+
+        let mut server_security = Self::default();
+        server_security.apply_environment();
+
+        return server_security;
+    }
+
+    /// replaces the users with those in 
+    /// SERVER_USERS_ENVIRONMENT_VARIABLE if it is set, users 
+    /// which can't be parsed are left out with a warning
+    pub fn apply_environment(&mut self){
+
+        if let Ok(users_string) = std::env::var(SERVER_USERS_ENVIRONMENT_VARIABLE) {
+            match Self::parse_users(&users_string) {
+                Ok(users) => self.users = users,
+                Err(error_message) => println!(
+                    "ignoring {}: {}", 
+                    SERVER_USERS_ENVIRONMENT_VARIABLE, 
+                    error_message),
+            }
+        }
+    }
+
+    /// parses comma separated user_name:password:role entries
+    pub fn parse_users(users_string: &str) -> Result<Vec<CIETServerUser>, String> {
+
+        let mut users: Vec<CIETServerUser> = vec![];
+
+        for user_entry in users_string.split(',').
+            map(|user_entry| user_entry.trim()).
+            filter(|user_entry| !user_entry.is_empty()) {
+
+            // the password may itself contain colons, so i split
+            // the user name off the front and the role off the 
+            // back
+            let (user_name, password_and_role) = user_entry.split_once(':').
+                ok_or(format!("{} is not user_name:password:role", user_entry))?;
+            let (password, role_name) = password_and_role.rsplit_once(':').
+                ok_or(format!("{} is not user_name:password:role", user_entry))?;
+
+            let role = CIETServerRole::from_name(role_name).
+                ok_or(format!("{} is not a role", role_name))?;
+
+            if user_name.is_empty() || password.is_empty() {
+                return Err(format!("{} has an empty user name or password", 
+                                   user_entry));
+            }
+
+            if users.iter().any(|user| user.user_name == user_name) {
+                return Err(format!("{} is given more than once", user_name));
+            }
+
+            users.push(CIETServerUser { 
+                user_name: user_name.to_string(), 
+                password: password.to_string(), 
+                role: role,
+            });
+        }
+
+        return Ok(users);
+    }
+
+    /// returns the role of a username and password user, 
+    /// anonymous and unknown users have none
+    pub fn get_user_role(&self, user_name: &str) -> Option<CIETServerRole> {
+        return self.users.iter().
+            find(|user| user.user_name == user_name).
+            map(|user| user.role);
+    }
+
+    /// users who may write the controller variables
+    pub fn get_controller_writers(&self) -> Vec<&CIETServerUser> {
+        return self.users.iter().
+            filter(|user| user.role.includes(CONTROLLER_WRITE_ROLE)).
+            collect();
+    }
+
+    // the bundled keypair is used as is, the server should not 
+    // quietly make its own
+    fn configure_certificate(&self, server_builder: ServerBuilder) -> ServerBuilder {
+        return server_builder
+            .pki_dir(self.pki_dir.clone())
+            .certificate_path(self.certificate_path.clone())
+            .private_key_path(self.private_key_path.clone())
+            .create_sample_keypair(false);
+    }
+
+    /// sets up the certificate, user tokens and endpoints on the
+    /// server builder
+    pub fn configure_server_builder(
+        &self, 
+        server_builder: ServerBuilder,
+        endpoint_path: &str) -> ServerBuilder {
+
+        let mut server_builder = self.configure_certificate(server_builder);
+
+        let anonymous_user_token_ids = vec![
+            config::ANONYMOUS_USER_TOKEN_ID.to_string()
+        ];
+
+        let mut secure_user_token_ids = anonymous_user_token_ids.clone();
+
+        for user in self.users.iter() {
+            server_builder = server_builder.user_token(
+                user.get_user_token_id(), 
+                ServerUserToken::user_pass(&user.user_name, &user.password));
+
+            secure_user_token_ids.push(user.get_user_token_id());
+        }
+
+        let mut endpoints = vec![];
+
+        if self.none_endpoint {
+            endpoints.push(
+                ("none", ServerEndpoint::new_none(
+                        endpoint_path, &anonymous_user_token_ids)));
+        }
+
+        if self.sign_endpoint {
+            endpoints.push(
+                ("basic256sha256_sign", ServerEndpoint::new_basic256sha256_sign(
+                        endpoint_path, &secure_user_token_ids)));
+        }
+
+        if self.sign_and_encrypt_endpoint {
+            endpoints.push(
+                ("basic256sha256_sign_encrypt", 
+                 ServerEndpoint::new_basic256sha256_sign_encrypt(
+                        endpoint_path, &secure_user_token_ids)));
+        }
+
+        return server_builder.endpoints(endpoints);
+    }
+
+    /// sets up the control server, which has only the 
+    /// Basic256Sha256 endpoints and only the users with 
+    /// CONTROLLER_WRITE_ROLE, anonymous users can't connect
+    ///
+    /// returns None if there are no such users or no 
+    /// Basic256Sha256 endpoints, then there is no control server
+    pub fn configure_control_server_builder(
+        &self, 
+        server_builder: ServerBuilder,
+        endpoint_path: &str) -> Option<ServerBuilder> {
+
+        let controller_writers = self.get_controller_writers();
+
+        if controller_writers.is_empty() || 
+            !(self.sign_endpoint || self.sign_and_encrypt_endpoint) {
+            return None;
+        }
+
+        let mut server_builder = self.configure_certificate(server_builder);
+
+        let mut controller_writer_token_ids = vec![];
+
+        for user in controller_writers.into_iter() {
+            server_builder = server_builder.user_token(
+                user.get_user_token_id(), 
+                ServerUserToken::user_pass(&user.user_name, &user.password));
+
+            controller_writer_token_ids.push(user.get_user_token_id());
+        }
+
+        let mut endpoints = vec![];
+
+        if self.sign_endpoint {
+            endpoints.push(
+                ("basic256sha256_sign", ServerEndpoint::new_basic256sha256_sign(
+                        endpoint_path, &controller_writer_token_ids)));
+        }
+
+        if self.sign_and_encrypt_endpoint {
+            endpoints.push(
+                ("basic256sha256_sign_encrypt", 
+                 ServerEndpoint::new_basic256sha256_sign_encrypt(
+                        endpoint_path, &controller_writer_token_ids)));
+        }
+
+        return Some(server_builder.endpoints(endpoints));
+    }
+
+    /// returns the role of the user who opened the session, 
+    /// anonymous sessions have none
+    pub fn get_session_role(
+        &self,
+        session_id: &NodeId,
+        session_manager: Arc<RwLock<SessionManager>>) -> Option<CIETServerRole> {
+
+        let session_manager = session_manager.read();
+        let session = session_manager.find_session_by_id(session_id)?;
+        let session = session.read();
+
+        match session.user_identity() {
+            IdentityToken::UserNameIdentityToken(user_name_token) => {
+                let user_name = user_name_token.user_name.as_ref();
+                return self.get_user_role(user_name);
+            },
+            _ => return None,
+        }
+    }
+
+    /// for method callbacks, errors with BadUserAccessDenied 
+    /// unless the session's user has the required role
+    pub fn check_session_role(
+        &self,
+        session_id: &NodeId,
+        session_manager: Arc<RwLock<SessionManager>>,
+        required_role: CIETServerRole) -> Result<(), StatusCode> {
+
+        match self.get_session_role(session_id, session_manager) {
+            Some(role) if role.includes(required_role) => return Ok(()),
+            _ => return Err(StatusCode::BadUserAccessDenied),
+        }
+    }
+}
@@ -0,0 +1,736 @@
+// This application was developed for use in my PhD thesis under supervision 
+// of Professor Per F. Peterson. It is part of a thermal hydraulics
+// library in Rust that is released under the GNU General Public License
+// v 3.0. This is partly due to the fact that some of the libraries 
+// inherit from GeN-Foam and OpenFOAM, both licensed under GNU General
+// Public License v3.0.
+//
+// As such, the entire library is released under GNU GPL v3.0. It is a strong 
+// copyleft license which means you cannot use it in proprietary software.
+//
+//
+// License
+//    This application is part ciet_digital_twin_rust which uses
+//    fluid_mechanics_rust, a partial library of the
+//    thermal hydraulics library written in rust meant to help with the
+//    fluid mechanics aspects of the calculations
+//     
+//    Copyright (C) 2022-2023  Theodore Kay Chen Ong, Singapore Nuclear
+//    Research and Safety Initiative, Per F. Peterson, University of 
+//    California, Berkeley Thermal Hydraulics Laboratory
+//
+//    ciet_digital_twin_rust is free software; you can redistribute it and/or modify it
+//    under the terms of the GNU General Public License as published by the
+//    Free Software Foundation; either version 2 of the License, or (at your
+//    option) any later version.
+//
+//    ciet_digital_twin_rust is distributed in the hope that it will be useful, but WITHOUT
+//    ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or
+//    FITNESS FOR A PARTICULAR PURPOSE.  See the GNU General Public License
+//    for more details.
+//
+//    ciet_digital_twin_rust relies on a thermal hydraulics library
+//    also written in rust. The isothermal digital twin in particular
+//    relies on fluid_mechanics_rust.
+//    This library is part of a thermal hydraulics library in rust
+//    and contains some code copied from GeN-Foam, and OpenFOAM derivative.
+//    This offering is not approved or endorsed by the OpenFOAM Foundation nor
+//    OpenCFD Limited, producer and distributor of the OpenFOAM(R)software via
+//    www.openfoam.com, and owner of the OPENFOAM(R) and OpenCFD(R) trademarks.
+//    Nor is it endorsed by the authors and owners of GeN-Foam.
+//
+//    You should have received a copy of the GNU General Public License
+//    along with this program.  If not, see <http://www.gnu.org/licenses/>.
+//
+// © All rights reserved. Theodore Kay Chen Ong,
+// Singapore Nuclear Research and Safety Initiative,
+// Per F. Peterson,
+// University of California, Berkeley Thermal Hydraulics Laboratory
+//
+// Main author of the code: Theodore Kay Chen Ong, supervised by
+// Professor Per F. Peterson
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use opcua::server::prelude::*;
+use opcua::server::callbacks;
+use opcua::server::session::SessionManager;
+use opcua::server::address_space::method::MethodBuilder;
+use opcua::sync::RwLock;
+
+use fluid_mechanics_rust::prelude::*;
+
+use crate::OwnedCIETFacility;
+use crate::CIETSolverError;
+use super::ciet_server::{construct_ciet_facility, get_solver_error_status_code};
+use super::server_security::{ServerSecurity, CIETServerRole};
+
+/// longest timestep StepTransient will take in one call (seconds)
+pub const MAX_METHOD_TIMESTEP_SECONDS: f64 = 600.0;
+
+/// state shared between the polling action and the simulation 
+/// control methods
+///
+/// methods are called with the address space locked, so they 
+/// cannot write the controller variables themselves. Instead 
+/// they queue the values here, and the polling action writes 
+/// them before it reads the controller variables
+pub struct SimulationControl {
+    paused: bool,
+    pending_controller_values: Vec<(NodeId, Variant)>,
+}
+
+impl SimulationControl {
+
+    pub fn new() -> Self {
+        return Self { 
+            paused: false, 
+            pending_controller_values: vec![],
+        };
+    }
+
+    /// while paused, the polling action still reads the 
+    /// controller variables and publishes the facility state, 
+    /// but it neither solves nor steps the facility
+    pub fn set_paused(&mut self, paused: bool){
+        self.paused = paused;
+    }
+
+    pub fn is_paused(&self) -> bool {
+        return self.paused;
+    }
+
+    pub fn queue_controller_values(
+        &mut self, 
+        mut controller_values: Vec<(NodeId, Variant)>){
+        self.pending_controller_values.append(&mut controller_values);
+    }
+
+    /// returns the queued controller values and empties the queue
+    pub fn take_pending_controller_values(&mut self) -> Vec<(NodeId, Variant)> {
+        return std::mem::take(&mut self.pending_controller_values);
+    }
+
+    /// writes the queued controller values into the address space
+    pub fn write_pending_controller_values(&mut self, 
+                                           address_space: &mut AddressSpace){
+
+        for (node_id, value) in self.take_pending_controller_values() {
+            let now = DateTime::now();
+            let _ = address_space.set_variable_value(
+                node_id, 
+                value,
+                &now, 
+                &now);
+        }
+    }
+}
+
+/// node ids of the controller variables which Reset and 
+/// LoadScenario write
+#[derive(Debug, Clone)]
+pub struct ScenarioControllerNodes {
+    pub ctah_pump_pressure: NodeId,
+    pub ctah_pump_speed_control: NodeId,
+    pub transient_mode: NodeId,
+    pub loop_temperature: NodeId,
+    pub thermal_hydraulics_mode: NodeId,
+    pub heater_power: NodeId,
+    pub ctah_fan_speed: NodeId,
+    pub ambient_temperature: NodeId,
+    pub point_kinetics_mode: NodeId,
+    pub external_reactivity: NodeId,
+    pub scram: NodeId,
+}
+
+/// operating conditions which LoadScenario sets up
+///
+/// the pump runs on a fixed pressure, and point kinetics is off
+#[derive(Debug, Clone, PartialEq)]
+pub struct CIETScenario {
+    pub name: &'static str,
+    pub ctah_pump_pressure_pa: f64,
+    pub heater_power_watts: f64,
+    pub ctah_fan_speed_rpm: f64,
+    pub loop_temperature_degc: f64,
+    pub ambient_temperature_degc: f64,
+    pub thermal_hydraulics_mode: bool,
+    pub transient_mode: bool,
+}
+
+impl CIETScenario {
+
+    /// sets the facility up for this scenario, the facility 
+    /// should be freshly constructed
+    pub fn apply(&self, ciet_isothermal_facility: &mut OwnedCIETFacility){
+
+        ciet_isothermal_facility.set_loop_temperature(
+            ThermodynamicTemperature::new::<degree_celsius>(self.loop_temperature_degc));
+        ciet_isothermal_facility.set_ambient_temperature(
+            ThermodynamicTemperature::new::<degree_celsius>(self.ambient_temperature_degc));
+        ciet_isothermal_facility.set_ctah_pump_pressure(
+            Pressure::new::<pascal>(self.ctah_pump_pressure_pa));
+        ciet_isothermal_facility.set_thermal_hydraulics_enabled(
+            self.thermal_hydraulics_mode);
+        ciet_isothermal_facility.set_heater_power_watts(self.heater_power_watts);
+        ciet_isothermal_facility.set_ctah_fan_speed_rpm(self.ctah_fan_speed_rpm);
+    }
+
+    /// returns the values the controller variables should take 
+    /// so that the polling action keeps the facility in this 
+    /// scenario
+    pub fn get_controller_values(
+        &self, 
+        controller_nodes: &ScenarioControllerNodes) -> Vec<(NodeId, Variant)> {
+
+        return vec![
+            (controller_nodes.ctah_pump_pressure.clone(), 
+             Variant::from(self.ctah_pump_pressure_pa)),
+            (controller_nodes.ctah_pump_speed_control.clone(), 
+             Variant::from(false)),
+            (controller_nodes.transient_mode.clone(), 
+             Variant::from(self.transient_mode)),
+            (controller_nodes.loop_temperature.clone(), 
+             Variant::from(self.loop_temperature_degc)),
+            (controller_nodes.thermal_hydraulics_mode.clone(), 
+             Variant::from(self.thermal_hydraulics_mode)),
+            (controller_nodes.heater_power.clone(), 
+             Variant::from(self.heater_power_watts)),
+            (controller_nodes.ctah_fan_speed.clone(), 
+             Variant::from(self.ctah_fan_speed_rpm)),
+            (controller_nodes.ambient_temperature.clone(), 
+             Variant::from(self.ambient_temperature_degc)),
+            (controller_nodes.point_kinetics_mode.clone(), 
+             Variant::from(false)),
+            (controller_nodes.external_reactivity.clone(), 
+             Variant::from(0.0_f64)),
+            (controller_nodes.scram.clone(), 
+             Variant::from(false)),
+        ];
+    }
+}
+
+/// the facility at rest at 21C, this is also what Reset returns
+/// the controller variables to
+pub const DEFAULT_SCENARIO: CIETScenario = CIETScenario { 
+    name: "isothermal_rest", 
+    ctah_pump_pressure_pa: 0.0, 
+    heater_power_watts: 0.0, 
+    ctah_fan_speed_rpm: 0.0, 
+    loop_temperature_degc: 21.0, 
+    ambient_temperature_degc: 21.0, 
+    thermal_hydraulics_mode: false, 
+    transient_mode: false,
+};
+
+/// returns the scenarios which LoadScenario knows by name
+pub fn get_ciet_scenarios() -> Vec<CIETScenario> {
+    return vec![
+        DEFAULT_SCENARIO,
+        // pump driven flow at 21C
+        CIETScenario { 
+            name: "isothermal_forced_circulation", 
+            ctah_pump_pressure_pa: 4000.0, 
+            ..DEFAULT_SCENARIO
+        },
+        // pump driven flow, heated by the heater and cooled by 
+        // the ctah
+        CIETScenario { 
+            name: "heated_forced_circulation", 
+            ctah_pump_pressure_pa: 4000.0, 
+            heater_power_watts: 5000.0, 
+            ctah_fan_speed_rpm: 1000.0, 
+            thermal_hydraulics_mode: true, 
+            transient_mode: true,
+            ..DEFAULT_SCENARIO
+        },
+        // pump off, buoyancy drives the flow from the heater to 
+        // the ctah
+        CIETScenario { 
+            name: "natural_circulation", 
+            heater_power_watts: 5000.0, 
+            ctah_fan_speed_rpm: 1000.0, 
+            thermal_hydraulics_mode: true, 
+            transient_mode: true,
+            ..DEFAULT_SCENARIO
+        },
+    ];
+}
+
+/// returns the scenario with the given name, if there is one
+pub fn get_ciet_scenario(name: &str) -> Option<CIETScenario> {
+    return get_ciet_scenarios().into_iter().
+        find(|scenario| scenario.name == name);
+}
+
+/// adds a "Simulation Control" folder with the SolveSteadyState, 
+/// Reset, Pause, Resume, StepTransient and LoadScenario methods
+///
+/// Reset and LoadScenario need the supervisor role, the others 
+/// need the operator role
+///
+/// the methods which solve or step return the solver status, 
+/// the ctah, heater and dhx branch flowrates (kg/s) and the 
+/// simulated time (s) once they are done. The variables are 
+/// updated at the next polling tick
+///
+/// StepTransient, Reset and LoadScenario pause the polling 
+/// action, so the facility is left as the method left it 
+/// until Resume is called
+pub fn add_simulation_control_methods(
+    address_space: &mut AddressSpace,
+    ns: u16,
+    ciet_isothermal_facility_ptr: &Arc<Mutex<OwnedCIETFacility>>,
+    simulation_control_ptr: &Arc<Mutex<SimulationControl>>,
+    controller_nodes: &ScenarioControllerNodes,
+    server_security: &Arc<ServerSecurity>){
+
+    let folder_id = address_space
+        .add_folder("Simulation Control", "Simulation Control", 
+                    &NodeId::objects_folder_id())
+        .unwrap();
+
+    let scenario_names: Vec<&str> = get_ciet_scenarios().iter().
+        map(|scenario| scenario.name).
+        collect();
+
+    let _ = address_space.add_variables(
+        vec![
+            Variable::new(&NodeId::new(ns, "available_scenarios"), 
+                          "available_scenarios", 
+                          "available_scenarios", 
+                          UAString::from(scenario_names.join(","))),
+        ], 
+        &folder_id);
+
+    MethodBuilder::new(&NodeId::new(ns, "SolveSteadyState"), 
+                       "SolveSteadyState", "SolveSteadyState")
+        .component_of(folder_id.clone())
+        .output_args(address_space, &get_solver_result_output_arguments())
+        .callback(Box::new(SolveSteadyStateMethod {
+            ciet_isothermal_facility_ptr: ciet_isothermal_facility_ptr.clone(),
+            server_security: server_security.clone(),
+        }))
+        .insert(address_space);
+
+    MethodBuilder::new(&NodeId::new(ns, "Reset"), "Reset", "Reset")
+        .component_of(folder_id.clone())
+        .output_args(address_space, &get_solver_result_output_arguments())
+        .callback(Box::new(LoadScenarioMethod {
+            ciet_isothermal_facility_ptr: ciet_isothermal_facility_ptr.clone(),
+            simulation_control_ptr: simulation_control_ptr.clone(),
+            controller_nodes: controller_nodes.clone(),
+            reset: true,
+            server_security: server_security.clone(),
+        }))
+        .insert(address_space);
+
+    MethodBuilder::new(&NodeId::new(ns, "Pause"), "Pause", "Pause")
+        .component_of(folder_id.clone())
+        .output_args(address_space, &[("Paused", DataTypeId::Boolean).into()])
+        .callback(Box::new(PauseMethod {
+            simulation_control_ptr: simulation_control_ptr.clone(),
+            paused: true,
+            server_security: server_security.clone(),
+        }))
+        .insert(address_space);
+
+    MethodBuilder::new(&NodeId::new(ns, "Resume"), "Resume", "Resume")
+        .component_of(folder_id.clone())
+        .output_args(address_space, &[("Paused", DataTypeId::Boolean).into()])
+        .callback(Box::new(PauseMethod {
+            simulation_control_ptr: simulation_control_ptr.clone(),
+            paused: false,
+            server_security: server_security.clone(),
+        }))
+        .insert(address_space);
+
+    MethodBuilder::new(&NodeId::new(ns, "StepTransient"), 
+                       "StepTransient", "StepTransient")
+        .component_of(folder_id.clone())
+        .input_args(address_space, &[("TimestepSeconds", DataTypeId::Double).into()])
+        .output_args(address_space, &get_solver_result_output_arguments())
+        .callback(Box::new(StepTransientMethod {
+            ciet_isothermal_facility_ptr: ciet_isothermal_facility_ptr.clone(),
+            simulation_control_ptr: simulation_control_ptr.clone(),
+            server_security: server_security.clone(),
+        }))
+        .insert(address_space);
+
+    MethodBuilder::new(&NodeId::new(ns, "LoadScenario"), 
+                       "LoadScenario", "LoadScenario")
+        .component_of(folder_id.clone())
+        .input_args(address_space, &[("ScenarioName", DataTypeId::String).into()])
+        .output_args(address_space, &get_solver_result_output_arguments())
+        .callback(Box::new(LoadScenarioMethod {
+            ciet_isothermal_facility_ptr: ciet_isothermal_facility_ptr.clone(),
+            simulation_control_ptr: simulation_control_ptr.clone(),
+            controller_nodes: controller_nodes.clone(),
+            reset: false,
+            server_security: server_security.clone(),
+        }))
+        .insert(address_space);
+}
+
+// output arguments of the methods which solve or step
+fn get_solver_result_output_arguments() -> Vec<Argument> {
+    return vec![
+        ("Status", DataTypeId::String).into(),
+        ("CTAHBranchMassFlowrate", DataTypeId::Double).into(),
+        ("HeaterBranchMassFlowrate", DataTypeId::Double).into(),
+        ("DHXBranchMassFlowrate", DataTypeId::Double).into(),
+        ("SimulationTime", DataTypeId::Double).into(),
+    ];
+}
+
+// turns a solve or step into a method result, a solver error 
+// gives the same status code as the flowrate variables get
+fn get_solver_call_method_result(
+    ciet_isothermal_facility: &OwnedCIETFacility,
+    calculation_result: Result<(Duration, MassRate, MassRate, MassRate), CIETSolverError>,
+    number_of_input_arguments: usize) -> CallMethodResult {
+
+    let simulation_time_seconds = 
+        ciet_isothermal_facility.get_simulation_time().as_secs_f64();
+
+    let (status_code, output_arguments) = match calculation_result {
+        Ok((_calc_time, 
+            ctah_branch_flowrate, 
+            heater_branch_flowrate, 
+            dhx_branch_flowrate)) => {
+            (StatusCode::Good, vec![
+                Variant::from(UAString::from("ok")),
+                Variant::from(ctah_branch_flowrate.value),
+                Variant::from(heater_branch_flowrate.value),
+                Variant::from(dhx_branch_flowrate.value),
+                Variant::from(simulation_time_seconds),
+            ])
+        },
+        Err(solver_error) => {
+            (get_solver_error_status_code(&solver_error), vec![
+                Variant::from(UAString::from(solver_error.to_string())),
+                Variant::from(f64::NAN),
+                Variant::from(f64::NAN),
+                Variant::from(f64::NAN),
+                Variant::from(simulation_time_seconds),
+            ])
+        },
+    };
+
+    return CallMethodResult { 
+        status_code: status_code, 
+        input_argument_results: Some(vec![StatusCode::Good; number_of_input_arguments]), 
+        input_argument_diagnostic_infos: None, 
+        output_arguments: Some(output_arguments),
+    };
+}
+
+// returns the current flowrates without solving, as a solver 
+// result
+fn get_current_flowrates(
+    ciet_isothermal_facility: &OwnedCIETFacility) -> 
+    Result<(Duration, MassRate, MassRate, MassRate), CIETSolverError> {
+
+    return Ok((Duration::ZERO,
+               ciet_isothermal_facility.get_ctah_branch_mass_flowrate(),
+               ciet_isothermal_facility.get_heater_branch_mass_flowrate(),
+               ciet_isothermal_facility.get_dhx_branch_mass_flowrate()));
+}
+
+// returns the first input argument, or an error if there isn't 
+// one
+fn get_first_input_argument(request: &CallMethodRequest) -> Result<&Variant, StatusCode> {
+    return request.input_arguments.as_ref().
+        and_then(|input_arguments| input_arguments.first()).
+        ok_or(StatusCode::BadArgumentsMissing);
+}
+
+/// solves for steady state at the current settings
+struct SolveSteadyStateMethod {
+    ciet_isothermal_facility_ptr: Arc<Mutex<OwnedCIETFacility>>,
+    server_security: Arc<ServerSecurity>,
+}
+
+impl callbacks::Method for SolveSteadyStateMethod {
+    fn call(&mut self, 
+            session_id: &NodeId, 
+            session_manager: Arc<RwLock<SessionManager>>, 
+            _request: &CallMethodRequest) -> Result<CallMethodResult, StatusCode> {
+
+        self.server_security.check_session_role(
+            session_id, session_manager, CIETServerRole::Operator)?;
+
+        let mut ciet_isothermal_facility = 
+            self.ciet_isothermal_facility_ptr.lock().unwrap();
+
+        let calculation_result = ciet_isothermal_facility.calculate();
+
+        return Ok(get_solver_call_method_result(
+                &ciet_isothermal_facility, calculation_result, 0));
+    }
+}
+
+/// advances the transient by the timestep given (seconds), 
+/// and pauses the polling action so it does not step the 
+/// facility any further
+struct StepTransientMethod {
+    ciet_isothermal_facility_ptr: Arc<Mutex<OwnedCIETFacility>>,
+    simulation_control_ptr: Arc<Mutex<SimulationControl>>,
+    server_security: Arc<ServerSecurity>,
+}
+
+impl callbacks::Method for StepTransientMethod {
+    fn call(&mut self, 
+            session_id: &NodeId, 
+            session_manager: Arc<RwLock<SessionManager>>, 
+            request: &CallMethodRequest) -> Result<CallMethodResult, StatusCode> {
+
+        self.server_security.check_session_role(
+            session_id, session_manager, CIETServerRole::Operator)?;
+
+        let timestep_seconds = get_first_input_argument(request)?.
+            as_f64().
+            ok_or(StatusCode::BadTypeMismatch)?;
+
+        if !timestep_seconds.is_finite() || timestep_seconds <= 0.0 || 
+            timestep_seconds > MAX_METHOD_TIMESTEP_SECONDS {
+            return Err(StatusCode::BadOutOfRange);
+        }
+
+        let mut ciet_isothermal_facility = 
+            self.ciet_isothermal_facility_ptr.lock().unwrap();
+
+        // the polling action holds the address space lock for the 
+        // whole of its tick, and methods are called with it held, 
+        // so no tick runs between here and the pause
+        self.simulation_control_ptr.lock().unwrap().set_paused(true);
+
+        let calculation_result = ciet_isothermal_facility.step_transient(
+            Duration::from_secs_f64(timestep_seconds));
+
+        return Ok(get_solver_call_method_result(
+                &ciet_isothermal_facility, calculation_result, 1));
+    }
+}
+
+/// pauses (or resumes) the polling action
+struct PauseMethod {
+    simulation_control_ptr: Arc<Mutex<SimulationControl>>,
+    paused: bool,
+    server_security: Arc<ServerSecurity>,
+}
+
+impl callbacks::Method for PauseMethod {
+    fn call(&mut self, 
+            session_id: &NodeId, 
+            session_manager: Arc<RwLock<SessionManager>>, 
+            _request: &CallMethodRequest) -> Result<CallMethodResult, StatusCode> {
+
+        self.server_security.check_session_role(
+            session_id, session_manager, CIETServerRole::Operator)?;
+
+        let mut simulation_control = self.simulation_control_ptr.lock().unwrap();
+
+        simulation_control.set_paused(self.paused);
+
+        return Ok(CallMethodResult { 
+            status_code: StatusCode::Good, 
+            input_argument_results: None, 
+            input_argument_diagnostic_infos: None, 
+            output_arguments: Some(vec![Variant::from(simulation_control.is_paused())]),
+        });
+    }
+}
+
+/// rebuilds the facility and sets it up for a scenario, then 
+/// solves for steady state
+///
+/// Reset does the same for DEFAULT_SCENARIO but doesn't solve,
+/// since the facility is at rest
+///
+/// both pause the polling action, so the facility stays as 
+/// the method left it. The controller variables are set to the
+/// scenario at the next polling tick, which applies them to the
+/// facility (changing nothing) without solving
+struct LoadScenarioMethod {
+    ciet_isothermal_facility_ptr: Arc<Mutex<OwnedCIETFacility>>,
+    simulation_control_ptr: Arc<Mutex<SimulationControl>>,
+    controller_nodes: ScenarioControllerNodes,
+    reset: bool,
+    server_security: Arc<ServerSecurity>,
+}
+
+impl callbacks::Method for LoadScenarioMethod {
+    fn call(&mut self, 
+            session_id: &NodeId, 
+            session_manager: Arc<RwLock<SessionManager>>, 
+            request: &CallMethodRequest) -> Result<CallMethodResult, StatusCode> {
+
+        self.server_security.check_session_role(
+            session_id, session_manager, CIETServerRole::Supervisor)?;
+
+        let (scenario, number_of_input_arguments) = if self.reset {
+            (DEFAULT_SCENARIO, 0)
+        } else {
+            let scenario_name = match get_first_input_argument(request)? {
+                Variant::String(scenario_name) => 
+                    scenario_name.value().clone().unwrap_or_default(),
+                _ => return Err(StatusCode::BadTypeMismatch),
+            };
+
+            (get_ciet_scenario(&scenario_name).ok_or(StatusCode::BadInvalidArgument)?, 1)
+        };
+
+        let mut ciet_isothermal_facility = 
+            self.ciet_isothermal_facility_ptr.lock().unwrap();
+
+        *ciet_isothermal_facility = construct_ciet_facility();
+        scenario.apply(&mut ciet_isothermal_facility);
+
+        let calculation_result = if self.reset {
+            get_current_flowrates(&ciet_isothermal_facility)
+        } else {
+            ciet_isothermal_facility.calculate()
+        };
+
+        {
+            let mut simulation_control = self.simulation_control_ptr.lock().unwrap();
+            simulation_control.set_paused(true);
+            simulation_control.queue_controller_values(
+                scenario.get_controller_values(&self.controller_nodes));
+        }
+
+        return Ok(get_solver_call_method_result(
+                &ciet_isothermal_facility, 
+                calculation_result, 
+                number_of_input_arguments));
+    }
+}
+
+/// used by the polling action while paused, so the variables 
+/// show the facility as it is
+pub fn get_paused_calculation_result(
+    ciet_isothermal_facility: &OwnedCIETFacility) -> 
+    Result<(Duration, MassRate, MassRate, MassRate), CIETSolverError> {
+    return get_current_flowrates(ciet_isothermal_facility);
+}
+
+/// a controller variable which SetControllerValue may write
+#[derive(Debug, Clone)]
+pub struct ControllerVariable {
+    pub browse_name: String,
+    pub node_id: NodeId,
+    pub is_boolean: bool,
+}
+
+impl ControllerVariable {
+
+    pub fn new_float(browse_name: &str, node_id: &NodeId) -> Self {
+        return Self { 
+            browse_name: browse_name.to_string(), 
+            node_id: node_id.clone(), 
+            is_boolean: false,
+        };
+    }
+
+    pub fn new_boolean(browse_name: &str, node_id: &NodeId) -> Self {
+        return Self { 
+            browse_name: browse_name.to_string(), 
+            node_id: node_id.clone(), 
+            is_boolean: true,
+        };
+    }
+}
+
+/// adds the SetControllerValue method to the "Controller" 
+/// folder, it takes the browse name of a controller variable 
+/// and its new value
+///
+/// the controller variables are read only on the main server, 
+/// so that anonymous users can't write them, operators write 
+/// them on the control server or through this method. The value
+/// is written at the next polling tick, and clamped there like 
+/// any other controller value
+pub fn add_set_controller_value_method(
+    address_space: &mut AddressSpace,
+    ns: u16,
+    controller_folder_id: &NodeId,
+    controller_variables: Vec<ControllerVariable>,
+    simulation_control_ptr: &Arc<Mutex<SimulationControl>>,
+    server_security: &Arc<ServerSecurity>){
+
+    MethodBuilder::new(&NodeId::new(ns, "SetControllerValue"), 
+                       "SetControllerValue", "SetControllerValue")
+        .component_of(controller_folder_id.clone())
+        .input_args(address_space, &[
+                    ("ControllerVariable", DataTypeId::String).into(),
+                    ("Value", DataTypeId::BaseDataType).into(),
+        ])
+        .callback(Box::new(SetControllerValueMethod {
+            controller_variables: controller_variables,
+            simulation_control_ptr: simulation_control_ptr.clone(),
+            server_security: server_security.clone(),
+        }))
+        .insert(address_space);
+}
+
+/// queues a controller value for the polling action
+struct SetControllerValueMethod {
+    controller_variables: Vec<ControllerVariable>,
+    simulation_control_ptr: Arc<Mutex<SimulationControl>>,
+    server_security: Arc<ServerSecurity>,
+}
+
+impl callbacks::Method for SetControllerValueMethod {
+    fn call(&mut self, 
+            session_id: &NodeId, 
+            session_manager: Arc<RwLock<SessionManager>>, 
+            request: &CallMethodRequest) -> Result<CallMethodResult, StatusCode> {
+
+        self.server_security.check_session_role(
+            session_id, session_manager, CIETServerRole::Operator)?;
+
+        let input_arguments = match request.input_arguments.as_ref() {
+            Some(input_arguments) if input_arguments.len() == 2 => input_arguments,
+            _ => return Err(StatusCode::BadArgumentsMissing),
+        };
+
+        let browse_name = match &input_arguments[0] {
+            Variant::String(browse_name) => 
+                browse_name.value().clone().unwrap_or_default(),
+            _ => return Err(StatusCode::BadTypeMismatch),
+        };
+
+        let controller_variable = self.controller_variables.iter().
+            find(|controller_variable| controller_variable.browse_name == browse_name).
+            ok_or(StatusCode::BadNodeIdUnknown)?;
+
+        // the controller variables hold doubles or booleans, 
+        // anything numeric is written as a double
+        let value = match (&input_arguments[1], controller_variable.is_boolean) {
+            (Variant::Boolean(value), true) => Variant::from(*value),
+            (numeric_value, false) => {
+                let value = numeric_value.as_f64().
+                    ok_or(StatusCode::BadTypeMismatch)?;
+
+                if !value.is_finite() {
+                    return Err(StatusCode::BadOutOfRange);
+                }
+
+                Variant::from(value)
+            },
+            _ => return Err(StatusCode::BadTypeMismatch),
+        };
+
+        self.simulation_control_ptr.lock().unwrap().queue_controller_values(
+            vec![(controller_variable.node_id.clone(), value)]);
+
+        return Ok(CallMethodResult { 
+            status_code: StatusCode::Good, 
+            input_argument_results: Some(vec![StatusCode::Good; 2]), 
+            input_argument_diagnostic_infos: None, 
+            output_arguments: None,
+        });
+    }
+}
@@ -60,7 +60,8 @@ pub mod ciet_libraries;
 pub use ciet_libraries::*;
 
 
-use crate::examples::ciet_server::construct_and_run_ciet_server;
+use crate::examples::ciet_server::construct_and_run_ciet_server_with_config;
+use crate::examples::server_config::{CIETServerConfig, COMMAND_LINE_USAGE};
 
 
 fn main() {
@@ -72,7 +73,18 @@ fn main() {
 
 
 
-    construct_and_run_ciet_server(run_server);
+    // settings come from ciet_server.toml (or --config), with 
+    // command line overrides
+    let server_config = match CIETServerConfig::from_args(
+        std::env::args().skip(1)) {
+        Ok(server_config) => server_config,
+        Err(error_message) => {
+            println!("{}\n\n{}", error_message, COMMAND_LINE_USAGE);
+            std::process::exit(1);
+        },
+    };
+
+    construct_and_run_ciet_server_with_config(run_server, server_config);
 
 }
 
@@ -264,8 +264,8 @@ pub fn assert_ctah_behave_ok() {
         ctah_branch.set_fluid_component_vector(ctah_branch_vector);
 
         let test_ctah_mass_flowrate = 
-            ctah_branch.get_mass_flowrate_from_pressure_change(
-                Pressure::new::<pascal>(*pressure_change_value));
+            ctah_branch.try_get_mass_flowrate_from_pressure_change(
+                Pressure::new::<pascal>(*pressure_change_value)).unwrap();
 
         let reference_ctah_pressure_change: f64 = 
             get_ctah_branch_isothermal_pressure_change_pascals(
@@ -390,8 +390,8 @@ pub fn assert_ctah_behave_ok() {
         ctah_branch.set_fluid_component_vector(ctah_branch_vector);
 
         let test_ctah_mass_flowrate = 
-            ctah_branch.get_mass_flowrate_from_pressure_change(
-                Pressure::new::<pascal>(1000.0));
+            ctah_branch.try_get_mass_flowrate_from_pressure_change(
+                Pressure::new::<pascal>(1000.0)).unwrap();
 
         let reference_ctah_pressure_change: f64 = 
             get_ctah_branch_isothermal_pressure_change_pascals(
@@ -504,8 +504,8 @@ pub fn ctah_branch_manual_test(){
     ctah_branch.set_fluid_component_vector(ctah_branch_vector);
 
     let test_ctah_mass_flowrate = 
-        ctah_branch.get_mass_flowrate_from_pressure_change(
-            Pressure::new::<pascal>(pressure_change_value));
+        ctah_branch.try_get_mass_flowrate_from_pressure_change(
+            Pressure::new::<pascal>(pressure_change_value)).unwrap();
 
     let reference_ctah_pressure_change: f64 = 
         get_ctah_branch_isothermal_pressure_change_pascals(
@@ -679,8 +679,8 @@ pub fn assert_dhx_branch_ok(){
 
         let test_dhx_mass_flowrate = 
             dhx_branch.
-            get_mass_flowrate_from_pressure_change(
-                Pressure::new::<pascal>(*pressure_change_value));
+            try_get_mass_flowrate_from_pressure_change(
+                Pressure::new::<pascal>(*pressure_change_value)).unwrap();
 
         let reference_dhx_pressure_change: f64 = 
             get_dhx_branch_isothermal_pressure_change_pascals(
@@ -838,8 +838,8 @@ pub fn assert_heater_branch_ok(){
 
         let test_heater_mass_flowrate = 
             heater_branch.
-            get_mass_flowrate_from_pressure_change(
-                Pressure::new::<pascal>(*pressure_change_value));
+            try_get_mass_flowrate_from_pressure_change(
+                Pressure::new::<pascal>(*pressure_change_value)).unwrap();
 
         let reference_heater_pressure_change: f64 = 
             get_heater_branch_isothermal_pressure_change_pascals(
@@ -869,6 +869,2063 @@ pub fn assert_heater_branch_ok(){
 }
 
 
+#[test]
+pub fn assert_manometer_readings_ok(){
+
+    use fluid_mechanics_rust::prelude::*;
+    use crate::{CTAHBranch, HeaterBranch, DHXBranch, 
+        CIETIsothermalFacility, ManometerReference};
+
+    let pump_pressure_pascals = 10000.0;
+
+    // first, the ctah branch
+    let ctah_branch_factory = CTAHBranch::new();
+    let pipe6a = ctah_branch_factory.get_pipe6a();
+    let static_mixer_41 = ctah_branch_factory.get_static_mixer_41();
+    let ctah_vertical = ctah_branch_factory.get_ctah_vertical();
+    let ctah_horizontal = ctah_branch_factory.get_ctah_horizontal();
+    let pipe_8a = ctah_branch_factory.get_pipe_8a();
+    let static_mixer_40 = ctah_branch_factory.get_static_mixer_40();
+    let pipe_9 = ctah_branch_factory.get_pipe_9();
+    let pipe_10 = ctah_branch_factory.get_pipe_10();
+    let pipe_11 = ctah_branch_factory.get_pipe_11();
+    let pipe_12 = ctah_branch_factory.get_pipe_12();
+    let pipe_13 = ctah_branch_factory.get_pipe_13();
+    let pipe_14 = ctah_branch_factory.get_pipe_14();
+    let flowmeter_40_14a = ctah_branch_factory.get_flowmeter_40_14a();
+    let pipe_15 = ctah_branch_factory.get_pipe_15();
+    let pipe_16 = ctah_branch_factory.get_pipe_16();
+    let branch_17 = ctah_branch_factory.get_branch_17();
+
+    let mut mutable_ctah_pump = ctah_branch_factory.get_ctah_pump();
+    mutable_ctah_pump.set_internal_pressure_source(
+        Pressure::new::<pascal>(pump_pressure_pascals));
+
+    let mut ctah_branch_vector :Vec<&dyn FluidComponent> = vec![];
+    ctah_branch_vector.push(&pipe6a); 
+    ctah_branch_vector.push(&static_mixer_41);
+    ctah_branch_vector.push(&ctah_vertical);
+    ctah_branch_vector.push(&ctah_horizontal);
+    ctah_branch_vector.push(&pipe_8a);
+    ctah_branch_vector.push(&static_mixer_40);
+    ctah_branch_vector.push(&pipe_9);
+    ctah_branch_vector.push(&pipe_10);
+    ctah_branch_vector.push(&pipe_11);
+    ctah_branch_vector.push(&pipe_12);
+    ctah_branch_vector.push(&mutable_ctah_pump);
+    ctah_branch_vector.push(&pipe_13);
+    ctah_branch_vector.push(&pipe_14);
+    ctah_branch_vector.push(&flowmeter_40_14a);
+    ctah_branch_vector.push(&pipe_15);
+    ctah_branch_vector.push(&pipe_16);
+    ctah_branch_vector.push(&branch_17);
+
+    let mut ctah_branch = CTAHBranch::new();
+    ctah_branch.set_fluid_component_vector(ctah_branch_vector);
+
+    // then the heater branch
+    let heater_branch_factory = HeaterBranch::new();
+
+    let branch5 = heater_branch_factory.get_branch5();
+    let pipe4 = heater_branch_factory.get_pipe4();
+    let pipe3 = heater_branch_factory.get_pipe3();
+    let mixer10 = heater_branch_factory.get_mixer10();
+    let pipe2a = heater_branch_factory.get_pipe2a();
+    let heater_top_head_1a = heater_branch_factory.get_heater_top_head_1a();
+    let ciet_heater = heater_branch_factory.get_ciet_heater();
+    let heater_bottom_head_1b = heater_branch_factory.get_heater_bottom_head_1b();
+    let pipe18 = heater_branch_factory.get_pipe18();
+
+    let mut heater_branch_vector :Vec<&dyn FluidComponent> = vec![];
+
+    heater_branch_vector.push(&branch5);
+    heater_branch_vector.push(&pipe4);
+    heater_branch_vector.push(&pipe3);
+    heater_branch_vector.push(&mixer10);
+    heater_branch_vector.push(&pipe2a);
+    heater_branch_vector.push(&heater_top_head_1a);
+    heater_branch_vector.push(&ciet_heater);
+    heater_branch_vector.push(&heater_bottom_head_1b);
+    heater_branch_vector.push(&pipe18);
+
+    let mut heater_branch = HeaterBranch::new();
+    heater_branch.set_fluid_component_vector(heater_branch_vector);
+
+    // and the dhx branch
+    let dhx_branch_factory = DHXBranch::new();
+
+    let pipe26 = dhx_branch_factory.get_pipe26();
+    let static_mixer_21 = dhx_branch_factory.get_static_mixer_21();
+    let pipe25a = dhx_branch_factory.get_pipe25a();
+    let dhx_shell_side_heat_exchanger = dhx_branch_factory.get_dhx_shell_side_heat_exchanger();
+    let static_mixer_20 = dhx_branch_factory.get_static_mixer_20();
+    let pipe23a = dhx_branch_factory.get_pipe23a();
+    let pipe22 = dhx_branch_factory.get_pipe22();
+    let flowmeter20 = dhx_branch_factory.get_flowmeter20();
+    let pipe21 = dhx_branch_factory.get_pipe21();
+    let pipe20 = dhx_branch_factory.get_pipe20();
+    let pipe19 = dhx_branch_factory.get_pipe19();
+
+    let mut dhx_branch_vector :Vec<&dyn FluidComponent> = vec![];
+
+    dhx_branch_vector.push(&pipe26);
+    dhx_branch_vector.push(&static_mixer_21);
+    dhx_branch_vector.push(&pipe25a);
+    dhx_branch_vector.push(&dhx_shell_side_heat_exchanger);
+    dhx_branch_vector.push(&static_mixer_20);
+    dhx_branch_vector.push(&pipe23a);
+    dhx_branch_vector.push(&pipe22);
+    dhx_branch_vector.push(&flowmeter20);
+    dhx_branch_vector.push(&pipe21);
+    dhx_branch_vector.push(&pipe20);
+    dhx_branch_vector.push(&pipe19);
+
+    let mut dhx_branch = DHXBranch::new();
+    dhx_branch.set_fluid_component_vector(dhx_branch_vector);
+
+    let mut ciet_isothermal_facility = 
+        CIETIsothermalFacility::new(ctah_branch, heater_branch, dhx_branch);
+
+    let (_calc_time, ctah_branch_flowrate, _heater_branch_flowrate, 
+         _dhx_branch_flowrate) = ciet_isothermal_facility.calculate().unwrap();
+
+    let header_pressure_change = 
+        ciet_isothermal_facility.get_header_pressure_change();
+
+    // there are 9 heater components, 11 dhx components and
+    // 17 ctah components, so 8 + 10 + 16 junctions
+    let top_header_readings = ciet_isothermal_facility.
+        get_manometer_readings(ManometerReference::TopHeader);
+
+    assert_eq!(top_header_readings.len(), 8 + 10 + 16);
+    assert_eq!(top_header_readings.len(), 
+               CIETIsothermalFacility::get_manometer_tap_labels().len());
+
+    // the first tap in the ctah branch is just after pipe 6a
+    // so its gauge pressure relative to the top header is
+    // just the pressure change across pipe 6a
+    let first_ctah_reading = &top_header_readings[8 + 10];
+
+    assert_eq!(first_ctah_reading.upstream_component_name, "pipe_6a");
+
+    approx::assert_relative_eq!(
+        first_ctah_reading.gauge_pressure.value,
+        pipe6a.get_pressure_change_immutable(ctah_branch_flowrate).value,
+        max_relative = 1e-9);
+
+    // the last tap in the ctah branch is just before branch 17,
+    // adding the pressure change across branch 17 should give the
+    // bottom header pressure
+    let last_ctah_reading = &top_header_readings[8 + 10 + 15];
+
+    assert_eq!(last_ctah_reading.downstream_component_name, "branch_17");
+
+    approx::assert_relative_eq!(
+        last_ctah_reading.gauge_pressure.value 
+        + branch_17.get_pressure_change_immutable(ctah_branch_flowrate).value,
+        header_pressure_change.value,
+        max_relative = 1e-6);
+
+    // readings relative to the bottom header should be shifted
+    // by the header pressure change
+    let bottom_header_readings = ciet_isothermal_facility.
+        get_manometer_readings(ManometerReference::BottomHeader);
+
+    for (top_reading, bottom_reading) in 
+        top_header_readings.iter().zip(bottom_header_readings.iter()) {
+
+        approx::assert_abs_diff_eq!(
+            top_reading.gauge_pressure.value - header_pressure_change.value,
+            bottom_reading.gauge_pressure.value,
+            epsilon = 1e-6);
+    }
+
+}
+
+#[test]
+pub fn assert_owned_ciet_facility_ok(){
+
+    use fluid_mechanics_rust::prelude::*;
+    use crate::{OwnedCIETFacility, ManometerReference};
+
+    // the facility is constructed once and the pump pressure
+    // is changed in place
+    let mut ciet_isothermal_facility = OwnedCIETFacility::new();
+
+    let mut pump_pressure_vec_pa: Vec<f64> = vec![];
+
+    pump_pressure_vec_pa.push(0.0);
+    pump_pressure_vec_pa.push(10000.0);
+    pump_pressure_vec_pa.push(-10000.0);
+    pump_pressure_vec_pa.push(40000.0);
+
+    for pump_pressure_pascals in pump_pressure_vec_pa.iter() {
+
+        ciet_isothermal_facility.set_ctah_pump_pressure(
+            Pressure::new::<pascal>(*pump_pressure_pascals));
+
+        let (_calc_time, ctah_branch_flowrate, heater_branch_flowrate,
+             dhx_branch_flowrate) = ciet_isothermal_facility.calculate().unwrap();
+
+        // mass must be conserved at the headers
+        approx::assert_abs_diff_eq!(
+            ctah_branch_flowrate.value 
+            + heater_branch_flowrate.value
+            + dhx_branch_flowrate.value,
+            0.0,
+            epsilon = 1e-6);
+
+        // the ctah branch pressure change at the calculated flowrate
+        // should match the header pressure change, i check this 
+        // against the reference code
+        let reference_ctah_pressure_change = 
+            get_ctah_branch_isothermal_pressure_change_pascals(
+                ctah_branch_flowrate.value,
+                21.0,
+                *pump_pressure_pascals);
+
+        approx::assert_abs_diff_eq!(
+            reference_ctah_pressure_change,
+            ciet_isothermal_facility.get_header_pressure_change().value,
+            epsilon = 10.0);
+    }
+
+    let manometer_readings = ciet_isothermal_facility.
+        get_manometer_readings(ManometerReference::TopHeader);
+
+    assert_eq!(manometer_readings.len(), 
+               ciet_isothermal_facility.get_manometer_tap_labels().len());
+}
+
+#[test]
+pub fn assert_ciet_solver_errors_ok(){
+
+    use fluid_mechanics_rust::prelude::*;
+    use crate::{OwnedCIETFacility, CIETSolverError,
+        find_root_brent_checked};
+
+    // x^2 + 1 has no roots, so the bracket should not work
+    let no_root_result = find_root_brent_checked(
+        "x", -1.0, 1.0, |x: f64| -> f64 { x * x + 1.0 });
+
+    assert!(matches!(no_root_result, 
+                     Err(CIETSolverError::RootNotBracketed { .. })));
+
+    let mut ciet_isothermal_facility = OwnedCIETFacility::new();
+
+    // a NaN pump pressure should give a non finite residual 
+    // instead of a panic
+    ciet_isothermal_facility.set_ctah_pump_pressure(
+        Pressure::new::<pascal>(f64::NAN));
+
+    assert!(matches!(ciet_isothermal_facility.calculate(), 
+                     Err(CIETSolverError::NonFiniteResidual { .. })));
+
+    // the facility should still work after the errors
+    ciet_isothermal_facility.set_ctah_pump_pressure(
+        Pressure::new::<pascal>(10000.0));
+
+    assert!(ciet_isothermal_facility.calculate().is_ok());
+}
+
+#[test]
+pub fn assert_bracket_expansion_ok(){
+
+    use fluid_mechanics_rust::prelude::*;
+    use crate::{OwnedCIETFacility, find_root_brent_expanding};
+
+    // the root at 1000 is well outside the initial bracket
+    let root = find_root_brent_expanding(
+        "x", -1.0, 1.0, |x: f64| -> f64 { x - 1000.0 }).unwrap();
+
+    approx::assert_relative_eq!(root, 1000.0, max_relative = 1e-6);
+
+    // same on the other side
+    let root = find_root_brent_expanding(
+        "x", -1.0, 1.0, |x: f64| -> f64 { x + 1000.0 }).unwrap();
+
+    approx::assert_relative_eq!(root, -1000.0, max_relative = 1e-6);
+
+    // a pump pressure beyond the old 45 kPa limit should now 
+    // be solvable since the brackets expand
+    let mut ciet_isothermal_facility = OwnedCIETFacility::new();
+
+    ciet_isothermal_facility.set_ctah_pump_pressure(
+        Pressure::new::<pascal>(150000.0));
+
+    let (_calc_time, ctah_branch_flowrate, heater_branch_flowrate,
+         dhx_branch_flowrate) = ciet_isothermal_facility.calculate().unwrap();
+
+    approx::assert_abs_diff_eq!(
+        ctah_branch_flowrate.value 
+        + heater_branch_flowrate.value
+        + dhx_branch_flowrate.value,
+        0.0,
+        epsilon = 1e-6);
+}
+
+#[test]
+pub fn assert_warm_start_ok(){
+
+    use fluid_mechanics_rust::prelude::*;
+    use crate::OwnedCIETFacility;
+
+    let mut warm_facility = OwnedCIETFacility::new();
+    let mut cold_facility = OwnedCIETFacility::new();
+    cold_facility.set_warm_start_enabled(false);
+
+    // first solve is always a cold start
+    warm_facility.set_ctah_pump_pressure(Pressure::new::<pascal>(10000.0));
+    warm_facility.calculate().unwrap();
+
+    assert!(!warm_facility.get_last_iteration_counts().warm_started);
+
+    // now i change the pump pressure slightly, as the server would
+    warm_facility.set_ctah_pump_pressure(Pressure::new::<pascal>(10100.0));
+    cold_facility.set_ctah_pump_pressure(Pressure::new::<pascal>(10100.0));
+
+    let (_calc_time, warm_ctah_flowrate, warm_heater_flowrate, 
+         warm_dhx_flowrate) = warm_facility.calculate().unwrap();
+    let (_calc_time, cold_ctah_flowrate, cold_heater_flowrate, 
+         cold_dhx_flowrate) = cold_facility.calculate().unwrap();
+
+    // same answers either way
+    approx::assert_abs_diff_eq!(warm_ctah_flowrate.value, 
+                                cold_ctah_flowrate.value, epsilon = 1e-6);
+    approx::assert_abs_diff_eq!(warm_heater_flowrate.value, 
+                                cold_heater_flowrate.value, epsilon = 1e-6);
+    approx::assert_abs_diff_eq!(warm_dhx_flowrate.value, 
+                                cold_dhx_flowrate.value, epsilon = 1e-6);
+
+    // but the warm start should take less work
+    let warm_iteration_counts = warm_facility.get_last_iteration_counts();
+    let cold_iteration_counts = cold_facility.get_last_iteration_counts();
+
+    assert!(warm_iteration_counts.warm_started);
+    assert!(!cold_iteration_counts.warm_started);
+    assert!(warm_iteration_counts.get_total_residual_evaluations() 
+            < cold_iteration_counts.get_total_residual_evaluations());
+
+    // after a reset, it is a cold start again
+    warm_facility.reset_warm_start();
+    warm_facility.calculate().unwrap();
+
+    assert!(!warm_facility.get_last_iteration_counts().warm_started);
+}
+
+#[test]
+pub fn assert_parallel_branch_network_ok(){
+
+    use fluid_mechanics_rust::prelude::*;
+    use crate::{ParallelBranchNetwork, OwnedCIETFacility,
+        HeaterBranch, DHXBranch, CTAHBranch};
+
+    let pump_pressure = Pressure::new::<pascal>(10000.0);
+
+    let mut ctah_branch = CTAHBranch::new().get_owned_branch();
+    let ctah_pump_index = ctah_branch.get_component_index("ctah_pump").unwrap();
+    ctah_branch.set_component_internal_pressure_source(
+        ctah_pump_index, pump_pressure);
+
+    // ciet as a three branch network should give the same 
+    // answers as the facility
+    let mut network = ParallelBranchNetwork::new(vec![
+        HeaterBranch::new().get_owned_branch(),
+        DHXBranch::new().get_owned_branch(),
+        ctah_branch,
+    ]);
+
+    let branch_mass_flowrates = network.solve().unwrap();
+
+    let mut ciet_isothermal_facility = OwnedCIETFacility::new();
+    ciet_isothermal_facility.set_ctah_pump_pressure(pump_pressure);
+
+    let (_calc_time, ctah_branch_flowrate, heater_branch_flowrate,
+         dhx_branch_flowrate) = ciet_isothermal_facility.calculate().unwrap();
+
+    assert_eq!(branch_mass_flowrates.len(), 3);
+    approx::assert_abs_diff_eq!(branch_mass_flowrates[0].value, 
+                                heater_branch_flowrate.value, epsilon = 1e-6);
+    approx::assert_abs_diff_eq!(branch_mass_flowrates[1].value, 
+                                dhx_branch_flowrate.value, epsilon = 1e-6);
+    approx::assert_abs_diff_eq!(branch_mass_flowrates[2].value, 
+                                ctah_branch_flowrate.value, epsilon = 1e-6);
+
+    // now i add a bypass line (made of the dhx branch pipes, 
+    // but without its check valve)
+    let dhx_branch = DHXBranch::new();
+    let bypass_branch = crate::TherminolBranch::new(
+        "bypass_branch",
+        vec![
+            dhx_branch.get_pipe26().into(),
+            dhx_branch.get_pipe25a().into(),
+            dhx_branch.get_pipe23a().into(),
+            dhx_branch.get_pipe22().into(),
+            dhx_branch.get_pipe21().into(),
+            dhx_branch.get_pipe20().into(),
+            dhx_branch.get_pipe19().into(),
+        ]);
+
+    let bypass_branch_index = network.add_branch(bypass_branch);
+    assert_eq!(bypass_branch_index, 3);
+
+    let branch_mass_flowrates = network.solve().unwrap();
+
+    assert_eq!(branch_mass_flowrates.len(), 4);
+
+    let total_mass_flowrate: f64 = branch_mass_flowrates.iter().
+        map(|mass_flowrate| mass_flowrate.value).sum();
+
+    approx::assert_abs_diff_eq!(total_mass_flowrate, 0.0, epsilon = 1e-6);
+
+    // every branch should see the same header pressure change
+    for (branch, branch_mass_flowrate) in network.get_branches().iter().
+        zip(branch_mass_flowrates.iter()) {
+
+        approx::assert_abs_diff_eq!(
+            branch.get_pressure_change(*branch_mass_flowrate).value,
+            network.get_header_pressure_change().value,
+            epsilon = 1e-3);
+    }
+
+    assert_eq!(network.get_manometer_readings(
+            crate::ManometerReference::TopHeader).len(),
+        network.get_manometer_tap_labels().len());
+
+    // there is no fifth branch to get or set a flowrate for
+    assert!(network.get_branch_mut(bypass_branch_index).is_some());
+    assert!(network.get_branch_mut(4).is_none());
+    assert!(network.get_branch(4).is_none());
+    assert!(network.get_branch_mass_flowrate(4).is_none());
+    assert!(network.get_component_inlet_temperature(4, 0).is_none());
+    assert!(network.get_component_range_inlet_outlet_temperatures(
+            4, 0, 0).is_none());
+
+    // nor is there a component past the end of the bypass branch
+    let bypass_component_count = network.get_branch(bypass_branch_index).
+        unwrap().get_components().len();
+    assert!(network.get_component_inlet_temperature(
+            bypass_branch_index, bypass_component_count).is_none());
+    assert!(network.get_component_range_inlet_outlet_temperatures(
+            bypass_branch_index, 0, bypass_component_count).is_none());
+    assert!(network.get_component_range_inlet_outlet_temperatures(
+            bypass_branch_index, 0, 0).is_some());
+
+    let zero_flowrate = MassRate::new::<kilogram_per_second>(0.0);
+
+    match network.set_branch_mass_flowrates(vec![zero_flowrate; 3]) {
+        Err(crate::CIETSolverError::BranchCountMismatch { 
+            expected_branches, 
+            given_branches }) => {
+            assert_eq!(expected_branches, 4);
+            assert_eq!(given_branches, 3);
+        },
+        other => panic!("expected BranchCountMismatch, got {:?}", other),
+    }
+
+    assert!(network.set_branch_mass_flowrates(vec![zero_flowrate; 4]).is_ok());
+}
+
+#[test]
+pub fn assert_pump_curve_ok(){
+
+    use fluid_mechanics_rust::prelude::*;
+    use crate::{PumpCurve, OwnedCIETFacility, PumpControl};
+
+    let pump_curve = PumpCurve::new(
+        2900.0,
+        Pressure::new::<pascal>(45000.0),
+        MassRate::new::<kilogram_per_second>(0.5),
+        Pressure::new::<pascal>(35000.0));
+
+    let zero_flowrate = MassRate::new::<kilogram_per_second>(0.0);
+    let rated_flowrate = MassRate::new::<kilogram_per_second>(0.5);
+
+    // the curve passes through shutoff and the rated point
+    approx::assert_relative_eq!(
+        pump_curve.get_pressure_rise(zero_flowrate, 2900.0).value,
+        45000.0, max_relative = 1e-9);
+    approx::assert_relative_eq!(
+        pump_curve.get_pressure_rise(rated_flowrate, 2900.0).value,
+        35000.0, max_relative = 1e-9);
+
+    // affinity laws, at half speed, half the flow gives a 
+    // quarter of the pressure
+    approx::assert_relative_eq!(
+        pump_curve.get_pressure_rise(rated_flowrate * 0.5, 1450.0).value,
+        35000.0 * 0.25, max_relative = 1e-9);
+
+    // now the pump in ciet
+    let mut ciet_isothermal_facility = OwnedCIETFacility::new();
+
+    ciet_isothermal_facility.set_ctah_pump_speed(1450.0);
+    assert_eq!(ciet_isothermal_facility.get_ctah_pump_control(),
+               PumpControl::Speed(1450.0));
+
+    let (_calc_time, low_speed_ctah_flowrate, heater_branch_flowrate,
+         dhx_branch_flowrate) = ciet_isothermal_facility.calculate().unwrap();
+
+    approx::assert_abs_diff_eq!(
+        low_speed_ctah_flowrate.value 
+        + heater_branch_flowrate.value
+        + dhx_branch_flowrate.value,
+        0.0,
+        epsilon = 1e-6);
+
+    // the operating point is where the pump curve meets the 
+    // system curve, so the pump pressure rise should match 
+    // what a fixed pressure source needs for the same flow
+    let pump_pressure_rise = ciet_isothermal_facility.get_ctah_pump_pressure();
+
+    let mut fixed_pressure_facility = OwnedCIETFacility::new();
+    fixed_pressure_facility.set_ctah_pump_pressure(pump_pressure_rise);
+
+    let (_calc_time, fixed_pressure_ctah_flowrate, _heater_branch_flowrate,
+         _dhx_branch_flowrate) = fixed_pressure_facility.calculate().unwrap();
+
+    approx::assert_abs_diff_eq!(
+        low_speed_ctah_flowrate.value,
+        fixed_pressure_ctah_flowrate.value,
+        epsilon = 1e-6);
+
+    // more speed, more flow
+    ciet_isothermal_facility.set_ctah_pump_speed(2900.0);
+
+    let (_calc_time, high_speed_ctah_flowrate, _heater_branch_flowrate,
+         _dhx_branch_flowrate) = ciet_isothermal_facility.calculate().unwrap();
+
+    assert!(high_speed_ctah_flowrate.value.abs() > 
+            low_speed_ctah_flowrate.value.abs());
+
+    // setting a pressure switches the pump back to a pressure source
+    ciet_isothermal_facility.set_ctah_pump_pressure(
+        Pressure::new::<pascal>(10000.0));
+
+    assert_eq!(ciet_isothermal_facility.get_ctah_pump_control(),
+               PumpControl::PressureSource(Pressure::new::<pascal>(10000.0)));
+}
+
+#[test]
+pub fn assert_check_valve_ok(){
+
+    use fluid_mechanics_rust::prelude::*;
+    use crate::{CheckValve, Flowmeter20, OwnedCIETFacility};
+
+    let mut check_valve = CheckValve::new(
+        Flowmeter20::new().get(),
+        Pressure::new::<pascal>(1000.0),
+        1.0e-7,
+        Pressure::new::<pascal>(400.0));
+
+    assert!(!check_valve.is_open());
+
+    // below cracking pressure, only leakage flows through the seat,
+    // at 500 Pa, that is 5e-5 kg/s
+    let leakage_flowrate = MassRate::new::<kilogram_per_second>(5.0e-5);
+
+    approx::assert_relative_eq!(
+        check_valve.get_seat_pressure_change(leakage_flowrate).value,
+        -500.0, max_relative = 1e-9);
+
+    // reverse leakage is resisted the same way
+    approx::assert_relative_eq!(
+        check_valve.get_seat_pressure_change(-leakage_flowrate).value,
+        500.0, max_relative = 1e-9);
+
+    // beyond cracking, the seat pressure change is the cracking 
+    // pressure
+    let open_flowrate = MassRate::new::<kilogram_per_second>(0.1);
+
+    approx::assert_relative_eq!(
+        check_valve.get_seat_pressure_change(open_flowrate).value,
+        -1000.0, max_relative = 1e-9);
+
+    check_valve.update_state(open_flowrate);
+    assert!(check_valve.is_open());
+
+    // hysteresis, once open, the valve only needs 600 Pa to stay
+    // open, so 8e-5 kg/s (800 Pa of leakage) keeps it open
+    let reduced_flowrate = MassRate::new::<kilogram_per_second>(8.0e-5);
+
+    check_valve.update_state(reduced_flowrate);
+    assert!(check_valve.is_open());
+
+    approx::assert_relative_eq!(
+        check_valve.get_seat_pressure_change(reduced_flowrate).value,
+        -600.0, max_relative = 1e-9);
+
+    // but 5e-5 kg/s closes it again
+    check_valve.update_state(leakage_flowrate);
+    assert!(!check_valve.is_open());
+
+    // in ciet, the dhx check valve should close when the pump 
+    // pushes flow backwards through the dhx branch and open 
+    // when it pushes flow forwards
+    let mut ciet_isothermal_facility = OwnedCIETFacility::new();
+
+    for pump_pressure_pascals in [10000.0, -10000.0] {
+
+        ciet_isothermal_facility.set_ctah_pump_pressure(
+            Pressure::new::<pascal>(pump_pressure_pascals));
+
+        let (_calc_time, _ctah_branch_flowrate, _heater_branch_flowrate,
+             dhx_branch_flowrate) = ciet_isothermal_facility.calculate().unwrap();
+
+        // reverse flow is limited to leakage
+        assert!(dhx_branch_flowrate.value > -1.0e-4);
+
+        assert_eq!(ciet_isothermal_facility.get_dhx_check_valve_open(),
+                   dhx_branch_flowrate.value > 0.0);
+    }
+}
+
+#[test]
+pub fn assert_transient_pump_start_up_and_trip_ok(){
+
+    use fluid_mechanics_rust::prelude::*;
+    use std::time::Duration;
+    use crate::OwnedCIETFacility;
+
+    // steady state at full speed, for comparison
+    let mut steady_facility = OwnedCIETFacility::new();
+    steady_facility.set_ctah_pump_speed(2900.0);
+
+    let (_calc_time, steady_ctah_flowrate, steady_heater_flowrate,
+         steady_dhx_flowrate) = steady_facility.calculate().unwrap();
+
+    // pump start-up from rest
+    let mut ciet_isothermal_facility = OwnedCIETFacility::new();
+    ciet_isothermal_facility.reset_to_rest();
+    ciet_isothermal_facility.set_ctah_pump_target_speed(2900.0);
+
+    let timestep = Duration::from_millis(500);
+
+    let mut ctah_flowrate = MassRate::new::<kilogram_per_second>(0.0);
+    let mut heater_flowrate = MassRate::new::<kilogram_per_second>(0.0);
+    let mut dhx_flowrate = MassRate::new::<kilogram_per_second>(0.0);
+
+    // 30 s is 15 pump time constants
+    for _ in 0..60 {
+
+        let (_calc_time, ctah_branch_flowrate, heater_branch_flowrate,
+             dhx_branch_flowrate) = ciet_isothermal_facility.
+            step_transient(timestep).unwrap();
+
+        // mass is conserved at every step
+        approx::assert_abs_diff_eq!(
+            ctah_branch_flowrate.value 
+            + heater_branch_flowrate.value
+            + dhx_branch_flowrate.value,
+            0.0,
+            epsilon = 1e-9);
+
+        ctah_flowrate = ctah_branch_flowrate;
+        heater_flowrate = heater_branch_flowrate;
+        dhx_flowrate = dhx_branch_flowrate;
+    }
+
+    approx::assert_abs_diff_eq!(
+        ciet_isothermal_facility.get_simulation_time().as_secs_f64(), 
+        30.0, epsilon = 1e-9);
+
+    // the transient should settle at the steady state
+    approx::assert_abs_diff_eq!(ctah_flowrate.value, 
+                                steady_ctah_flowrate.value, epsilon = 1e-4);
+    approx::assert_abs_diff_eq!(heater_flowrate.value, 
+                                steady_heater_flowrate.value, epsilon = 1e-4);
+    approx::assert_abs_diff_eq!(dhx_flowrate.value, 
+                                steady_dhx_flowrate.value, epsilon = 1e-4);
+
+    // now the pump trips and coasts down
+    ciet_isothermal_facility.trip_ctah_pump();
+
+    let (_calc_time, ctah_flowrate_after_one_step, _heater_branch_flowrate,
+         _dhx_branch_flowrate) = ciet_isothermal_facility.
+        step_transient(timestep).unwrap();
+
+    // the flow does not stop immediately
+    assert!(ctah_flowrate_after_one_step.value.abs() > 0.1 * ctah_flowrate.value.abs());
+    assert!(ctah_flowrate_after_one_step.value.abs() < ctah_flowrate.value.abs());
+
+    for _ in 0..120 {
+        ciet_isothermal_facility.step_transient(timestep).unwrap();
+    }
+
+    // but it does eventually
+    approx::assert_abs_diff_eq!(
+        ciet_isothermal_facility.get_ctah_branch_mass_flowrate().value,
+        0.0, epsilon = 1e-3);
+}
+
+#[test]
+pub fn assert_loop_temperature_control_ok(){
+
+    use fluid_mechanics_rust::prelude::*;
+    use crate::{OwnedCIETFacility, Pipe6a};
+
+    let cold_temperature = ThermodynamicTemperature::new::<degree_celsius>(21.0);
+    let hot_temperature = ThermodynamicTemperature::new::<degree_celsius>(80.0);
+
+    // density and viscosity follow the component temperature
+    let mut pipe_6a = Pipe6a::new().get();
+    let cold_density = pipe_6a.get_fluid_density_immutable();
+    let cold_viscosity = pipe_6a.get_fluid_viscosity_immutable();
+
+    pipe_6a.set_fluid_temperature(hot_temperature);
+    assert_eq!(pipe_6a.get_fluid_temperature(), hot_temperature);
+    assert!(pipe_6a.get_fluid_density_immutable() < cold_density);
+    assert!(pipe_6a.get_fluid_viscosity_immutable() < cold_viscosity);
+
+    // the facility solves at 21C by default
+    let pump_pressure = Pressure::new::<pascal>(10000.0);
+
+    let mut cold_facility = OwnedCIETFacility::new();
+    cold_facility.set_ctah_pump_pressure(pump_pressure);
+    assert_eq!(cold_facility.get_loop_temperature(), cold_temperature);
+
+    let (_calc_time, cold_ctah_flowrate, _heater_flowrate, _dhx_flowrate) = 
+        cold_facility.calculate().unwrap();
+
+    // at 80C therminol is less viscous, so the same pump 
+    // pressure drives more flow
+    let mut hot_facility = OwnedCIETFacility::new();
+    hot_facility.set_ctah_pump_pressure(pump_pressure);
+    hot_facility.set_loop_temperature(hot_temperature);
+
+    assert_eq!(hot_facility.get_component_temperature("pipe_6a"), 
+               Some(hot_temperature));
+
+    let (_calc_time, hot_ctah_flowrate, hot_heater_flowrate, hot_dhx_flowrate) = 
+        hot_facility.calculate().unwrap();
+
+    assert!(hot_ctah_flowrate.value.abs() > cold_ctah_flowrate.value.abs());
+    approx::assert_abs_diff_eq!(
+        hot_ctah_flowrate.value + hot_heater_flowrate.value + hot_dhx_flowrate.value,
+        0.0, epsilon = 1e-6);
+
+    // a per component override only changes that component
+    assert!(!hot_facility.set_component_temperature(
+            "no_such_component", cold_temperature));
+    assert!(hot_facility.set_component_temperature(
+            "heater_version_1_label_1", cold_temperature));
+
+    assert_eq!(hot_facility.get_component_temperature("heater_version_1_label_1"), 
+               Some(cold_temperature));
+    assert_eq!(hot_facility.get_component_temperature("pipe_6a"), 
+               Some(hot_temperature));
+
+    // the override survives a change in loop temperature
+    let warm_temperature = ThermodynamicTemperature::new::<degree_celsius>(50.0);
+    hot_facility.set_loop_temperature(warm_temperature);
+
+    assert_eq!(hot_facility.get_component_temperature("heater_version_1_label_1"), 
+               Some(cold_temperature));
+    assert_eq!(hot_facility.get_component_temperature("pipe_6a"), 
+               Some(warm_temperature));
+
+    hot_facility.calculate().unwrap();
+
+    // until it is cleared
+    hot_facility.clear_component_temperature("heater_version_1_label_1");
+    assert_eq!(hot_facility.get_component_temperature("heater_version_1_label_1"), 
+               Some(warm_temperature));
+}
+
+#[test]
+pub fn assert_upwind_energy_equation_ok(){
+
+    use fluid_mechanics_rust::prelude::*;
+    use std::time::Duration;
+    use crate::{calculate_upwind_control_volume_temperature,
+        get_therminol_specific_heat_capacity_joule_per_kg_kelvin,
+        get_temperature_degrees_c};
+
+    let old_temperature = ThermodynamicTemperature::new::<degree_celsius>(40.0);
+    let upstream_temperature = ThermodynamicTemperature::new::<degree_celsius>(30.0);
+    let specific_heat_capacity = 
+        get_therminol_specific_heat_capacity_joule_per_kg_kelvin(old_temperature);
+
+    // with no flow, the heat just warms up the fluid in place
+    let new_temperature = calculate_upwind_control_volume_temperature(
+        old_temperature,
+        upstream_temperature,
+        2.0,
+        MassRate::new::<kilogram_per_second>(0.0),
+        1000.0,
+        Duration::from_secs(1));
+
+    approx::assert_relative_eq!(
+        get_temperature_degrees_c(new_temperature),
+        40.0 + 1000.0/(2.0 * specific_heat_capacity),
+        max_relative = 1e-9);
+
+    // with a very long timestep, the control volume reaches 
+    // steady state, the outlet is hotter than the inlet by 
+    // Q/(m cp) whichever way the fluid flows
+    for mass_flowrate_kg_per_s in [0.2, -0.2] {
+
+        let new_temperature = calculate_upwind_control_volume_temperature(
+            old_temperature,
+            upstream_temperature,
+            2.0,
+            MassRate::new::<kilogram_per_second>(mass_flowrate_kg_per_s),
+            1000.0,
+            Duration::from_secs(1_000_000));
+
+        approx::assert_relative_eq!(
+            get_temperature_degrees_c(new_temperature),
+            30.0 + 1000.0/(0.2 * specific_heat_capacity),
+            max_relative = 1e-3);
+    }
+}
+
+#[test]
+pub fn assert_heater_thermal_hydraulics_ok(){
+
+    use fluid_mechanics_rust::prelude::*;
+    use std::time::Duration;
+    use crate::{OwnedCIETFacility, 
+        get_therminol_specific_heat_capacity_joule_per_kg_kelvin,
+        get_temperature_degrees_c};
+
+    let mut ciet_facility = OwnedCIETFacility::new();
+    ciet_facility.set_ctah_pump_speed(2900.0);
+    ciet_facility.set_thermal_hydraulics_enabled(true);
+    ciet_facility.set_heater_power_watts(5000.0);
+
+    approx::assert_abs_diff_eq!(ciet_facility.get_heater_power_watts(), 5000.0);
+
+    let (_calc_time, _ctah_flowrate, heater_flowrate, _dhx_flowrate) = 
+        ciet_facility.calculate().unwrap();
+
+    // before any time passes, everything is still at 21C
+    approx::assert_abs_diff_eq!(
+        get_temperature_degrees_c(ciet_facility.get_heater_outlet_temperature()),
+        21.0, epsilon = 1e-9);
+
+    // at a fixed flowrate, the heater settles to an outlet 
+    // temperature Q/(m cp) above its inlet, the inlet itself 
+    // warms up slowly as the whole loop heats up
+    ciet_facility.advance_temperatures(Duration::from_secs(300));
+
+    let heater_inlet_temperature = ciet_facility.get_heater_inlet_temperature();
+    let heater_outlet_temperature = ciet_facility.get_heater_outlet_temperature();
+
+    let specific_heat_capacity = 
+        get_therminol_specific_heat_capacity_joule_per_kg_kelvin(
+            heater_outlet_temperature);
+    let expected_temperature_rise = 
+        5000.0/(heater_flowrate.value.abs() * specific_heat_capacity);
+
+    approx::assert_relative_eq!(
+        get_temperature_degrees_c(heater_outlet_temperature) 
+        - get_temperature_degrees_c(heater_inlet_temperature),
+        expected_temperature_rise,
+        max_relative = 0.1);
+
+    // no heat is removed anywhere, so the hot fluid reaches
+    // the ctah and the whole loop warms up
+    assert!(get_temperature_degrees_c(
+            ciet_facility.get_ctah_inlet_temperature()) > 21.0);
+    assert!(get_temperature_degrees_c(heater_inlet_temperature) > 21.0);
+
+    // the transient carries on from here with the flowrates 
+    // following the temperatures
+    for _ in 0..10 {
+        ciet_facility.step_transient(Duration::from_millis(500)).unwrap();
+    }
+
+    assert!(ciet_facility.get_heater_outlet_temperature() > 
+            ciet_facility.get_heater_inlet_temperature());
+
+    // holding one component at its own temperature leaves the 
+    // temperatures the transient has reached everywhere else
+    let heater_outlet_temperature = ciet_facility.get_heater_outlet_temperature();
+    let held_temperature = ThermodynamicTemperature::new::<degree_celsius>(80.0);
+
+    assert!(ciet_facility.set_component_temperature("pipe_6a", held_temperature));
+    approx::assert_relative_eq!(
+        ciet_facility.get_component_temperature("pipe_6a").unwrap().value,
+        held_temperature.value, max_relative = 1e-12);
+    approx::assert_relative_eq!(
+        ciet_facility.get_heater_outlet_temperature().value,
+        heater_outlet_temperature.value, max_relative = 1e-12);
+
+    // and so does letting go of it
+    ciet_facility.clear_all_component_temperatures();
+    approx::assert_relative_eq!(
+        ciet_facility.get_component_temperature("pipe_6a").unwrap().value,
+        held_temperature.value, max_relative = 1e-12);
+    approx::assert_relative_eq!(
+        ciet_facility.get_heater_outlet_temperature().value,
+        heater_outlet_temperature.value, max_relative = 1e-12);
+}
+
+#[test]
+pub fn assert_natural_circulation_ok(){
+
+    use fluid_mechanics_rust::prelude::*;
+    use std::time::Duration;
+    use crate::OwnedCIETFacility;
+
+    // at a uniform temperature and with the pump off, there is
+    // (next to) nothing to drive the flow
+    let mut ciet_facility = OwnedCIETFacility::new();
+    ciet_facility.set_ctah_pump_pressure(Pressure::new::<pascal>(0.0));
+
+    let isothermal_driving_head = 
+        ciet_facility.get_natural_circulation_driving_head();
+
+    let (_calc_time, _ctah_flowrate, isothermal_heater_flowrate, 
+         _dhx_flowrate) = ciet_facility.calculate().unwrap();
+
+    // a hot heater is lighter than the rest of the loop, so 
+    // the fluid rises through it (negative flow, since positive 
+    // flow runs from the top header to the bottom header) and 
+    // sinks down the dhx branch through the check valve
+    assert!(ciet_facility.set_component_temperature(
+            "heater_version_1_label_1", 
+            ThermodynamicTemperature::new::<degree_celsius>(80.0)));
+
+    assert!(ciet_facility.get_natural_circulation_driving_head() > 
+            isothermal_driving_head);
+
+    let (_calc_time, ctah_flowrate, heater_flowrate, dhx_flowrate) = 
+        ciet_facility.calculate().unwrap();
+
+    assert!(heater_flowrate.value < isothermal_heater_flowrate.value);
+    assert!(heater_flowrate.value < 0.0);
+    assert!(dhx_flowrate.value > 0.0);
+    assert!(ciet_facility.get_dhx_check_valve_open());
+    approx::assert_abs_diff_eq!(
+        ctah_flowrate.value + heater_flowrate.value + dhx_flowrate.value,
+        0.0, epsilon = 1e-6);
+
+    // the same happens when the heater warms up the fluid 
+    // in a transient starting from rest
+    let mut ciet_facility = OwnedCIETFacility::new();
+    ciet_facility.set_ctah_pump_pressure(Pressure::new::<pascal>(0.0));
+    ciet_facility.reset_to_rest();
+    ciet_facility.set_thermal_hydraulics_enabled(true);
+    ciet_facility.set_heater_power_watts(2000.0);
+
+    for _ in 0..120 {
+        ciet_facility.step_transient(Duration::from_millis(500)).unwrap();
+    }
+
+    assert!(ciet_facility.get_natural_circulation_driving_head() > 
+            isothermal_driving_head);
+    assert!(ciet_facility.get_heater_branch_mass_flowrate().value < 0.0);
+    assert!(ciet_facility.get_dhx_branch_mass_flowrate().value > 0.0);
+}
+
+#[test]
+pub fn assert_ctah_heat_exchanger_ok(){
+
+    use fluid_mechanics_rust::prelude::*;
+    use std::time::Duration;
+    use crate::{CTAHHeatExchanger, OwnedCIETFacility, TherminolVP1,
+        get_therminol_specific_heat_capacity_joule_per_kg_kelvin,
+        get_temperature_degrees_c};
+
+    let oil_inlet_temperature = ThermodynamicTemperature::new::<degree_celsius>(80.0);
+    let oil_mass_flowrate = MassRate::new::<kilogram_per_second>(0.18);
+
+    let mut ctah = CTAHHeatExchanger::ciet_ctah();
+
+    // with the fan off, the oil goes through unchanged
+    approx::assert_abs_diff_eq!(
+        ctah.get_heat_removal_watts(&TherminolVP1, oil_inlet_temperature, oil_mass_flowrate),
+        0.0);
+    assert_eq!(
+        ctah.get_oil_outlet_temperature(&TherminolVP1, oil_inlet_temperature, oil_mass_flowrate),
+        oil_inlet_temperature);
+
+    // at half speed, the oil is cooled but stays above the air
+    // temperature
+    ctah.set_fan_speed_rpm(0.5 * ctah.get_rated_fan_speed_rpm());
+
+    let half_speed_outlet_temperature = 
+        ctah.get_oil_outlet_temperature(&TherminolVP1, oil_inlet_temperature, oil_mass_flowrate);
+    let half_speed_heat_removal_watts = 
+        ctah.get_heat_removal_watts(&TherminolVP1, oil_inlet_temperature, oil_mass_flowrate);
+
+    assert!(half_speed_outlet_temperature < oil_inlet_temperature);
+    assert!(half_speed_outlet_temperature > ctah.get_air_inlet_temperature());
+
+    let effectiveness = ctah.get_effectiveness(
+        &TherminolVP1, oil_inlet_temperature, oil_mass_flowrate);
+    assert!(effectiveness > 0.0 && effectiveness < 1.0);
+
+    // the heat removed is the heat lost by the oil
+    let specific_heat_capacity = 
+        get_therminol_specific_heat_capacity_joule_per_kg_kelvin(
+            oil_inlet_temperature);
+
+    approx::assert_relative_eq!(
+        half_speed_heat_removal_watts,
+        oil_mass_flowrate.value * specific_heat_capacity * 
+        (get_temperature_degrees_c(oil_inlet_temperature) 
+         - get_temperature_degrees_c(half_speed_outlet_temperature)),
+        max_relative = 1e-9);
+
+    // turning up the fan cools the oil further
+    ctah.set_fan_speed_rpm(ctah.get_rated_fan_speed_rpm());
+
+    assert!(ctah.get_oil_outlet_temperature(
+            &TherminolVP1, oil_inlet_temperature, oil_mass_flowrate) 
+            < half_speed_outlet_temperature);
+
+    // in the facility, the ctah eventually removes all the 
+    // heater power
+    let mut ciet_facility = OwnedCIETFacility::new();
+    ciet_facility.set_ctah_pump_speed(2900.0);
+    ciet_facility.set_thermal_hydraulics_enabled(true);
+    ciet_facility.set_heater_power_watts(5000.0);
+    ciet_facility.set_ctah_fan_speed_rpm(1750.0);
+
+    ciet_facility.calculate().unwrap();
+    ciet_facility.advance_temperatures(Duration::from_secs(5000));
+
+    approx::assert_relative_eq!(
+        ciet_facility.get_ctah_heat_removal_watts(),
+        5000.0,
+        max_relative = 0.02);
+
+    assert!(ciet_facility.get_ctah_outlet_temperature() < 
+            ciet_facility.get_ctah_inlet_temperature());
+}
+
+#[test]
+pub fn assert_dracs_loop_ok(){
+
+    use fluid_mechanics_rust::prelude::*;
+    use std::time::Duration;
+    use crate::OwnedDRACSFacility;
+
+    // both legs drop by the same height, so at one temperature
+    // and with no driving head, nothing flows
+    let mut dracs_facility = OwnedDRACSFacility::new();
+
+    approx::assert_abs_diff_eq!(
+        dracs_facility.get_natural_circulation_driving_head().value,
+        0.0, epsilon = 0.01);
+
+    let (_calc_time, loop_flowrate) = dracs_facility.calculate().unwrap();
+    approx::assert_abs_diff_eq!(loop_flowrate.value, 0.0, epsilon = 1e-5);
+
+    // a driving head pushes fluid down the cold leg and up the
+    // hot leg
+    dracs_facility.set_driving_head(Pressure::new::<pascal>(500.0));
+
+    let (_calc_time, pumped_loop_flowrate) = dracs_facility.calculate().unwrap();
+
+    assert!(pumped_loop_flowrate.value > 0.0);
+    assert_eq!(dracs_facility.get_hot_branch().get_name(), "dracs_hot_branch");
+    approx::assert_abs_diff_eq!(
+        dracs_facility.get_network().get_branch_mass_flowrate(0).unwrap().value,
+        -pumped_loop_flowrate.value, epsilon = 1e-9);
+
+    // so does a hot leg which is hotter than the cold leg
+    dracs_facility.set_driving_head(Pressure::new::<pascal>(0.0));
+    dracs_facility.set_hot_branch_temperature(
+        ThermodynamicTemperature::new::<degree_celsius>(60.0));
+
+    assert!(dracs_facility.get_natural_circulation_driving_head().value > 0.0);
+
+    let (_calc_time, natural_circulation_flowrate) = 
+        dracs_facility.calculate().unwrap();
+
+    assert!(natural_circulation_flowrate.value > 0.0);
+
+    // starting from rest, the transient settles at the same 
+    // flowrate
+    dracs_facility.reset_to_rest();
+
+    for _ in 0..240 {
+        dracs_facility.step_transient(Duration::from_millis(500)).unwrap();
+    }
+
+    approx::assert_relative_eq!(
+        dracs_facility.get_loop_mass_flowrate().value,
+        natural_circulation_flowrate.value,
+        max_relative = 1e-3);
+
+    assert!(!dracs_facility.get_manometer_tap_labels().is_empty());
+}
+
+#[test]
+pub fn assert_dhx_heat_exchanger_ok(){
+
+    use fluid_mechanics_rust::prelude::*;
+    use crate::{DHXHeatExchanger, TherminolVP1};
+
+    // with balanced flows, effectiveness is NTU/(1+NTU)
+    approx::assert_relative_eq!(
+        DHXHeatExchanger::get_counterflow_effectiveness(2.0, 1.0),
+        2.0/3.0, max_relative = 1e-9);
+
+    // and close to balanced, the general formula agrees
+    approx::assert_relative_eq!(
+        DHXHeatExchanger::get_counterflow_effectiveness(2.0, 1.0 - 1e-6),
+        2.0/3.0, max_relative = 1e-5);
+
+    let dhx = DHXHeatExchanger::ciet_dhx();
+
+    let hot_temperature = ThermodynamicTemperature::new::<degree_celsius>(80.0);
+    let cold_temperature = ThermodynamicTemperature::new::<degree_celsius>(30.0);
+    let mass_flowrate = MassRate::new::<kilogram_per_second>(0.05);
+    let zero_flowrate = MassRate::new::<kilogram_per_second>(0.0);
+
+    // heat goes from the hotter side to the colder side
+    let heat_transfer_watts = dhx.get_heat_transfer_watts(
+        &TherminolVP1, hot_temperature, mass_flowrate, 
+        &TherminolVP1, cold_temperature, -mass_flowrate);
+
+    assert!(heat_transfer_watts > 0.0);
+    approx::assert_relative_eq!(
+        dhx.get_heat_transfer_watts(
+            &TherminolVP1, cold_temperature, mass_flowrate, 
+            &TherminolVP1, hot_temperature, mass_flowrate),
+        -heat_transfer_watts, max_relative = 1e-2);
+
+    // and no heat is transferred without flow on both sides
+    approx::assert_abs_diff_eq!(
+        dhx.get_heat_transfer_watts(
+            &TherminolVP1, hot_temperature, zero_flowrate, 
+            &TherminolVP1, cold_temperature, mass_flowrate),
+        0.0);
+}
+
+#[test]
+pub fn assert_coupled_passive_decay_heat_removal_ok(){
+
+    use fluid_mechanics_rust::prelude::*;
+    use std::time::Duration;
+    use crate::CoupledCIETFacility;
+
+    // the ctah pump is off, the heater is on, and the only 
+    // way out for the heat is through the DHX, DRACS and TCHX
+    let mut coupled_facility = CoupledCIETFacility::new();
+
+    coupled_facility.get_primary_loop_mut().
+        set_ctah_pump_pressure(Pressure::new::<pascal>(0.0));
+    coupled_facility.get_primary_loop_mut().reset_to_rest();
+    coupled_facility.get_primary_loop_mut().set_heater_power_watts(2000.0);
+    coupled_facility.get_dracs_loop_mut().reset_to_rest();
+    coupled_facility.get_dracs_loop_mut().set_tchx_fan_speed_rpm(1750.0);
+
+    for _ in 0..1200 {
+        coupled_facility.step_transient(Duration::from_millis(500)).unwrap();
+    }
+
+    approx::assert_abs_diff_eq!(
+        coupled_facility.get_simulation_time().as_secs_f64(), 
+        600.0, epsilon = 1e-6);
+
+    let flowrates = coupled_facility.get_flowrates();
+
+    // natural circulation in both loops
+    assert!(flowrates.heater_branch_mass_flowrate.value < 0.0);
+    assert!(flowrates.dhx_branch_mass_flowrate.value > 0.0);
+    assert!(flowrates.dracs_loop_mass_flowrate.value > 0.0);
+
+    // heat leaves the primary loop through the DHX and the 
+    // DRACS loop through the TCHX
+    let dhx_heat_transfer_watts = coupled_facility.get_dhx_heat_transfer_watts();
+    assert!(dhx_heat_transfer_watts > 0.0);
+    assert!(coupled_facility.get_dracs_loop().get_tchx_heat_removal_watts() > 0.0);
+
+    // what the primary loop loses, the DRACS loop gains
+    approx::assert_abs_diff_eq!(
+        coupled_facility.get_primary_loop().get_dhx_heat_removal_watts(),
+        coupled_facility.get_dracs_loop().get_dhx_heat_input_watts(),
+        epsilon = 1e-9);
+
+    assert!(coupled_facility.get_dracs_loop().get_dhx_tube_side_outlet_temperature() 
+            > coupled_facility.get_dracs_loop().get_dhx_tube_side_inlet_temperature());
+}
+
+#[test]
+pub fn assert_insulation_heat_loss_ok(){
+
+    use fluid_mechanics_rust::prelude::*;
+    use std::time::Duration;
+    use std::f64::consts::PI;
+    use crate::{OwnedCIETFacility, InsulationParameters, Pipe6a,
+        calculate_upwind_control_volume_temperature_with_ambient_loss,
+        get_temperature_degrees_c};
+
+    // a pipe with no insulation loses no heat
+    let mut pipe_6a = Pipe6a::new().get();
+    approx::assert_abs_diff_eq!(
+        pipe_6a.get_ambient_conductance_watts_per_kelvin(), 0.0);
+
+    // with zero thickness, only convection from the bare 
+    // surface is left, UA = h 2 pi r L
+    pipe_6a.set_insulation(InsulationParameters::new(
+            Length::new::<meter>(0.0), 0.04, 6.0));
+
+    let pipe_radius = 
+        (pipe_6a.get_cross_sectional_area_immutable().value / PI).sqrt();
+    let pipe_length = pipe_6a.get_component_length_immutable().value;
+
+    approx::assert_relative_eq!(
+        pipe_6a.get_ambient_conductance_watts_per_kelvin(),
+        6.0 * 2.0 * PI * pipe_radius * pipe_length,
+        max_relative = 1e-9);
+
+    // insulation adds resistance, so less heat is lost
+    let bare_conductance = pipe_6a.get_ambient_conductance_watts_per_kelvin();
+    pipe_6a.set_insulation(InsulationParameters::ciet_fiberglass());
+    assert!(pipe_6a.get_ambient_conductance_watts_per_kelvin() < bare_conductance);
+    assert!(pipe_6a.get_ambient_conductance_watts_per_kelvin() > 0.0);
+
+    // stagnant fluid cools towards ambient, and never past it
+    let ambient_temperature = ThermodynamicTemperature::new::<degree_celsius>(21.0);
+    let new_temperature = 
+        calculate_upwind_control_volume_temperature_with_ambient_loss(
+            ThermodynamicTemperature::new::<degree_celsius>(80.0),
+            ThermodynamicTemperature::new::<degree_celsius>(80.0),
+            1.0,
+            1600.0,
+            MassRate::new::<kilogram_per_second>(0.0),
+            0.0,
+            1.0,
+            ambient_temperature,
+            Duration::from_secs(1_000_000));
+
+    approx::assert_abs_diff_eq!(
+        get_temperature_degrees_c(new_temperature), 21.0, epsilon = 0.1);
+
+    // a hot loop with no heater stays hot when it is not 
+    // insulated...
+    let mut ciet_facility = OwnedCIETFacility::new();
+    ciet_facility.set_ctah_pump_speed(2900.0);
+    ciet_facility.set_loop_temperature(
+        ThermodynamicTemperature::new::<degree_celsius>(80.0));
+    ciet_facility.set_thermal_hydraulics_enabled(true);
+    ciet_facility.calculate().unwrap();
+
+    approx::assert_abs_diff_eq!(ciet_facility.get_heat_loss_watts(), 0.0);
+
+    ciet_facility.advance_temperatures(Duration::from_secs(60));
+    approx::assert_abs_diff_eq!(
+        get_temperature_degrees_c(ciet_facility.get_heater_inlet_temperature()), 
+        80.0, epsilon = 1e-6);
+
+    // ...and cools down once it is, losing less heat as it 
+    // gets closer to ambient
+    ciet_facility.set_insulation(InsulationParameters::ciet_fiberglass());
+    ciet_facility.set_ambient_temperature(ambient_temperature);
+
+    let initial_heat_loss_watts = ciet_facility.get_heat_loss_watts();
+    assert!(initial_heat_loss_watts > 0.0);
+    assert!(ciet_facility.get_component_heat_loss_watts(
+            "heater_version_1_label_1").unwrap() > 0.0);
+
+    ciet_facility.advance_temperatures(Duration::from_secs(600));
+
+    let heater_inlet_temperature_degc = 
+        get_temperature_degrees_c(ciet_facility.get_heater_inlet_temperature());
+    assert!(heater_inlet_temperature_degc < 80.0);
+    assert!(heater_inlet_temperature_degc > 21.0);
+    assert!(ciet_facility.get_heat_loss_watts() < initial_heat_loss_watts);
+}
+
+#[test]
+pub fn assert_wall_thermal_inertia_ok(){
+
+    use fluid_mechanics_rust::prelude::*;
+    use std::time::Duration;
+    use crate::{OwnedCIETFacility, PipeWall, WallParameters,
+        get_fluid_wall_nusselt_number, get_temperature_degrees_c,
+        LAMINAR_NUSSELT_NUMBER, TURBULENT_REYNOLDS_NUMBER_LIMIT};
+
+    // laminar flow has a constant Nusselt number, and the 
+    // transition joins onto the turbulent correlation
+    approx::assert_abs_diff_eq!(
+        get_fluid_wall_nusselt_number(1000.0, 20.0), LAMINAR_NUSSELT_NUMBER);
+    approx::assert_relative_eq!(
+        get_fluid_wall_nusselt_number(TURBULENT_REYNOLDS_NUMBER_LIMIT - 1e-6, 20.0),
+        get_fluid_wall_nusselt_number(TURBULENT_REYNOLDS_NUMBER_LIMIT, 20.0),
+        max_relative = 1e-6);
+    assert!(get_fluid_wall_nusselt_number(1.0e4, 20.0) > 
+            get_fluid_wall_nusselt_number(5.0e3, 20.0));
+
+    // a lumped wall heated from within, with nothing to lose 
+    // the heat to, warms up at Q/(m cp)
+    let cross_sectional_area = Area::new::<square_meter>(5.0e-4);
+    let component_length = Length::new::<meter>(1.0);
+    let mut lumped_wall = PipeWall::new(
+        WallParameters::ciet_pipe_wall(),
+        ThermodynamicTemperature::new::<degree_celsius>(21.0));
+    lumped_wall.set_heat_input_watts(100.0);
+
+    let heat_capacity = lumped_wall.get_heat_capacity_joule_per_kelvin(
+        cross_sectional_area, component_length);
+
+    lumped_wall.advance_temperatures(
+        ThermodynamicTemperature::new::<degree_celsius>(21.0),
+        0.0,
+        0.0,
+        ThermodynamicTemperature::new::<degree_celsius>(21.0),
+        cross_sectional_area,
+        component_length,
+        Duration::from_secs(10));
+
+    approx::assert_relative_eq!(
+        get_temperature_degrees_c(lumped_wall.get_inner_node_temperature()),
+        21.0 + 100.0 * 10.0 / heat_capacity,
+        max_relative = 1e-9);
+
+    // a radially discretised wall heated by the fluid warms 
+    // from the inside out
+    let mut radial_wall = PipeWall::new(
+        WallParameters::stainless_steel_304(Length::new::<millimeter>(5.0), 4),
+        ThermodynamicTemperature::new::<degree_celsius>(21.0));
+
+    radial_wall.advance_temperatures(
+        ThermodynamicTemperature::new::<degree_celsius>(80.0),
+        50.0,
+        0.0,
+        ThermodynamicTemperature::new::<degree_celsius>(21.0),
+        cross_sectional_area,
+        component_length,
+        Duration::from_secs(1));
+
+    let wall_temperatures = radial_wall.get_node_temperatures();
+    assert_eq!(wall_temperatures.len(), 4);
+    for node_index in 1..4 {
+        assert!(wall_temperatures[node_index - 1] > wall_temperatures[node_index]);
+    }
+    assert!(get_temperature_degrees_c(radial_wall.get_outer_node_temperature()) > 21.0);
+
+    // the walls soak up heat, so the heater outlet warms up 
+    // more slowly with them than without them
+    let mut facility_without_walls = OwnedCIETFacility::new();
+    let mut facility_with_walls = OwnedCIETFacility::new();
+    facility_with_walls.set_ciet_walls();
+
+    for ciet_facility in [&mut facility_without_walls, &mut facility_with_walls] {
+        ciet_facility.set_ctah_pump_speed(2900.0);
+        ciet_facility.set_thermal_hydraulics_enabled(true);
+        ciet_facility.set_heater_power_watts(5000.0);
+        ciet_facility.calculate().unwrap();
+        ciet_facility.advance_temperatures(Duration::from_secs(20));
+    }
+
+    approx::assert_abs_diff_eq!(facility_with_walls.get_heater_power_watts(), 5000.0);
+
+    assert!(facility_with_walls.get_heater_outlet_temperature() < 
+            facility_without_walls.get_heater_outlet_temperature());
+    assert!(facility_with_walls.get_heater_outlet_temperature() > 
+            facility_with_walls.get_heater_inlet_temperature());
+
+    // the heat is generated in the heater wall, so the wall is
+    // hotter than the fluid it heats
+    let heater_wall_temperatures = facility_with_walls.
+        get_component_wall_temperatures("heater_version_1_label_1").unwrap();
+    assert_eq!(heater_wall_temperatures.len(), 3);
+    assert!(heater_wall_temperatures[0] > 
+            facility_with_walls.get_heater_outlet_temperature());
+
+    // removing the walls puts the heater power back into the 
+    // fluid
+    facility_with_walls.clear_walls();
+    approx::assert_abs_diff_eq!(facility_with_walls.get_heater_power_watts(), 5000.0);
+    assert!(facility_with_walls.
+            get_component_wall_temperatures("heater_version_1_label_1").is_none());
+
+    // a component held at its own temperature starts with its 
+    // wall at that temperature, not the loop temperature
+    let held_temperature = ThermodynamicTemperature::new::<degree_celsius>(80.0);
+    let mut held_facility = OwnedCIETFacility::new();
+    held_facility.set_ciet_walls();
+    held_facility.set_component_temperature("pipe_6a", held_temperature);
+
+    for wall_temperature in held_facility.
+        get_component_wall_temperatures("pipe_6a").unwrap() {
+        approx::assert_relative_eq!(
+            wall_temperature.value, held_temperature.value, max_relative = 1e-12);
+    }
+}
+
+#[test]
+pub fn assert_temperature_range_validation_ok(){
+
+    use fluid_mechanics_rust::prelude::*;
+    use crate::{OwnedCIETFacility, CIETSolverError, Pipe6a,
+        TemperatureRangePolicy, check_component_fluid_temperature,
+        THERMINOL_MAX_VALIDATED_TEMPERATURE_DEGC};
+
+    // within 20C to 180C there is nothing to report
+    assert!(check_component_fluid_temperature(
+            "pipe_6a", 
+            ThermodynamicTemperature::new::<degree_celsius>(21.0)).is_ok());
+
+    match check_component_fluid_temperature(
+        "pipe_6a", 
+        ThermodynamicTemperature::new::<degree_celsius>(200.0)) {
+        Err(CIETSolverError::TemperatureOutOfRange { 
+            component_name, 
+            temperature_degrees_c, 
+            max_temperature_degrees_c, .. }) => {
+            assert_eq!(component_name, "pipe_6a");
+            approx::assert_relative_eq!(temperature_degrees_c, 200.0, 
+                                        max_relative = 1e-9);
+            approx::assert_abs_diff_eq!(max_temperature_degrees_c, 
+                                        THERMINOL_MAX_VALIDATED_TEMPERATURE_DEGC);
+        },
+        other => panic!("expected TemperatureOutOfRange, got {:?}", other),
+    }
+
+    let mut pipe_6a = Pipe6a::new().get();
+    assert!(pipe_6a.check_fluid_temperature_range().is_ok());
+    pipe_6a.set_fluid_temperature(
+        ThermodynamicTemperature::new::<degree_celsius>(10.0));
+    assert!(pipe_6a.check_fluid_temperature_range().is_err());
+
+    // by default, the facility carries on with extrapolated 
+    // properties, but counts the event
+    let mut ciet_facility = OwnedCIETFacility::new();
+    ciet_facility.set_ctah_pump_speed(2900.0);
+    ciet_facility.calculate().unwrap();
+
+    assert_eq!(ciet_facility.get_temperature_range_policy(), 
+               TemperatureRangePolicy::Warn);
+    assert_eq!(ciet_facility.get_out_of_range_temperature_events(), 0);
+
+    ciet_facility.set_component_temperature(
+        "heater_version_1_label_1", 
+        ThermodynamicTemperature::new::<degree_celsius>(200.0));
+    ciet_facility.calculate().unwrap();
+
+    assert_eq!(ciet_facility.get_out_of_range_temperature_events(), 1);
+    assert_eq!(ciet_facility.get_temperature_range_violations().len(), 1);
+
+    // staying out of range is still one event
+    ciet_facility.calculate().unwrap();
+    assert_eq!(ciet_facility.get_out_of_range_temperature_events(), 1);
+
+    // coming back into range clears the violation, and leaving 
+    // again counts as a new event
+    ciet_facility.clear_component_temperature("heater_version_1_label_1");
+    ciet_facility.calculate().unwrap();
+    assert!(ciet_facility.get_temperature_range_violations().is_empty());
+
+    ciet_facility.set_component_temperature(
+        "heater_version_1_label_1", 
+        ThermodynamicTemperature::new::<degree_celsius>(200.0));
+    ciet_facility.calculate().unwrap();
+    assert_eq!(ciet_facility.get_out_of_range_temperature_events(), 2);
+
+    // if asked to, the facility refuses to solve instead
+    ciet_facility.set_temperature_range_policy(TemperatureRangePolicy::Error);
+
+    match ciet_facility.calculate() {
+        Err(CIETSolverError::TemperatureOutOfRange { component_name, .. }) => 
+            assert_eq!(component_name, "heater_version_1_label_1"),
+        other => panic!("expected TemperatureOutOfRange, got {:?}", other),
+    }
+
+    ciet_facility.reset_out_of_range_temperature_events();
+    assert_eq!(ciet_facility.get_out_of_range_temperature_events(), 0);
+}
+
+#[test]
+pub fn assert_working_fluid_properties_ok(){
+
+    use fluid_mechanics_rust::prelude::*;
+    use std::sync::Arc;
+    use crate::{OwnedCIETFacility, Pipe6a, FluidProperties,
+        TherminolVP1, LiquidWater, DowthermA, FLiBe};
+
+    let room_temperature = ThermodynamicTemperature::new::<degree_celsius>(21.0);
+
+    // the default fluid is therminol, with the same density and
+    // viscosity TherminolVP1Properties always gave
+    let mut pipe_6a = Pipe6a::new().get();
+    assert_eq!(pipe_6a.get_fluid_properties().get_fluid_name(), "therminol_vp1");
+    approx::assert_relative_eq!(
+        pipe_6a.get_fluid_density_immutable().value,
+        TherminolVP1Properties::new().density(room_temperature).value,
+        max_relative = 1e-12);
+
+    // spot checks against handbook values
+    approx::assert_relative_eq!(
+        LiquidWater.get_density(room_temperature).value, 998.0, 
+        max_relative = 1e-3);
+    approx::assert_relative_eq!(
+        LiquidWater.get_viscosity(room_temperature).value, 9.8e-4, 
+        max_relative = 0.03);
+    approx::assert_relative_eq!(
+        LiquidWater.get_specific_heat_capacity_joule_per_kg_kelvin(room_temperature), 
+        4182.0, max_relative = 1e-2);
+
+    let salt_temperature = ThermodynamicTemperature::new::<degree_celsius>(600.0);
+    approx::assert_relative_eq!(
+        FLiBe.get_density(salt_temperature).value, 
+        2413.0 - 0.488 * 873.15, max_relative = 1e-9);
+    approx::assert_relative_eq!(
+        FLiBe.get_viscosity(salt_temperature).value, 0.0085, 
+        max_relative = 0.05);
+
+    // dowtherm A and therminol VP-1 are much the same fluid
+    let oil_temperature = ThermodynamicTemperature::new::<degree_celsius>(80.0);
+    approx::assert_relative_eq!(
+        DowthermA.get_density(oil_temperature).value,
+        TherminolVP1.get_density(oil_temperature).value,
+        max_relative = 0.02);
+
+    // the range check follows the fluid
+    pipe_6a.set_fluid_properties(Arc::new(FLiBe));
+    pipe_6a.set_fluid_temperature(room_temperature);
+    assert!(pipe_6a.check_fluid_temperature_range().is_err());
+    pipe_6a.set_fluid_temperature(salt_temperature);
+    assert!(pipe_6a.check_fluid_temperature_range().is_ok());
+
+    // water is far less viscous than therminol, so the same 
+    // pump pressure drives more flow through the same loop
+    let mut therminol_facility = OwnedCIETFacility::new();
+    let mut water_facility = OwnedCIETFacility::new();
+    water_facility.set_fluid_properties(Arc::new(LiquidWater));
+
+    for ciet_facility in [&mut therminol_facility, &mut water_facility] {
+        ciet_facility.set_ctah_pump_pressure(Pressure::new::<pascal>(4000.0));
+    }
+
+    let (_, therminol_ctah_flowrate, _, _) = therminol_facility.calculate().unwrap();
+    let (_, water_ctah_flowrate, _, _) = water_facility.calculate().unwrap();
+
+    assert!(water_ctah_flowrate.value.abs() > therminol_ctah_flowrate.value.abs());
+    assert!(water_facility.get_temperature_range_violations().is_empty());
+
+    // the same loop filled with salt at its operating 
+    // temperature still solves
+    let mut salt_facility = OwnedCIETFacility::new();
+    salt_facility.set_fluid_properties(Arc::new(FLiBe));
+    salt_facility.set_loop_temperature(salt_temperature);
+    salt_facility.set_ctah_pump_pressure(Pressure::new::<pascal>(4000.0));
+
+    salt_facility.calculate().unwrap();
+    assert!(salt_facility.get_temperature_range_violations().is_empty());
+}
+
+#[test]
+pub fn assert_ciet_heater_version_2_ok(){
+
+    use fluid_mechanics_rust::prelude::*;
+    use crate::{CietHeaterVersion, CietHeaterVersion1, CietHeaterVersion2,
+        HeaterBranch, OwnedCIETFacility, HeatTransferCorrelation};
+
+    // the twisted tape raises the friction factor and the 
+    // Nusselt number over the bare annulus, in both laminar 
+    // and turbulent flow
+    for reynolds_number in [500.0, 3000.0, 1.0e4] {
+        assert!(CietHeaterVersion2::custom_darcy(reynolds_number, 0.0) > 
+                CietHeaterVersion1::custom_darcy(reynolds_number, 0.0));
+        assert!(CietHeaterVersion2::get_heat_transfer_correlation().
+                get_nusselt_number(reynolds_number, 20.0) > 
+                HeatTransferCorrelation::Tube.
+                get_nusselt_number(reynolds_number, 20.0));
+    }
+
+    // reverse flow gives a negative friction factor, as in 
+    // the other components
+    approx::assert_relative_eq!(
+        CietHeaterVersion2::custom_darcy(-500.0, 0.0),
+        -CietHeaterVersion2::custom_darcy(500.0, 0.0));
+
+    // the heater branch is built with the first version unless
+    // told otherwise
+    let heater_branch_version_1 = HeaterBranch::new();
+    let heater_branch_version_2 = 
+        HeaterBranch::new_with_heater_version(CietHeaterVersion::Version2);
+
+    assert_eq!(heater_branch_version_1.get_ciet_heater().get_name(), 
+               "heater_version_1_label_1");
+    assert_eq!(heater_branch_version_2.get_ciet_heater().get_name(), 
+               "heater_version_2_label_1");
+    assert_eq!(heater_branch_version_2.get_ciet_heater().
+               get_heat_transfer_correlation(),
+               CietHeaterVersion2::get_heat_transfer_correlation());
+
+    // with the same pump pressure, the extra losses of the 
+    // insert take flow away from the heater branch
+    let mut facility_version_1 = OwnedCIETFacility::new();
+    let mut facility_version_2 = OwnedCIETFacility::
+        new_with_heater_version(CietHeaterVersion::Version2);
+
+    assert_eq!(facility_version_2.get_ciet_heater_version(), 
+               CietHeaterVersion::Version2);
+    assert_eq!(facility_version_2.get_heater_name(), "heater_version_2_label_1");
+
+    let pump_pressure = Pressure::new::<pascal>(4000.0);
+    facility_version_1.set_ctah_pump_pressure(pump_pressure);
+    facility_version_2.set_ctah_pump_pressure(pump_pressure);
+
+    facility_version_1.calculate().unwrap();
+    facility_version_2.calculate().unwrap();
+
+    assert!(facility_version_2.get_heater_branch_mass_flowrate().value.abs() <
+            facility_version_1.get_heater_branch_mass_flowrate().value.abs());
+
+    // heater power still goes into the heater, whichever 
+    // version it is
+    facility_version_2.set_heater_power_watts(1000.0);
+    approx::assert_relative_eq!(facility_version_2.get_heater_power_watts(), 1000.0);
+}
+
+#[test]
+pub fn assert_point_kinetics_ok(){
+
+    use fluid_mechanics_rust::prelude::*;
+    use std::time::Duration;
+    use crate::{OwnedCIETFacility, PointKinetics, PointKineticsParameters};
+
+    let reference_temperature = ThermodynamicTemperature::new::<degree_celsius>(80.0);
+    let nominal_power_watts = 1000.0;
+
+    // a critical core at its reference temperatures stays at 
+    // its nominal power
+    let mut point_kinetics = PointKinetics::new(
+        PointKineticsParameters::ciet_default(), 
+        nominal_power_watts, 
+        reference_temperature, 
+        reference_temperature);
+
+    for _ in 0..100 {
+        point_kinetics.advance(reference_temperature, reference_temperature, 
+                               Duration::from_millis(100));
+    }
+
+    approx::assert_relative_eq!(point_kinetics.get_power_watts(), 
+                                nominal_power_watts, max_relative = 1e-9);
+
+    // the fluid and fuel temperature coefficients are negative, 
+    // so a hotter core loses reactivity
+    assert!(point_kinetics.get_feedback_reactivity(
+            ThermodynamicTemperature::new::<degree_celsius>(90.0), 
+            ThermodynamicTemperature::new::<degree_celsius>(90.0)) < 0.0);
+
+    // inserting positive reactivity raises the power
+    point_kinetics.set_external_reactivity_dollars(0.1);
+    approx::assert_relative_eq!(point_kinetics.get_external_reactivity_dollars(), 0.1);
+
+    for _ in 0..10 {
+        point_kinetics.advance(reference_temperature, reference_temperature, 
+                               Duration::from_millis(100));
+    }
+
+    assert!(point_kinetics.get_power_watts() > nominal_power_watts);
+
+    // after a scram, the fission power drops away and the power 
+    // left is mostly decay heat
+    point_kinetics.scram();
+    assert!(point_kinetics.is_scrammed());
+
+    for _ in 0..100 {
+        point_kinetics.advance(reference_temperature, reference_temperature, 
+                               Duration::from_millis(100));
+    }
+
+    assert!(point_kinetics.get_total_reactivity_dollars() < -1.0);
+    assert!(point_kinetics.get_power_watts() < 0.2 * nominal_power_watts);
+    assert!(point_kinetics.get_decay_heat_watts() > 0.04 * nominal_power_watts);
+    assert!(point_kinetics.get_decay_heat_watts() > 
+            0.5 * point_kinetics.get_power_watts());
+
+    // in the facility, the kinetics set the heater power as the
+    // temperatures advance
+    let mut ciet_facility = OwnedCIETFacility::new();
+    ciet_facility.set_thermal_hydraulics_enabled(true);
+    ciet_facility.set_ctah_pump_pressure(Pressure::new::<pascal>(4000.0));
+    ciet_facility.calculate().unwrap();
+
+    ciet_facility.enable_point_kinetics(
+        PointKineticsParameters::ciet_default(), 2000.0);
+
+    approx::assert_relative_eq!(ciet_facility.get_heater_power_watts(), 2000.0,
+                                max_relative = 1e-9);
+
+    ciet_facility.step_transient(Duration::from_secs(1)).unwrap();
+
+    // the loop barely heats up in a second, so the feedback is 
+    // small
+    approx::assert_relative_eq!(ciet_facility.get_heater_power_watts(), 2000.0,
+                                max_relative = 5e-2);
+
+    ciet_facility.get_point_kinetics_mut().unwrap().scram();
+    ciet_facility.step_transient(Duration::from_secs(5)).unwrap();
+
+    assert!(ciet_facility.get_heater_power_watts() < 0.2 * 2000.0);
+
+    // the operator gets the heater back at the last power
+    let scrammed_heater_power_watts = ciet_facility.get_heater_power_watts();
+    ciet_facility.disable_point_kinetics();
+    assert!(ciet_facility.get_point_kinetics().is_none());
+    approx::assert_relative_eq!(ciet_facility.get_heater_power_watts(), 
+                                scrammed_heater_power_watts);
+}
+
+#[test]
+pub fn assert_component_readings_ok(){
+
+    use fluid_mechanics_rust::prelude::*;
+    use crate::{OwnedCIETFacility, OwnedDRACSFacility};
+
+    let mut ciet_facility = OwnedCIETFacility::new();
+    ciet_facility.set_ctah_pump_pressure(Pressure::new::<pascal>(4000.0));
+    ciet_facility.calculate().unwrap();
+
+    let component_readings = ciet_facility.get_component_readings();
+
+    let number_of_components: usize = ciet_facility.get_network().
+        get_branches().iter().
+        map(|branch| branch.get_components().len()).
+        sum();
+
+    assert_eq!(component_readings.len(), number_of_components);
+
+    // every component carries its branch flowrate, and the 
+    // hydrostatic pressure changes add up to that of the branch
+    let branch_mass_flowrates = ciet_facility.get_network().
+        get_branch_mass_flowrates();
+
+    for (branch, branch_mass_flowrate) in ciet_facility.get_network().
+        get_branches().iter().zip(branch_mass_flowrates.iter()) {
+
+        let branch_readings: Vec<_> = component_readings.iter().
+            filter(|component_reading| 
+                   component_reading.branch_name == branch.get_name()).
+            collect();
+
+        assert_eq!(branch_readings.len(), branch.get_components().len());
+
+        let mut hydrostatic_pressure_change_pa = 0.0;
+
+        for component_reading in branch_readings.iter() {
+            assert_eq!(component_reading.mass_flowrate, *branch_mass_flowrate);
+            assert!(component_reading.reynolds_number.is_finite());
+            assert!(component_reading.reynolds_number * 
+                    branch_mass_flowrate.value >= 0.0);
+            assert!(component_reading.velocity_meters_per_second * 
+                    branch_mass_flowrate.value >= 0.0);
+
+            hydrostatic_pressure_change_pa += 
+                component_reading.hydrostatic_pressure_change.value;
+        }
+
+        approx::assert_relative_eq!(
+            hydrostatic_pressure_change_pa, 
+            branch.get_hydrostatic_pressure_change().value,
+            max_relative = 1e-9);
+    }
+
+    // components are named as in the component libraries
+    let pipe_6a_reading = component_readings.iter().
+        find(|component_reading| component_reading.component_name == "pipe_6a").
+        unwrap();
+
+    assert_eq!(pipe_6a_reading.branch_name, "ctah_branch");
+    assert!(pipe_6a_reading.component_length.value > 0.0);
+    assert!(pipe_6a_reading.hydraulic_diameter.value > 0.0);
+
+    assert!(component_readings.iter().any(
+            |component_reading| component_reading.component_name == 
+            "static_mixer_41_label_6"));
+
+    // the DRACS loop is read the same way
+    let dracs_facility = OwnedDRACSFacility::new();
+    let dracs_number_of_components: usize = dracs_facility.get_network().
+        get_branches().iter().
+        map(|branch| branch.get_components().len()).
+        sum();
+
+    assert_eq!(dracs_facility.get_component_readings().len(), 
+               dracs_number_of_components);
+}
+
+#[test]
+pub fn assert_ciet_scenarios_ok(){
+
+    use fluid_mechanics_rust::prelude::*;
+    use crate::examples::{get_ciet_scenarios, get_ciet_scenario, 
+        construct_ciet_facility, DEFAULT_SCENARIO};
+
+    // LoadScenario finds scenarios by name, so names must be 
+    // unique
+    let scenarios = get_ciet_scenarios();
+
+    for (index, scenario) in scenarios.iter().enumerate() {
+        assert!(scenarios.iter().skip(index + 1).
+                all(|other_scenario| other_scenario.name != scenario.name));
+    }
+
+    assert_eq!(get_ciet_scenario("isothermal_rest"), Some(DEFAULT_SCENARIO));
+    assert_eq!(get_ciet_scenario("no_such_scenario"), None);
+
+    // a scenario sets the facility up as an operator would
+    let scenario = get_ciet_scenario("isothermal_forced_circulation").unwrap();
+
+    let mut scenario_facility = construct_ciet_facility();
+    scenario.apply(&mut scenario_facility);
+
+    let (_, scenario_ctah_branch_flowrate, _, _) = 
+        scenario_facility.calculate().unwrap();
+
+    let mut reference_facility = construct_ciet_facility();
+    reference_facility.set_ctah_pump_pressure(
+        Pressure::new::<pascal>(scenario.ctah_pump_pressure_pa));
+
+    let (_, reference_ctah_branch_flowrate, _, _) = 
+        reference_facility.calculate().unwrap();
+
+    approx::assert_relative_eq!(
+        scenario_ctah_branch_flowrate.value, 
+        reference_ctah_branch_flowrate.value,
+        max_relative = 1e-9);
+
+    assert!(scenario_ctah_branch_flowrate.value.abs() > 0.0);
+}
+
+#[test]
+pub fn assert_server_users_ok(){
+
+    use opcua::server::prelude::ServerBuilder;
+    use crate::examples::{ServerSecurity, CIETServerRole};
+
+    let users = ServerSecurity::parse_users(
+        "alice:secret:operator, bob:pass:word:Supervisor,").unwrap();
+
+    assert_eq!(users.len(), 2);
+    assert_eq!(users[0].user_name, "alice");
+    assert_eq!(users[0].role, CIETServerRole::Operator);
+
+    // passwords may contain colons
+    assert_eq!(users[1].password, "pass:word");
+    assert_eq!(users[1].role, CIETServerRole::Supervisor);
+
+    // unknown roles, missing fields and repeated users are 
+    // rejected
+    assert!(ServerSecurity::parse_users("alice:secret:admin").is_err());
+    assert!(ServerSecurity::parse_users("alice:secret").is_err());
+    assert!(ServerSecurity::parse_users("alice::operator").is_err());
+    assert!(ServerSecurity::parse_users(
+            "alice:secret:operator,alice:other:supervisor").is_err());
+
+    let server_security = ServerSecurity {
+        users: users,
+        ..ServerSecurity::default()
+    };
+
+    assert_eq!(server_security.get_user_role("bob"), 
+               Some(CIETServerRole::Supervisor));
+    assert_eq!(server_security.get_user_role("mallory"), None);
+
+    // supervisors may do what operators may, not the other way 
+    // round
+    assert!(CIETServerRole::Supervisor.includes(CIETServerRole::Operator));
+    assert!(!CIETServerRole::Operator.includes(CIETServerRole::Supervisor));
+
+    // the bundled keypair and all three endpoints by default
+    let default_security = ServerSecurity::default();
+    assert!(default_security.none_endpoint);
+    assert!(default_security.sign_endpoint);
+    assert!(default_security.sign_and_encrypt_endpoint);
+    assert_eq!(default_security.pki_dir.join(&default_security.certificate_path), 
+               std::path::PathBuf::from("./pki/own/cert.der"));
+
+    // only operators (and so supervisors) get onto the control 
+    // server, where the controller variables are writable, and 
+    // without them (or without secure endpoints) there is none
+    assert_eq!(server_security.get_controller_writers().len(), 2);
+    assert!(server_security.configure_control_server_builder(
+            ServerBuilder::new(), "/rust_ciet_opcua_server").is_some());
+
+    assert!(default_security.get_controller_writers().is_empty());
+    assert!(default_security.configure_control_server_builder(
+            ServerBuilder::new(), "/rust_ciet_opcua_server").is_none());
+
+    let none_endpoint_only = ServerSecurity {
+        sign_endpoint: false,
+        sign_and_encrypt_endpoint: false,
+        ..server_security.clone()
+    };
+    assert!(none_endpoint_only.configure_control_server_builder(
+            ServerBuilder::new(), "/rust_ciet_opcua_server").is_none());
+}
+
+#[test]
+pub fn assert_server_config_ok(){
+
+    use crate::examples::{CIETServerConfig, CIETServerRole, DEFAULT_PORT,
+        DEFAULT_CONTROL_PORT};
+
+    // missing keys take the defaults
+    let server_config = CIETServerConfig::from_toml_str("").unwrap();
+    assert_eq!(server_config, CIETServerConfig::default());
+    assert_eq!(server_config.port, DEFAULT_PORT);
+    assert_eq!(server_config.control_port, DEFAULT_CONTROL_PORT);
+
+    // the bundled configuration file has the defaults too
+    let bundled_config = CIETServerConfig::from_file(
+        std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("ciet_server.toml").as_path()).
+        unwrap();
+    assert_eq!(bundled_config, CIETServerConfig::default());
+
+    let server_config = CIETServerConfig::from_toml_str(r#"
+        host = "0.0.0.0"
+        port = 4841
+        polling_interval_milliseconds = 250
+        max_ctah_pump_pressure_pa = 30000.0
+
+        [security]
+        none_endpoint = false
+
+        [[security.users]]
+        user_name = "alice"
+        password = "secret"
+        role = "supervisor"
+        "#).unwrap();
+
+    assert_eq!(server_config.get_bind_host(), "0.0.0.0");
+    assert_eq!(server_config.port, 4841);
+    assert_eq!(server_config.get_polling_interval().as_millis(), 250);
+    assert_eq!(server_config.max_ctah_pump_pressure_pa, 30000.0);
+    assert!(!server_config.security.none_endpoint);
+    assert_eq!(server_config.security.get_user_role("alice"), 
+               Some(CIETServerRole::Supervisor));
+
+    // a server bound to every interface is not reached on 0.0.0.0
+    assert!(!server_config.get_endpoint_url().contains("0.0.0.0"));
+    assert!(server_config.get_endpoint_url().ends_with(":4841/rust_ciet_opcua_server"));
+
+    // "auto" never panics, without a network interface it falls 
+    // back to the loopback address
+    assert!(!CIETServerConfig::default().get_bind_host().is_empty());
+
+    // misspelt keys and unusable values are rejected
+    assert!(CIETServerConfig::from_toml_str("prot = 4841").is_err());
+    assert!(CIETServerConfig::from_toml_str("port = 0").is_err());
+    assert!(CIETServerConfig::from_toml_str(
+            &format!("port = {}", DEFAULT_CONTROL_PORT)).is_err());
+    assert!(CIETServerConfig::from_toml_str("polling_interval_milliseconds = 0").is_err());
+    assert!(CIETServerConfig::from_toml_str("endpoint_path = \"no_slash\"").is_err());
+    assert!(CIETServerConfig::from_toml_str("max_ctah_pump_pressure_pa = -1.0").is_err());
+
+    // command line options override the configuration file, 
+    // wherever --config is
+    let config_path = std::env::temp_dir().join("assert_server_config_ok.toml");
+    std::fs::write(&config_path, "host = \"0.0.0.0\"\nport = 4841\n").unwrap();
+
+    let args: Vec<String> = vec![
+        "--port", "4842", 
+        "--config", config_path.to_str().unwrap(), 
+        "--host", "127.0.0.1",
+        "--control-port", "4843",
+    ].into_iter().map(|arg| arg.to_string()).collect();
+
+    let server_config = CIETServerConfig::from_args(args).unwrap();
+
+    assert_eq!(server_config.get_bind_host(), "127.0.0.1");
+    assert_eq!(server_config.port, 4842);
+    assert_eq!(server_config.get_endpoint_url(), 
+               "opc.tcp://127.0.0.1:4842/rust_ciet_opcua_server");
+    assert_eq!(server_config.get_control_endpoint_url(), 
+               "opc.tcp://127.0.0.1:4843/rust_ciet_opcua_server");
+
+    let _ = std::fs::remove_file(&config_path);
+
+    // unknown options, options without values and bad values 
+    // are rejected
+    let bad_args_list: Vec<Vec<&str>> = vec![
+        vec!["--prot", "4842"],
+        vec!["--port"],
+        vec!["--port", "not_a_port"],
+        vec!["--polling-interval-ms", "0"],
+    ];
+
+    for bad_args in bad_args_list {
+        let bad_args: Vec<String> = bad_args.into_iter().
+            map(|arg| arg.to_string()).collect();
+        assert!(CIETServerConfig::from_args(bad_args).is_err());
+    }
+}
+
+#[test]
+pub fn assert_facilities_are_send_ok(){
+
+    use crate::{OwnedCIETFacility, OwnedDRACSFacility, 
+        ParallelBranchNetwork, TherminolBranch};
+
+    // the opcua server moves the facilities into its polling 
+    // threads, the compiler (not an unsafe impl) has to agree 
+    // that this is fine
+    fn assert_send<T: Send>(){}
+
+    assert_send::<TherminolBranch>();
+    assert_send::<ParallelBranchNetwork>();
+    assert_send::<OwnedCIETFacility>();
+    assert_send::<OwnedDRACSFacility>();
+}
+
+#[test]
+pub fn assert_ctah_heat_balance_with_flibe_ok(){
+
+    use fluid_mechanics_rust::prelude::*;
+    use std::sync::Arc;
+    use crate::{CTAHHeatExchanger, OwnedCIETFacility, FluidProperties,
+        TherminolVP1, FLiBe, get_temperature_degrees_c};
+
+    let salt_temperature = ThermodynamicTemperature::new::<degree_celsius>(600.0);
+    let salt_mass_flowrate = MassRate::new::<kilogram_per_second>(0.18);
+
+    let mut ctah = CTAHHeatExchanger::ciet_ctah();
+    ctah.set_fan_speed_rpm(ctah.get_rated_fan_speed_rpm());
+
+    // the heat removed is the heat lost by the salt, at the 
+    // salt's heat capacity
+    let heat_removal_watts = ctah.get_heat_removal_watts(
+        &FLiBe, salt_temperature, salt_mass_flowrate);
+    let outlet_temperature = ctah.get_oil_outlet_temperature(
+        &FLiBe, salt_temperature, salt_mass_flowrate);
+
+    assert!(heat_removal_watts > 0.0);
+    approx::assert_relative_eq!(
+        heat_removal_watts,
+        salt_mass_flowrate.value * 
+        FLiBe.get_specific_heat_capacity_joule_per_kg_kelvin(salt_temperature) * 
+        (get_temperature_degrees_c(salt_temperature) 
+         - get_temperature_degrees_c(outlet_temperature)),
+        max_relative = 1e-9);
+
+    // therminol's heat capacity extrapolated to 600C is much 
+    // higher, so it would give a different answer
+    assert!((ctah.get_oil_outlet_temperature(
+                &TherminolVP1, salt_temperature, salt_mass_flowrate).value 
+             - outlet_temperature.value).abs() > 1.0);
+
+    // the facility's ctah uses the fluid in the loop
+    let mut salt_facility = OwnedCIETFacility::new();
+    salt_facility.set_fluid_properties(Arc::new(FLiBe));
+    salt_facility.set_loop_temperature(salt_temperature);
+    salt_facility.set_ctah_pump_pressure(Pressure::new::<pascal>(4000.0));
+    salt_facility.set_ctah_fan_speed_rpm(1750.0);
+    salt_facility.calculate().unwrap();
+
+    assert_eq!(salt_facility.get_fluid_properties().get_fluid_name(), "flibe");
+
+    approx::assert_relative_eq!(
+        salt_facility.get_ctah_heat_removal_watts(),
+        salt_facility.get_ctah_heat_exchanger().get_heat_removal_watts(
+            &FLiBe, 
+            salt_facility.get_ctah_inlet_temperature(),
+            salt_facility.get_ctah_branch_mass_flowrate()),
+        max_relative = 1e-12);
+}
+
 extern crate fluid_mechanics_rust;
 use fluid_mechanics_rust::prelude::*;
 